@@ -0,0 +1,27 @@
+#import bevy_pbr::mesh_view_bindings
+
+struct GridMaterial {
+    color: vec4<f32>,
+    spacing: f32,
+    thickness: f32,
+    fade_distance: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: GridMaterial;
+
+@fragment
+fn fragment(
+    #import bevy_pbr::mesh_vertex_output
+) -> @location(0) vec4<f32> {
+    // Distance to the closest line in pixels, from screen space derivatives
+    let coord = world_position.xz / material.spacing;
+    let lines = abs(fract(coord - 0.5) - 0.5) / fwidth(coord);
+    let line = 1.0 - min(min(lines.x, lines.y) / material.thickness, 1.0);
+
+    // Fade out with distance from the camera
+    let distance = length(world_position.xyz - view.world_position.xyz);
+    let fade = 1.0 - smoothstep(0.0, material.fade_distance, distance);
+
+    return vec4<f32>(material.color.rgb, material.color.a * line * fade);
+}
@@ -856,6 +856,18 @@ where
         // NOTE: This code is a bit more involved than it needs to be because egui
         // does not support drawing rectangles with asymmetrical round corners.
 
+        let dimmed =
+            self.graph[self.node_id]
+                .user_data
+                .dimmed(self.node_id, self.graph, user_state);
+        let dim = |color: Color32| {
+            if dimmed {
+                color.linear_multiply(0.25)
+            } else {
+                color
+            }
+        };
+
         let (shape, outline) = {
             let rounding_radius = 2.0;
             let rounding = Rounding::same(rounding_radius);
@@ -866,10 +878,10 @@ where
             let titlebar = Shape::Rect(RectShape {
                 rect: titlebar_rect,
                 rounding,
-                fill: self.graph[self.node_id]
+                fill: dim(self.graph[self.node_id]
                     .user_data
                     .titlebar_color(ui, self.node_id, self.graph, user_state)
-                    .unwrap_or_else(|| background_color.lighten(0.8)),
+                    .unwrap_or_else(|| background_color.lighten(0.8))),
                 stroke: Stroke::NONE,
             });
 
@@ -880,7 +892,7 @@ where
             let body = Shape::Rect(RectShape {
                 rect: body_rect,
                 rounding: Rounding::none(),
-                fill: background_color,
+                fill: dim(background_color),
                 stroke: Stroke::NONE,
             });
 
@@ -891,7 +903,7 @@ where
             let bottom_body = Shape::Rect(RectShape {
                 rect: bottom_body_rect,
                 rounding,
-                fill: background_color,
+                fill: dim(background_color),
                 stroke: Stroke::NONE,
             });
 
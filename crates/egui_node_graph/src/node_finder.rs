@@ -71,6 +71,11 @@ where
                 let max_height = ui.input(|i| i.screen_rect.height() * 0.5);
                 let scroll_area_width = resp.rect.width() - 30.0;
 
+                let searched_kinds = if self.query.is_empty() {
+                    None
+                } else {
+                    all_kinds.search(&self.query)
+                };
                 let all_kinds = all_kinds.all_kinds();
                 let mut categories: BTreeMap<String, Vec<&NodeTemplate>> = Default::default();
                 let mut orphan_kinds = Vec::new();
@@ -94,6 +99,22 @@ where
                             .max_height(max_height)
                             .show(ui, |ui| {
                                 ui.set_width(scroll_area_width);
+
+                                // Ranked search results replace the categories
+                                if let Some(kinds) = searched_kinds {
+                                    for kind in kinds {
+                                        let kind_name =
+                                            kind.node_finder_label(user_state).to_string();
+                                        if ui.selectable_label(false, kind_name).clicked() {
+                                            submitted_archetype = Some(kind);
+                                        } else if query_submit {
+                                            submitted_archetype = Some(kind);
+                                            query_submit = false;
+                                        }
+                                    }
+                                    return;
+                                }
+
                                 for (category, kinds) in categories {
                                     let filtered_kinds: Vec<_> = kinds
                                         .into_iter()
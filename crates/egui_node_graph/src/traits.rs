@@ -205,6 +205,18 @@ where
     ) -> bool {
         true
     }
+
+    /// Draw the node faded out, e.g. to focus on a different part of the graph.
+    ///
+    /// Default implementation never dims nodes.
+    fn dimmed(
+        &self,
+        _node_id: NodeId,
+        _graph: &Graph<Self, Self::DataType, Self::ValueType>,
+        _user_state: &mut Self::UserState,
+    ) -> bool {
+        false
+    }
 }
 
 /// This trait can be implemented by any user type. The trait tells the library
@@ -213,6 +225,12 @@ where
 pub trait NodeTemplateIter {
     type Item;
     fn all_kinds(&self) -> Vec<Self::Item>;
+
+    /// The node templates matching a node finder query, best matches first.
+    /// When `None`, the node finder filters templates by their label instead.
+    fn search(&self, _query: &str) -> Option<Vec<Self::Item>> {
+        None
+    }
 }
 
 /// Describes a category of nodes.
@@ -0,0 +1,118 @@
+use crate::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How to add and remove a registered component
+pub struct BehaviorComponent {
+    pub insert: Box<dyn Fn(&mut Commands, Entity) + Send + Sync>,
+    pub remove: Box<dyn Fn(&mut Commands, Entity) + Send + Sync>,
+}
+
+/// Components available to AddComponent and RemoveComponent nodes, by name
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct BehaviorComponents(pub HashMap<Cow<'static, str>, BehaviorComponent>);
+
+impl BehaviorComponents {
+    /// Register a component inserted with its default value
+    pub fn register<C: Component + Default>(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.register_fn(
+            name,
+            |commands, entity| {
+                commands.entity(entity).insert(C::default());
+            },
+            |commands, entity| {
+                commands.entity(entity).remove::<C>();
+            },
+        );
+    }
+
+    pub fn register_fn(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        insert: impl Fn(&mut Commands, Entity) + Send + Sync + 'static,
+        remove: impl Fn(&mut Commands, Entity) + Send + Sync + 'static,
+    ) {
+        self.0.insert(
+            name.into(),
+            BehaviorComponent {
+                insert: Box::new(insert),
+                remove: Box::new(remove),
+            },
+        );
+    }
+}
+
+/// Add a registered component to the agent.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct AddComponent {
+    #[serde(default)]
+    pub component: BehaviorPropStr,
+}
+
+impl BehaviorSpec for AddComponent {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "AddComponent";
+    const ICON: &'static str = "⊕";
+    const DESC: &'static str = "Add a registered component to the agent, fails if the component \
+    is not registered";
+}
+
+impl BehaviorUI for AddComponent {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, component, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, component, state, ui, type_registry);
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut adds: Query<(Entity, &mut AddComponent, &BehaviorNode), BehaviorRunQuery>,
+    components: Res<BehaviorComponents>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut add, node) in &mut adds {
+        if let BehaviorPropValue::None = add.component.value {
+            let result = add.component.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(name) = &add.component.value {
+            match components.get(name.as_ref()) {
+                Some(component) => {
+                    (component.insert)(&mut commands, node.tree);
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                None => {
+                    error!("Component not registered: {}", name);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
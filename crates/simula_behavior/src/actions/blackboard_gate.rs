@@ -0,0 +1,142 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_script::script::{Dynamic, ImmutableString};
+
+/// A typed blackboard value, as expected by a BlackboardGate
+#[derive(Debug, Reflect, FromReflect, Clone, PartialEq, Deserialize, Serialize)]
+pub enum BlackboardValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl Default for BlackboardValue {
+    fn default() -> Self {
+        Self::Bool(true)
+    }
+}
+
+impl BlackboardValue {
+    /// Whether a blackboard value equals this one. Floats also match equal integers.
+    pub fn matches(&self, value: &Dynamic) -> bool {
+        let value = value.clone();
+        match self {
+            Self::Bool(expected) => value.try_cast::<bool>() == Some(*expected),
+            Self::Int(expected) => value.try_cast::<i64>() == Some(*expected),
+            Self::Float(expected) => match value.clone().try_cast::<f64>() {
+                Some(value) => value == *expected,
+                None => value.try_cast::<i64>().map(|value| value as f64) == Some(*expected),
+            },
+            Self::Str(expected) => value
+                .try_cast::<ImmutableString>()
+                .map_or(false, |value| value.as_str() == expected),
+        }
+    }
+}
+
+impl From<BlackboardValue> for Dynamic {
+    fn from(value: BlackboardValue) -> Self {
+        match value {
+            BlackboardValue::Bool(value) => Dynamic::from(value),
+            BlackboardValue::Int(value) => Dynamic::from(value),
+            BlackboardValue::Float(value) => Dynamic::from(value),
+            BlackboardValue::Str(value) => Dynamic::from(value),
+        }
+    }
+}
+
+/// Succeed if a blackboard key equals an expected value, fail otherwise, or if the key is
+/// missing. Use it to gate a branch on a flag or a state written by other nodes.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct BlackboardGate {
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+    #[serde(default)]
+    pub value: BlackboardValue,
+    #[serde(skip)]
+    pub current: Option<String>,
+}
+
+impl BehaviorSpec for BlackboardGate {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "BlackboardGate";
+    const ICON: &'static str = "🚧";
+    const DESC: &'static str = "Succeed if a blackboard key equals an expected value, fail \
+    otherwise";
+}
+
+impl BehaviorUI for BlackboardGate {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("value");
+                reflect_ui(self.value.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+        property_ui_readonly!(self, value, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, current, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut gates: Query<(Entity, &mut BlackboardGate, &BehaviorNode), BehaviorRunQuery>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut gate, node) in &mut gates {
+        if let BehaviorPropValue::None = gate.key.value {
+            let result = gate.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(key) = gate.key.value.clone() {
+            let value = match scripts.get_blackboard(node, &key) {
+                Ok(value) => value,
+                Err(err) => {
+                    error!("Cannot read {}: {}", key, err);
+                    None
+                }
+            };
+            gate.current = value.as_ref().map(|value| value.to_string());
+            if value.map_or(false, |value| gate.value.matches(&value)) {
+                commands.entity(entity).insert(BehaviorSuccess);
+            } else {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::{egui, prelude::*};
+use serde::{Deserialize, Serialize};
+use simula_script::Script;
+use std::borrow::Cow;
+
+/// Evaluate a script expression and store the result in a blackboard key.
+///
+/// Blackboard values are available to the expression as variables, so `dist < 5.0` reads the
+/// `dist` key. The expression is compiled once, on first run.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Compute {
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+    #[serde(default)]
+    pub expression: Cow<'static, str>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    pub handle: Option<Handle<Script>>,
+    #[serde(skip)]
+    pub result: Option<String>,
+}
+
+impl BehaviorSpec for Compute {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "Compute";
+    const ICON: &'static str = "ƒ";
+    const DESC: &'static str = "Evaluate an expression, with blackboard values as variables, \
+    and store the result in a blackboard key";
+}
+
+impl BehaviorUI for Compute {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        let mut expression = self.expression.to_string();
+        let response = ui
+            .horizontal(|ui| {
+                ui.label("expression");
+                ui.add(
+                    egui::TextEdit::multiline(&mut expression)
+                        .desired_rows(1)
+                        .code_editor(),
+                )
+            })
+            .inner;
+        if response.changed() {
+            self.expression = expression.into();
+            self.handle = None;
+            changed = true;
+        }
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+        ui.horizontal(|ui| {
+            ui.label("expression");
+            ui.code(self.expression.as_ref());
+        });
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, result, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut computes: Query<(Entity, &mut Compute, &BehaviorNode), BehaviorRunQuery>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut compute, node) in &mut computes {
+        if let BehaviorPropValue::None = compute.key.value {
+            let result = compute.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        // Compiled scripts are kept across runs
+        if compute.handle.is_none() {
+            match scripts.compile(node, compute.expression.clone()) {
+                Ok(handle) => compute.handle = Some(handle),
+                Err(err) => {
+                    error!("Cannot compile {}: {}", compute.expression, err);
+                    commands
+                        .entity(entity)
+                        .insert((BehaviorFailure, BehaviorOutcome::reason(err)));
+                    continue;
+                }
+            }
+        }
+
+        if let (BehaviorPropValue::Some(key), Some(handle)) =
+            (compute.key.value.clone(), compute.handle.clone())
+        {
+            let result = scripts
+                .eval_with_blackboard(node, &handle)
+                .and_then(|value| {
+                    compute.result = Some(value.to_string());
+                    scripts.set_blackboard(node, &key, value)
+                });
+            match result {
+                Ok(_) => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                Err(err) => {
+                    error!("Cannot compute {}: {}", key, err);
+                    commands
+                        .entity(entity)
+                        .insert((BehaviorFailure, BehaviorOutcome::reason(err)));
+                }
+            }
+        }
+    }
+}
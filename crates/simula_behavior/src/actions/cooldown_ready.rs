@@ -0,0 +1,107 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Succeed if the time stamped in a blackboard key is older than a duration.
+///
+/// The key is shared with a StampNow action, which writes the time an ability was used.
+/// A key that was never stamped is considered ready. Each ability uses its own key.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct CooldownReady {
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+    #[serde(default)]
+    pub duration: BehaviorPropGeneric<f64>,
+}
+
+impl BehaviorSpec for CooldownReady {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "CooldownReady";
+    const ICON: &'static str = "⏲";
+    const DESC: &'static str = "Succeed if the time stamped in a blackboard key is older than \
+    a duration, fail otherwise";
+}
+
+impl BehaviorUI for CooldownReady {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        changed |= behavior_ui!(self, duration, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+        behavior_ui_readonly!(self, duration, state, ui, type_registry);
+    }
+}
+
+pub fn run(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut cooldowns: Query<(Entity, &mut CooldownReady, &BehaviorNode), BehaviorRunQuery>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut cooldown, node) in &mut cooldowns {
+        if let BehaviorPropValue::None = cooldown.key.value {
+            let result = cooldown.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = cooldown.duration.value {
+            let result = cooldown.duration.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(key), BehaviorPropValue::Some(duration)) =
+            (&cooldown.key.value, &cooldown.duration.value)
+        {
+            let stamp = match scripts.get_blackboard(node, key) {
+                Ok(stamp) => stamp,
+                Err(err) => {
+                    error!("Cannot read {}: {}", key, err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                    continue;
+                }
+            };
+            let ready = match stamp.map(|stamp| stamp.try_cast::<f64>()) {
+                // never stamped, ready
+                None => true,
+                Some(Some(stamp)) => time.elapsed_seconds_f64() - stamp >= *duration,
+                Some(None) => {
+                    error!("Blackboard key {} is not a time stamp", key);
+                    false
+                }
+            };
+            if ready {
+                commands.entity(entity).insert(BehaviorSuccess);
+            } else {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
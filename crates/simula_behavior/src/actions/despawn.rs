@@ -0,0 +1,75 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Despawn a targeted entity.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Despawn {
+    #[serde(default)]
+    pub target: BehaviorPropGeneric<i64>,
+}
+
+impl BehaviorSpec for Despawn {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "Despawn";
+    const ICON: &'static str = "✖";
+    const DESC: &'static str = "Despawn the target entity, fails if the entity does not exist";
+}
+
+impl BehaviorUI for Despawn {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, target, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, target, state, ui, type_registry);
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut despawns: Query<(Entity, &mut Despawn, &BehaviorNode), BehaviorRunQuery>,
+    targets: Query<Entity, Without<BehaviorNode>>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut despawn, node) in &mut despawns {
+        if let BehaviorPropValue::None = despawn.target.value {
+            let result = despawn.target.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(target) = despawn.target.value.clone() {
+            let target = Entity::from_bits(target as u64);
+            // Never despawn the agent running this tree, or behavior nodes
+            if target != node.tree && targets.get(target).is_ok() {
+                commands.entity(target).despawn_recursive();
+                commands.entity(entity).insert(BehaviorSuccess);
+            } else {
+                error!("Invalid despawn target: {:?}", target);
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How a measured distance is compared to the threshold
+#[derive(Debug, Default, Reflect, FromReflect, Clone, PartialEq, Deserialize, Serialize)]
+pub enum DistanceComparator {
+    #[default]
+    Less,
+    Greater,
+}
+
+/// Succeed if the distance from the agent to a target entity compares to a threshold.
+///
+/// The target entity is read from a blackboard key, as stored by a Spawn action.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct DistanceCheck {
+    #[serde(default)]
+    pub target: BehaviorPropStr,
+    #[serde(default)]
+    pub threshold: BehaviorPropGeneric<f64>,
+    #[serde(default)]
+    pub comparator: DistanceComparator,
+    #[serde(skip)]
+    pub distance: Option<f32>,
+}
+
+impl BehaviorSpec for DistanceCheck {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "DistanceCheck";
+    const ICON: &'static str = "↔";
+    const DESC: &'static str = "Succeed if the distance from the agent to the target entity in \
+    a blackboard key is less or greater than a threshold, fail otherwise";
+}
+
+impl BehaviorUI for DistanceCheck {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, target, state, ui, type_registry);
+        changed |= behavior_ui!(self, threshold, state, ui, type_registry);
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("comparator");
+                reflect_ui(self.comparator.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, target, state, ui, type_registry);
+        behavior_ui_readonly!(self, threshold, state, ui, type_registry);
+        property_ui_readonly!(self, comparator, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, distance, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut checks: Query<(Entity, &mut DistanceCheck, &BehaviorNode), BehaviorRunQuery>,
+    transforms: Query<&GlobalTransform>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut check, node) in &mut checks {
+        if let BehaviorPropValue::None = check.target.value {
+            let result = check.target.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = check.threshold.value {
+            let result = check.threshold.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(key), BehaviorPropValue::Some(threshold)) =
+            (check.target.value.clone(), check.threshold.value.clone())
+        {
+            let target = match scripts.get_blackboard(node, &key) {
+                Ok(Some(target)) => target.try_cast::<i64>(),
+                Ok(None) => None,
+                Err(err) => {
+                    error!("Cannot read {}: {}", key, err);
+                    None
+                }
+            };
+            let Some(target) = target else {
+                error!("Blackboard key {} is not an entity", key);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+            let target = Entity::from_bits(target as u64);
+
+            let positions = (transforms.get(node.tree), transforms.get(target));
+            let (Ok(agent), Ok(target)) = positions else {
+                error!("Agent or target {:?} has no transform", target);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+
+            let distance = agent.translation().distance(target.translation());
+            check.distance = Some(distance);
+            let pass = match check.comparator {
+                DistanceComparator::Less => (distance as f64) < threshold,
+                DistanceComparator::Greater => (distance as f64) > threshold,
+            };
+            if pass {
+                commands.entity(entity).insert(BehaviorSuccess);
+            } else {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
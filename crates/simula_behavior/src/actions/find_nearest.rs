@@ -0,0 +1,160 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::{
+    ecs::{archetype::Archetypes, component::Components, entity::Entities},
+    prelude::*,
+};
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Find the entity with a tag component nearest to the agent, and write it to a blackboard
+/// key, as read by DistanceCheck and other targeting nodes. Fail if there is none in range.
+///
+/// The tag is a registered component type name, e.g. `Enemy`. Distances are measured between
+/// origins, and equally near candidates are broken by the lowest entity, so picks are stable.
+/// Every entity with a transform is checked, so keep counts modest.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct FindNearest {
+    #[serde(default)]
+    pub tag: BehaviorPropStr,
+    #[serde(default)]
+    pub range: Option<f32>,
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+    #[serde(skip)]
+    pub distance: Option<f32>,
+}
+
+impl BehaviorSpec for FindNearest {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "FindNearest";
+    const ICON: &'static str = "⌖";
+    const DESC: &'static str = "Write the entity with a tag component nearest to the agent to \
+    a blackboard key, fail if none is within range";
+}
+
+impl BehaviorUI for FindNearest {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, tag, state, ui, type_registry);
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("range");
+                reflect_ui(self.range.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, tag, state, ui, type_registry);
+        property_ui_readonly!(self, range, state, ui, type_registry);
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, distance, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    mut commands: Commands,
+    mut finds: Query<(Entity, &mut FindNearest, &BehaviorNode), BehaviorRunQuery>,
+    transforms: Query<(Entity, &GlobalTransform)>,
+    type_registry: Res<AppTypeRegistry>,
+    entities: &Entities,
+    archetypes: &Archetypes,
+    components: &Components,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut find, node) in &mut finds {
+        if let BehaviorPropValue::None = find.tag.value {
+            let result = find.tag.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = find.key.value {
+            let result = find.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(tag), BehaviorPropValue::Some(key)) =
+            (find.tag.value.clone(), find.key.value.clone())
+        {
+            let component_id = {
+                let type_registry = type_registry.read();
+                type_registry
+                    .get_with_short_name(&tag)
+                    .or_else(|| type_registry.get_with_name(&tag))
+                    .and_then(|registration| components.get_id(registration.type_id()))
+            };
+            let Some(component_id) = component_id else {
+                error!("Unknown tag component {}", tag);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+            let Ok((_, agent)) = transforms.get(node.tree) else {
+                error!("Agent {:?} has no transform", node.tree);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+
+            let origin = agent.translation();
+            let range = find.range.unwrap_or(f32::INFINITY);
+            let nearest = transforms
+                .iter()
+                .filter(|(candidate, _)| *candidate != node.tree)
+                .filter(|(candidate, _)| {
+                    entities.get(*candidate).map_or(false, |location| {
+                        archetypes[location.archetype_id].contains(component_id)
+                    })
+                })
+                .map(|(candidate, transform)| (candidate, transform.translation().distance(origin)))
+                .filter(|(_, distance)| *distance <= range)
+                .min_by(|(a, a_distance), (b, b_distance)| {
+                    a_distance.total_cmp(b_distance).then(a.cmp(b))
+                });
+            find.distance = nearest.map(|(_, distance)| distance);
+
+            let Some((nearest, _)) = nearest else {
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+            match scripts.set_blackboard(node, &key, (nearest.to_bits() as i64).into()) {
+                Ok(_) => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                Err(err) => {
+                    error!("Cannot write {}: {}", key, err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_action::Action;
+use std::hash::Hash;
+
+/// Names of the `simula_action` actions currently on, by action type, as read by `InputPressed`.
+///
+/// An action is named by its `Debug` form, so `KeyCode::Space` is "Space" and a mapped
+/// `MyAction::Jump` is "Jump". Keyboard keys and mouse buttons are collected by the plugin,
+/// other action types need their own `collect::<T>` system, added after their `action_map`.
+#[derive(Debug, Default, Resource)]
+pub struct BehaviorInputs {
+    pub on: HashMap<String, HashSet<String>>,
+}
+
+impl BehaviorInputs {
+    /// Check if an action is on. A name like "Space" matches any action type, while one
+    /// qualified with its type, like "KeyCode::Space", only matches that type.
+    pub fn is_on(&self, name: &str) -> bool {
+        match name.rsplit_once("::") {
+            Some((type_name, name)) => self
+                .on
+                .get(type_name)
+                .map_or(false, |names| names.contains(name)),
+            None => self.on.values().any(|names| names.contains(name)),
+        }
+    }
+}
+
+/// Collect the actions of a type currently on, from every `Action<T>` component
+pub fn collect<T>(actions: Query<&Action<T>>, mut inputs: ResMut<BehaviorInputs>)
+where
+    T: Copy + Eq + Hash + std::fmt::Debug + Send + Sync + 'static,
+{
+    let names = actions
+        .iter()
+        .flat_map(|action| action.get_on())
+        .map(|action| format!("{:?}", action))
+        .collect();
+    inputs
+        .on
+        .insert(pretty_type_name::pretty_type_name::<T>(), names);
+}
+
+/// Succeed if a named input action is currently on, fail otherwise. Lets player or debug
+/// input drive branches of a tree. See `BehaviorInputs` for how actions are named.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct InputPressed {
+    #[serde(default)]
+    pub action: BehaviorPropStr,
+    #[serde(skip)]
+    pub pressed: Option<bool>,
+}
+
+impl BehaviorSpec for InputPressed {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "InputPressed";
+    const ICON: &'static str = "🎮";
+    const DESC: &'static str = "Succeed if a named input action is currently on, fail otherwise";
+}
+
+impl BehaviorUI for InputPressed {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, action, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, action, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, pressed, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut inputs: Query<(Entity, &mut InputPressed, &BehaviorNode), BehaviorRunQuery>,
+    behavior_inputs: Res<BehaviorInputs>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut input, node) in &mut inputs {
+        if let BehaviorPropValue::None = input.action.value {
+            let result = input.action.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(action) = input.action.value.clone() {
+            let pressed = behavior_inputs.is_on(&action);
+            input.pressed = Some(pressed);
+            if pressed {
+                commands.entity(entity).insert(BehaviorSuccess);
+            } else {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
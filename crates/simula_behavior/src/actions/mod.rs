@@ -1,5 +1,39 @@
+pub mod add_component;
+pub mod blackboard_gate;
+pub mod compute;
+pub mod cooldown_ready;
 pub mod debug;
+pub mod despawn;
+pub mod distance_check;
+pub mod find_nearest;
+pub mod input_pressed;
+pub mod overlap;
+pub mod remove_component;
+pub mod script_condition;
+pub mod spawn;
+pub mod stamp_now;
+pub mod tween;
 pub mod wait;
+pub mod wait_for_signal;
+pub mod wander;
+pub mod yield_tick;
 
+pub use add_component::{AddComponent, BehaviorComponent, BehaviorComponents};
+pub use blackboard_gate::{BlackboardGate, BlackboardValue};
+pub use compute::Compute;
+pub use cooldown_ready::CooldownReady;
 pub use debug::Debug;
+pub use despawn::Despawn;
+pub use distance_check::{DistanceCheck, DistanceComparator};
+pub use find_nearest::FindNearest;
+pub use input_pressed::{BehaviorInputs, InputPressed};
+pub use overlap::{Overlap, OverlapShape};
+pub use remove_component::RemoveComponent;
+pub use script_condition::ScriptCondition;
+pub use spawn::{BehaviorSpawner, BehaviorSpawners, Spawn};
+pub use stamp_now::StampNow;
+pub use tween::Tween;
 pub use wait::Wait;
+pub use wait_for_signal::{BehaviorSignal, WaitForSignal};
+pub use wander::Wander;
+pub use yield_tick::Yield;
@@ -0,0 +1,194 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::{
+    ecs::{archetype::Archetypes, component::Components, entity::Entities},
+    prelude::*,
+};
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Area tested by an Overlap node, relative to the agent
+#[derive(Debug, Reflect, FromReflect, Clone, PartialEq, Deserialize, Serialize)]
+pub enum OverlapShape {
+    Sphere { radius: f32 },
+    Box { half_extents: Vec3 },
+}
+
+impl Default for OverlapShape {
+    fn default() -> Self {
+        Self::Sphere { radius: 1.0 }
+    }
+}
+
+impl OverlapShape {
+    /// Whether a point, in the shape local space, is inside the shape
+    pub fn contains(&self, point: Vec3) -> bool {
+        match self {
+            OverlapShape::Sphere { radius } => point.length() <= *radius,
+            OverlapShape::Box { half_extents } => point.abs().cmple(*half_extents).all(),
+        }
+    }
+}
+
+/// Succeed if any entity with a tag component is inside an area around the agent, and write
+/// the nearest one to a blackboard key. Fail if none is.
+///
+/// The tag is a registered component type name, e.g. `Enemy`. The area is placed at `offset`
+/// and oriented in the agent space, and candidates are tested by their origin. Every entity
+/// with a transform is checked, so keep counts modest.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Overlap {
+    #[serde(default)]
+    pub tag: BehaviorPropStr,
+    #[serde(default)]
+    pub shape: OverlapShape,
+    #[serde(default)]
+    pub offset: Vec3,
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+    #[serde(skip)]
+    pub hits: Option<usize>,
+}
+
+impl BehaviorSpec for Overlap {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "Overlap";
+    const ICON: &'static str = "◎";
+    const DESC: &'static str = "Succeed if any entity with a tag component is inside an area \
+    around the agent, writing the nearest to a blackboard key, fail otherwise";
+}
+
+impl BehaviorUI for Overlap {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, tag, state, ui, type_registry);
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("shape");
+                reflect_ui(self.shape.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("offset");
+                reflect_ui(self.offset.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, tag, state, ui, type_registry);
+        property_ui_readonly!(self, shape, state, ui, type_registry);
+        property_ui_readonly!(self, offset, state, ui, type_registry);
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, hits, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    mut commands: Commands,
+    mut overlaps: Query<(Entity, &mut Overlap, &BehaviorNode), BehaviorRunQuery>,
+    transforms: Query<(Entity, &GlobalTransform)>,
+    type_registry: Res<AppTypeRegistry>,
+    entities: &Entities,
+    archetypes: &Archetypes,
+    components: &Components,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut overlap, node) in &mut overlaps {
+        if let BehaviorPropValue::None = overlap.tag.value {
+            let result = overlap.tag.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = overlap.key.value {
+            let result = overlap.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(tag), BehaviorPropValue::Some(key)) =
+            (overlap.tag.value.clone(), overlap.key.value.clone())
+        {
+            let component_id = {
+                let type_registry = type_registry.read();
+                type_registry
+                    .get_with_short_name(&tag)
+                    .or_else(|| type_registry.get_with_name(&tag))
+                    .and_then(|registration| components.get_id(registration.type_id()))
+            };
+            let Some(component_id) = component_id else {
+                error!("Unknown tag component {}", tag);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+            let Ok((_, agent)) = transforms.get(node.tree) else {
+                error!("Agent {:?} has no transform", node.tree);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+
+            // Candidates in the shape space
+            let to_shape = agent.affine().inverse();
+            let mut hits = transforms
+                .iter()
+                .filter(|(candidate, _)| *candidate != node.tree)
+                .filter(|(candidate, _)| {
+                    entities.get(*candidate).map_or(false, |location| {
+                        archetypes[location.archetype_id].contains(component_id)
+                    })
+                })
+                .map(|(candidate, transform)| {
+                    let point = to_shape.transform_point3(transform.translation()) - overlap.offset;
+                    (candidate, point)
+                })
+                .filter(|(_, point)| overlap.shape.contains(*point))
+                .collect::<Vec<_>>();
+            hits.sort_by(|(_, a), (_, b)| a.length().total_cmp(&b.length()));
+            overlap.hits = Some(hits.len());
+
+            let Some((hit, _)) = hits.first() else {
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+            match scripts.set_blackboard(node, &key, (hit.to_bits() as i64).into()) {
+                Ok(_) => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                Err(err) => {
+                    error!("Cannot write {}: {}", key, err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Remove a registered component from the agent.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct RemoveComponent {
+    #[serde(default)]
+    pub component: BehaviorPropStr,
+}
+
+impl BehaviorSpec for RemoveComponent {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "RemoveComponent";
+    const ICON: &'static str = "⊖";
+    const DESC: &'static str = "Remove a registered component from the agent, fails if the \
+    component is not registered";
+}
+
+impl BehaviorUI for RemoveComponent {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, component, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, component, state, ui, type_registry);
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut removes: Query<(Entity, &mut RemoveComponent, &BehaviorNode), BehaviorRunQuery>,
+    components: Res<BehaviorComponents>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut remove, node) in &mut removes {
+        if let BehaviorPropValue::None = remove.component.value {
+            let result = remove.component.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(name) = &remove.component.value {
+            match components.get(name.as_ref()) {
+                Some(component) => {
+                    (component.remove)(&mut commands, node.tree);
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                None => {
+                    error!("Component not registered: {}", name);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
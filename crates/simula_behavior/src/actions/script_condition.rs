@@ -0,0 +1,130 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::{egui, prelude::*};
+use serde::{Deserialize, Serialize};
+use simula_script::Script;
+use std::borrow::Cow;
+
+/// Evaluate a script expression, succeed if it returns `true` and fail otherwise.
+///
+/// Like `Compute`, blackboard values are available to the expression as variables, so
+/// `x > 3` reads the `x` key. Script errors, or a result that is not a boolean, fail with a
+/// warning. The expression is compiled once, on first run.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct ScriptCondition {
+    #[serde(default)]
+    pub expression: Cow<'static, str>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    pub handle: Option<Handle<Script>>,
+    #[serde(skip)]
+    pub result: Option<String>,
+}
+
+impl BehaviorSpec for ScriptCondition {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "ScriptCondition";
+    const ICON: &'static str = "❔";
+    const DESC: &'static str = "Evaluate an expression, with blackboard values as variables, \
+    and succeed if it is true, fail otherwise";
+}
+
+impl BehaviorUI for ScriptCondition {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        _state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        _type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        let mut expression = self.expression.to_string();
+        let response = ui
+            .horizontal(|ui| {
+                ui.label("expression");
+                ui.add(
+                    egui::TextEdit::multiline(&mut expression)
+                        .desired_rows(1)
+                        .code_editor(),
+                )
+            })
+            .inner;
+        if response.changed() {
+            self.expression = expression.into();
+            self.handle = None;
+            changed = true;
+        }
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label("expression");
+            ui.code(self.expression.as_ref());
+        });
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, result, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut conditions: Query<(Entity, &mut ScriptCondition, &BehaviorNode), BehaviorRunQuery>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut condition, node) in &mut conditions {
+        // Compiled scripts are kept across runs
+        if condition.handle.is_none() {
+            match scripts.compile(node, condition.expression.clone()) {
+                Ok(handle) => condition.handle = Some(handle),
+                Err(err) => {
+                    warn!("Cannot compile {}: {}", condition.expression, err);
+                    commands
+                        .entity(entity)
+                        .insert((BehaviorFailure, BehaviorOutcome::reason(err)));
+                    continue;
+                }
+            }
+        }
+
+        let Some(handle) = condition.handle.clone() else {
+            continue;
+        };
+        let result = scripts
+            .eval_with_blackboard(node, &handle)
+            .and_then(|value| {
+                condition.result = Some(value.to_string());
+                let type_name = value.type_name();
+                value
+                    .try_cast::<bool>()
+                    .ok_or_else(|| format!("Expected a boolean, got {}", type_name))
+            });
+        match result {
+            Ok(true) => {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            Ok(false) => {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+            Err(err) => {
+                warn!("Cannot evaluate {}: {}", condition.expression, err);
+                commands
+                    .entity(entity)
+                    .insert((BehaviorFailure, BehaviorOutcome::reason(err)));
+            }
+        }
+    }
+}
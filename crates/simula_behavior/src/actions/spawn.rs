@@ -0,0 +1,186 @@
+use crate::prelude::*;
+use crate::property_ui_readonly;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How to instantiate a registered prefab
+pub enum BehaviorSpawner {
+    /// Spawn with a closure, returns the spawned entity
+    Func(Box<dyn Fn(&mut Commands, Transform) -> Entity + Send + Sync>),
+    /// Spawn a scene
+    Scene(Handle<Scene>),
+}
+
+/// Prefabs available to Spawn nodes, by name
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct BehaviorSpawners(pub HashMap<Cow<'static, str>, BehaviorSpawner>);
+
+impl BehaviorSpawners {
+    pub fn register_fn(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        func: impl Fn(&mut Commands, Transform) -> Entity + Send + Sync + 'static,
+    ) {
+        self.0
+            .insert(name.into(), BehaviorSpawner::Func(Box::new(func)));
+    }
+
+    pub fn register_scene(&mut self, name: impl Into<Cow<'static, str>>, scene: Handle<Scene>) {
+        self.0.insert(name.into(), BehaviorSpawner::Scene(scene));
+    }
+
+    fn spawn(&self, name: &str, commands: &mut Commands, transform: Transform) -> Option<Entity> {
+        match self.0.get(name)? {
+            BehaviorSpawner::Func(func) => Some(func(commands, transform)),
+            BehaviorSpawner::Scene(scene) => Some(
+                commands
+                    .spawn(SceneBundle {
+                        scene: scene.clone(),
+                        transform,
+                        ..default()
+                    })
+                    .id(),
+            ),
+        }
+    }
+}
+
+/// Spawn a registered prefab at the agent position.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Spawn {
+    #[serde(default)]
+    pub prefab: BehaviorPropStr,
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+    #[serde(skip)]
+    pub spawned: Option<Entity>,
+}
+
+impl BehaviorSpec for Spawn {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "Spawn";
+    const ICON: &'static str = "✚";
+    const DESC: &'static str = "Spawn a registered prefab at the agent position, and store the \
+    spawned entity in the blackboard key if set";
+}
+
+impl BehaviorUI for Spawn {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, prefab, state, ui, type_registry);
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, prefab, state, ui, type_registry);
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, spawned, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut spawns: Query<
+        (Entity, &mut Spawn, &BehaviorNode, Option<&BehaviorStarted>),
+        BehaviorRunQuery,
+    >,
+    transforms: Query<&GlobalTransform>,
+    entities: Query<Entity>,
+    spawners: Res<BehaviorSpawners>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut spawn, node, started) in &mut spawns {
+        if let BehaviorPropValue::None = spawn.prefab.value {
+            let result = spawn.prefab.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = spawn.key.value {
+            let result = spawn.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(prefab), BehaviorPropValue::Some(key)) =
+            (&spawn.prefab.value.clone(), &spawn.key.value.clone())
+        {
+            if started.is_some() {
+                let transform = transforms
+                    .get(node.tree)
+                    .map(|transform| transform.compute_transform())
+                    .unwrap_or_default();
+                match spawners.spawn(prefab, &mut commands, transform) {
+                    Some(spawned) => spawn.spawned = Some(spawned),
+                    None => {
+                        error!("Prefab not registered: {}", prefab);
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                }
+                continue;
+            }
+
+            // Spawn commands are applied, complete once the entity exists
+            let Some(spawned) = spawn.spawned else {
+                continue;
+            };
+            if entities.get(spawned).is_err() {
+                continue;
+            }
+            spawn.spawned = None;
+            if !key.is_empty() {
+                let value = (spawned.to_bits() as i64).into();
+                if let Err(err) = scripts.set_blackboard(node, key, value) {
+                    error!("Cannot store spawned entity: {}", err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                    continue;
+                }
+            }
+            commands.entity(entity).insert(BehaviorSuccess);
+        }
+    }
+}
+
+/// Despawn entities left behind by spawns that were stopped before completing
+pub fn cleanup(
+    mut commands: Commands,
+    mut spawns: Query<&mut Spawn, Without<BehaviorRunning>>,
+    entities: Query<Entity>,
+) {
+    for mut spawn in &mut spawns {
+        if let Some(spawned) = spawn.spawned.take() {
+            if entities.get(spawned).is_ok() {
+                commands.entity(spawned).despawn_recursive();
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Write the current time, in seconds, to a blackboard key.
+/// Pair with CooldownReady reading the same key to model cooldowns.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct StampNow {
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+}
+
+impl BehaviorSpec for StampNow {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "StampNow";
+    const ICON: &'static str = "⏱";
+    const DESC: &'static str = "Write the current time in seconds to a blackboard key";
+}
+
+impl BehaviorUI for StampNow {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+    }
+}
+
+pub fn run(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut stamps: Query<(Entity, &mut StampNow, &BehaviorNode), BehaviorRunQuery>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut stamp, node) in &mut stamps {
+        if let BehaviorPropValue::None = stamp.key.value {
+            let result = stamp.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(key) = &stamp.key.value {
+            let now = time.elapsed_seconds_f64();
+            match scripts.set_blackboard(node, key, now.into()) {
+                Ok(_) => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                Err(err) => {
+                    error!("Cannot stamp {}: {}", key, err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_core::ease::{Ease, EaseFunction};
+
+/// Interpolate a numeric blackboard value from its current value to a target over a duration,
+/// running until done. The value is written every tick, eased by `ease`, and set to exactly
+/// the target on the last one. Aborting leaves the value where it was.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Tween {
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+    #[serde(default)]
+    pub target: BehaviorPropGeneric<f64>,
+    #[serde(default)]
+    pub duration: BehaviorPropGeneric<f64>,
+    #[serde(default)]
+    pub ease: EaseFunction,
+    #[serde(skip)]
+    pub start: f64,
+    #[serde(skip)]
+    pub from: Option<f64>,
+    #[serde(skip)]
+    pub value: Option<f64>,
+}
+
+impl BehaviorSpec for Tween {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "Tween";
+    const ICON: &'static str = "〰";
+    const DESC: &'static str = "Interpolate a numeric blackboard value to a target over a \
+    duration, with an ease function";
+}
+
+impl BehaviorUI for Tween {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        changed |= behavior_ui!(self, target, state, ui, type_registry);
+        changed |= behavior_ui!(self, duration, state, ui, type_registry);
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("ease");
+                reflect_ui(self.ease.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+        behavior_ui_readonly!(self, target, state, ui, type_registry);
+        behavior_ui_readonly!(self, duration, state, ui, type_registry);
+        property_ui_readonly!(self, ease, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, from, state, ui, type_registry);
+                property_ui_readonly!(self, value, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut tweens: Query<
+        (Entity, &mut Tween, &BehaviorNode, Option<&BehaviorStarted>),
+        BehaviorRunQuery,
+    >,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut tween, node, started) in &mut tweens {
+        if let BehaviorPropValue::None = tween.key.value {
+            let result = tween.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = tween.target.value {
+            let result = tween.target.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = tween.duration.value {
+            let result = tween.duration.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (
+            BehaviorPropValue::Some(key),
+            BehaviorPropValue::Some(target),
+            BehaviorPropValue::Some(duration),
+        ) = (
+            tween.key.value.clone(),
+            tween.target.value.clone(),
+            tween.duration.value.clone(),
+        ) {
+            let elapsed = time.elapsed_seconds_f64();
+
+            // Start from the current value, every run
+            if started.is_some() || tween.from.is_none() {
+                let from = match scripts.get_blackboard(node, &key) {
+                    Ok(Some(value)) => value
+                        .as_float()
+                        .ok()
+                        .or_else(|| value.as_int().ok().map(|value| value as f64)),
+                    Ok(None) => None,
+                    Err(err) => {
+                        error!("Cannot read {}: {}", key, err);
+                        None
+                    }
+                };
+                let Some(from) = from else {
+                    error!("Blackboard key {} is not a number", key);
+                    commands.entity(entity).insert(BehaviorFailure);
+                    continue;
+                };
+                tween.start = elapsed;
+                tween.from = Some(from);
+            }
+
+            let from = tween.from.unwrap_or_default();
+            let t = if duration > 0.0 {
+                ((elapsed - tween.start) / duration).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            let done = t >= 1.0;
+            let value = if done {
+                target
+            } else {
+                from + (target - from) * t.calc(tween.ease)
+            };
+            tween.value = Some(value);
+
+            match scripts.set_blackboard(node, &key, value.into()) {
+                Ok(_) if done => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    error!("Cannot write {}: {}", key, err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
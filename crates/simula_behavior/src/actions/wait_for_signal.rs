@@ -0,0 +1,107 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::{prelude::*, utils::HashSet};
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A named signal, sent to every `WaitForSignal` waiting on that name.
+///
+/// Signals are not queued: a `WaitForSignal` only sees the ones sent while it is running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BehaviorSignal {
+    pub name: String,
+}
+
+impl BehaviorSignal {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+/// Stay running until a `BehaviorSignal` with the given name is sent, then succeed.
+/// Lets trees react to events instead of polling a condition.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct WaitForSignal {
+    #[serde(default)]
+    pub name: BehaviorPropStr,
+    #[serde(skip)]
+    pub ticks: u64,
+}
+
+impl BehaviorSpec for WaitForSignal {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "WaitForSignal";
+    const ICON: &'static str = "📡";
+    const DESC: &'static str = "Stay running until a named signal is sent, then succeed";
+}
+
+impl BehaviorUI for WaitForSignal {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, name, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, name, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, ticks, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut waits: Query<
+        (
+            Entity,
+            &mut WaitForSignal,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        BehaviorRunQuery,
+    >,
+    mut signals: EventReader<BehaviorSignal>,
+    mut scripts: ScriptQueries,
+) {
+    let sent: HashSet<String> = signals.iter().map(|signal| signal.name.clone()).collect();
+    for (entity, mut wait, node, started) in &mut waits {
+        if started.is_some() {
+            wait.ticks = 0;
+        }
+
+        if let BehaviorPropValue::None = wait.name.value {
+            let result = wait.name.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(name) = &wait.name.value {
+            if sent.iter().any(|sent| sent == name) {
+                commands.entity(entity).insert(BehaviorSuccess);
+            } else {
+                wait.ticks += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Pick a random point within a radius of the agent on the xz plane, write it to a blackboard
+/// key as a `Vec3`, and succeed. Follow it with a movement action to wander around.
+///
+/// Points are drawn from `BehaviorRng`, so seeding it makes wandering reproducible. A `bias`
+/// direction pulls points towards it, by its length from 0 (none) to 1 (always straight ahead).
+/// With a `grid_size`, points are kept within a `Grid` of that size centered on the origin.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Wander {
+    #[serde(default)]
+    pub radius: BehaviorPropGeneric<f64>,
+    #[serde(default)]
+    pub bias: Vec3,
+    #[serde(default)]
+    pub grid_size: Option<f32>,
+    #[serde(default)]
+    pub key: BehaviorPropStr,
+    #[serde(skip)]
+    pub point: Option<Vec3>,
+}
+
+impl BehaviorSpec for Wander {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "Wander";
+    const ICON: &'static str = "🚶";
+    const DESC: &'static str = "Pick a random point within a radius of the agent, optionally \
+    biased towards a direction and kept within grid bounds, and write it to a blackboard key";
+}
+
+impl BehaviorUI for Wander {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, radius, state, ui, type_registry);
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("bias");
+                reflect_ui(self.bias.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("grid_size");
+                reflect_ui(self.grid_size.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed |= behavior_ui!(self, key, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, radius, state, ui, type_registry);
+        property_ui_readonly!(self, bias, state, ui, type_registry);
+        property_ui_readonly!(self, grid_size, state, ui, type_registry);
+        behavior_ui_readonly!(self, key, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, point, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Wander {
+    /// Pick a point around an origin, using the rng for the direction and distance
+    pub fn pick(&self, origin: Vec3, radius: f32, rng: &mut impl Rng) -> Vec3 {
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let mut direction = Vec3::new(angle.cos(), 0.0, angle.sin());
+        let bias = Vec3::new(self.bias.x, 0.0, self.bias.z);
+        if let Some(bias_direction) = bias.try_normalize() {
+            let strength = bias.length().min(1.0);
+            direction = direction
+                .lerp(bias_direction, strength)
+                .try_normalize()
+                .unwrap_or(bias_direction);
+        }
+        // Uniform over the disc area, not crowding the center
+        let distance = radius * rng.gen::<f32>().sqrt();
+        let mut point = origin + direction * distance;
+        if let Some(grid_size) = self.grid_size {
+            let half_size = grid_size * 0.5;
+            point.x = point.x.clamp(-half_size, half_size);
+            point.z = point.z.clamp(-half_size, half_size);
+        }
+        point
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut wanders: Query<(Entity, &mut Wander, &BehaviorNode), BehaviorRunQuery>,
+    transforms: Query<&GlobalTransform>,
+    mut rng: ResMut<BehaviorRng>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut wander, node) in &mut wanders {
+        if let BehaviorPropValue::None = wander.radius.value {
+            let result = wander.radius.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = wander.key.value {
+            let result = wander.key.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(radius), BehaviorPropValue::Some(key)) =
+            (wander.radius.value.clone(), wander.key.value.clone())
+        {
+            let Ok(agent) = transforms.get(node.tree) else {
+                error!("Agent {:?} has no transform", node.tree);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            };
+
+            let point = wander.pick(agent.translation(), radius as f32, &mut rng.0);
+            wander.point = Some(point);
+            match scripts.set_blackboard(node, &key, simula_script::script::Dynamic::from(point)) {
+                Ok(_) => {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                Err(err) => {
+                    error!("Cannot write {}: {}", key, err);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::prelude::*;
+use crate::property_ui_readonly;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A yield stays running for exactly one tick, then succeeds.
+/// Unlike Wait, the pause does not depend on time, so sequences keep the same pacing
+/// regardless of frame rate.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Yield {
+    #[serde(skip)]
+    pub ticks: u64,
+}
+
+impl BehaviorSpec for Yield {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "Yield";
+    const ICON: &'static str = "⏭";
+    const DESC: &'static str = "Stay running for exactly one tick, then succeed";
+}
+
+impl BehaviorUI for Yield {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        _state: Option<protocol::BehaviorState>,
+        _ui: &mut bevy_inspector_egui::egui::Ui,
+        _type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        false
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, ticks, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut yields: Query<(Entity, &mut Yield, Option<&BehaviorStarted>), BehaviorRunQuery>,
+) {
+    for (entity, mut yield_tick, started) in &mut yields {
+        if started.is_some() {
+            yield_tick.ticks = 0;
+        }
+        yield_tick.ticks += 1;
+        if yield_tick.ticks > 1 {
+            commands.entity(entity).insert(BehaviorSuccess);
+        }
+    }
+}
@@ -1,13 +1,19 @@
-use crate::{BehaviorChildren, BehaviorCursor, BehaviorFactory, BehaviorNode, BehaviorTree};
+use crate::{
+    decorators::Subtree, BehaviorBlackboard, BehaviorChildren, BehaviorCursor, BehaviorFactory,
+    BehaviorFailure, BehaviorNode, BehaviorRunning, BehaviorStarted, BehaviorSuccess,
+    BehaviorSuspended, BehaviorTree,
+};
 use bevy::{
     asset::{AssetLoader, LoadContext, LoadedAsset},
     prelude::*,
     reflect::TypeUuid,
     utils::BoxedFuture,
 };
+use bincode::Options;
 use serde::{Deserialize, Serialize};
 use simula_script::ScriptContext;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 /// This is the one and only data type for creating behaviors.
@@ -19,6 +25,9 @@ pub struct Behavior<T: BehaviorFactory>(
     T,
     #[serde(default)] Vec<Behavior<T>>,
     #[serde(default)] T::Attributes,
+    /// Named behaviors defined inline, referenced by subtrees as `#name`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    BTreeMap<Cow<'static, str>, Behavior<T>>,
 );
 
 impl<T> Behavior<T>
@@ -31,7 +40,7 @@ where
         attrs: T::Attributes,
         nodes: Vec<Behavior<T>>,
     ) -> Self {
-        Self(name.into(), data, nodes, attrs)
+        Self(name.into(), data, nodes, attrs, BTreeMap::new())
     }
 
     pub fn name(&self) -> &str {
@@ -57,6 +66,166 @@ where
     pub fn nodes_mut(&mut self) -> &mut Vec<Behavior<T>> {
         &mut self.2
     }
+
+    pub fn inline(&self) -> &BTreeMap<Cow<'static, str>, Behavior<T>> {
+        &self.4
+    }
+
+    pub fn inline_mut(&mut self) -> &mut BTreeMap<Cow<'static, str>, Behavior<T>> {
+        &mut self.4
+    }
+}
+
+/// Header of behaviors in binary form, followed by a format version
+pub const BINARY_MAGIC: &[u8; 4] = b"BHT\0";
+const BINARY_VERSION: u8 = 1;
+
+/// Runtime form of a behavior, as stored in binary. Editor only attributes, like node
+/// positions, are left out and come back as defaults.
+#[derive(Serialize, Deserialize)]
+struct BehaviorBinary<T>(
+    Cow<'static, str>,
+    T,
+    Vec<BehaviorBinary<T>>,
+    BTreeMap<Cow<'static, str>, BehaviorBinary<T>>,
+);
+
+impl<T: BehaviorFactory> From<&Behavior<T>> for BehaviorBinary<T> {
+    fn from(behavior: &Behavior<T>) -> Self {
+        Self(
+            behavior.0.clone(),
+            behavior.1.clone(),
+            behavior.2.iter().map(Into::into).collect(),
+            behavior
+                .4
+                .iter()
+                .map(|(name, inline)| (name.clone(), inline.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: BehaviorFactory> From<BehaviorBinary<T>> for Behavior<T> {
+    fn from(binary: BehaviorBinary<T>) -> Self {
+        Self(
+            binary.0,
+            binary.1,
+            binary.2.into_iter().map(Into::into).collect(),
+            T::Attributes::default(),
+            binary
+                .3
+                .into_iter()
+                .map(|(name, inline)| (name, inline.into()))
+                .collect(),
+        )
+    }
+}
+
+fn binary_options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+impl<T> Behavior<T>
+where
+    T: BehaviorFactory + Serialize,
+{
+    /// Encode to the compact binary form, for shipping instead of RON
+    pub fn to_binary(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = BINARY_MAGIC.to_vec();
+        bytes.push(BINARY_VERSION);
+        binary_options()
+            .serialize_into(&mut bytes, &BehaviorBinary::from(self))
+            .map_err(|err| err.to_string())?;
+        Ok(bytes)
+    }
+}
+
+impl<T> Behavior<T>
+where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    /// Decode from the binary form, attributes are left as defaults
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, String> {
+        let Some(bytes) = bytes.strip_prefix(BINARY_MAGIC.as_slice()) else {
+            return Err("Not a binary behavior".to_string());
+        };
+        match bytes.split_first() {
+            Some((&BINARY_VERSION, bytes)) => binary_options()
+                .deserialize::<BehaviorBinary<T>>(bytes)
+                .map(Into::into)
+                .map_err(|err| err.to_string()),
+            Some((version, _)) => Err(format!("Unsupported binary behavior version {}", version)),
+            None => Err("Truncated binary behavior".to_string()),
+        }
+    }
+}
+
+/// Convert a behavior document from RON to binary, checking it first
+pub fn convert_document<T>(document: &str) -> Result<Vec<u8>, String>
+where
+    T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>,
+{
+    let behavior = ron::de::from_str::<Behavior<T>>(document).map_err(|err| err.to_string())?;
+    validate_inline(&behavior)?;
+    behavior.to_binary()
+}
+
+/// Prefix used by subtrees to reference inline behaviors instead of files
+pub const INLINE_PREFIX: &str = "#";
+
+/// Name of the inline behavior referenced by a subtree node, if any
+pub(crate) fn inline_ref<T: BehaviorFactory>(behavior: &Behavior<T>) -> Option<&str> {
+    behavior
+        .data()
+        .inner_reflect()
+        .downcast_ref::<Subtree<T>>()
+        .and_then(|subtree| subtree.asset.strip_prefix(INLINE_PREFIX))
+}
+
+fn collect_inline_refs<'a, T: BehaviorFactory>(behavior: &'a Behavior<T>, refs: &mut Vec<&'a str>) {
+    if let Some(name) = inline_ref(behavior) {
+        refs.push(name);
+    }
+    for node in behavior.nodes() {
+        collect_inline_refs(node, refs);
+    }
+}
+
+fn visit_inline<'a, T: BehaviorFactory>(
+    inline: &'a BTreeMap<Cow<'static, str>, Behavior<T>>,
+    name: &'a str,
+    stack: &mut Vec<&'a str>,
+) -> Result<(), String> {
+    if stack.contains(&name) {
+        return Err(format!(
+            "Recursive inline behavior: {} -> {}",
+            stack.join(" -> "),
+            name
+        ));
+    }
+    let Some(behavior) = inline.get(name) else {
+        return Err(format!("Unknown inline behavior: {}", name));
+    };
+    let mut refs = vec![];
+    collect_inline_refs(behavior, &mut refs);
+    stack.push(name);
+    for name in refs {
+        visit_inline(inline, name, stack)?;
+    }
+    stack.pop();
+    Ok(())
+}
+
+/// Check all inline references of a behavior exist and are not recursive
+pub fn validate_inline<T: BehaviorFactory>(behavior: &Behavior<T>) -> Result<(), String> {
+    let inline = behavior.inline();
+    let mut refs = vec![];
+    collect_inline_refs(behavior, &mut refs);
+    refs.extend(inline.keys().map(|name| name.as_ref()));
+    for name in refs {
+        visit_inline(inline, name, &mut vec![])?;
+    }
+    Ok(())
 }
 
 #[derive(Default, Debug, TypeUuid, Deserialize)]
@@ -70,9 +239,53 @@ where
     pub file_name: Option<Cow<'static, str>>,
 }
 
-#[derive(Default, Debug, TypeUuid, Deserialize, Deref)]
+/// File extensions of behavior documents, RON for editing and binary for shipping
+pub const BEHAVIOR_EXTENSIONS: &[&str] = &["bht.ron", "bht.bin"];
+
+/// Path of a behavior document without its extension
+pub fn trim_extension(file_path: &str) -> &str {
+    BEHAVIOR_EXTENSIONS
+        .iter()
+        .find_map(|ext| file_path.strip_suffix(ext)?.strip_suffix('.'))
+        .unwrap_or(file_path)
+}
+
+/// A behavior as loaded, in RON or binary form
+#[derive(Debug, TypeUuid, Deserialize)]
 #[uuid = "7f117190-5353-11ed-ae42-02a179e5df2b"]
-pub struct BehaviorDocument(String);
+pub enum BehaviorDocument {
+    Ron(String),
+    Binary(Vec<u8>),
+}
+
+impl Default for BehaviorDocument {
+    fn default() -> Self {
+        Self::Ron(String::new())
+    }
+}
+
+impl BehaviorDocument {
+    /// Binary documents are told apart by their header, anything else must be RON
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, std::str::Utf8Error> {
+        if bytes.starts_with(BINARY_MAGIC) {
+            Ok(Self::Binary(bytes.to_vec()))
+        } else {
+            Ok(Self::Ron(std::str::from_utf8(bytes)?.to_string()))
+        }
+    }
+
+    pub fn to_behavior<T>(&self) -> Result<Behavior<T>, String>
+    where
+        T: BehaviorFactory + for<'de> Deserialize<'de>,
+    {
+        match self {
+            Self::Ron(document) => {
+                ron::de::from_str::<Behavior<T>>(document).map_err(|err| err.to_string())
+            }
+            Self::Binary(bytes) => Behavior::from_binary(bytes),
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct BehaviorAssetLoader;
@@ -84,15 +297,14 @@ impl AssetLoader for BehaviorAssetLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
         Box::pin(async move {
-            let document = std::str::from_utf8(bytes)?.to_string();
-            let asset = BehaviorDocument(document);
+            let asset = BehaviorDocument::from_bytes(bytes)?;
             load_context.set_default_asset(LoadedAsset::new(asset));
             Ok(())
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["bht.ron"]
+        BEHAVIOR_EXTENSIONS
     }
 }
 
@@ -147,31 +359,242 @@ pub fn behavior_tree_reset<T>(
     }
 }
 
+/// Rebuild the nodes of hot reloading behavior trees whose behavior asset was modified.
+///
+/// The tree entity and its script context are kept, so the blackboard survives the reload,
+/// but the running state is reset and the new tree starts from its root.
+pub fn behavior_tree_hot_reload<T>(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<BehaviorAsset<T>>>,
+    trees: Query<(Entity, &BehaviorTree<T>, &Handle<BehaviorAsset<T>>)>,
+    nodes: Query<(Entity, &BehaviorNode)>,
+) where
+    T: BehaviorFactory,
+{
+    for event in asset_events.iter() {
+        let AssetEvent::Modified { handle } = event else {
+            continue;
+        };
+        for (entity, tree, behavior_asset) in &trees {
+            if !tree.hot_reload || behavior_asset != handle {
+                continue;
+            }
+            info!("Hot reloading behavior tree for entity {:?}", entity);
+
+            // Despawn the old nodes, subtrees go with their nodes
+            for (node_entity, node) in &nodes {
+                if node.tree == entity {
+                    commands.entity(node_entity).despawn_recursive();
+                }
+            }
+
+            commands
+                .entity(entity)
+                .remove::<(
+                    BehaviorCursor,
+                    BehaviorRunning,
+                    BehaviorStarted,
+                    BehaviorSuccess,
+                    BehaviorFailure,
+                )>()
+                .insert(BehaviorTreeReset::<T>::default());
+        }
+    }
+}
+
+/// Update behavior assets loaded from a document when the document is modified, e.g. edited
+/// on disk while the asset server watches for changes
+pub fn behavior_document_reload<T>(
+    mut asset_events: EventReader<AssetEvent<BehaviorDocument>>,
+    behavior_documents: Res<Assets<BehaviorDocument>>,
+    mut behavior_assets: ResMut<Assets<BehaviorAsset<T>>>,
+    asset_server: Res<AssetServer>,
+) where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    for event in asset_events.iter() {
+        let AssetEvent::Modified { handle } = event else {
+            continue;
+        };
+        let (Some(behavior_document), Some(path)) = (
+            behavior_documents.get(handle),
+            asset_server.get_handle_path(handle),
+        ) else {
+            continue;
+        };
+        let file_path = path.path().to_string_lossy();
+        let file_name = trim_extension(&file_path);
+
+        let res = behavior_document
+            .to_behavior::<T>()
+            .and_then(|behavior| validate_inline(&behavior).map(|_| behavior));
+        let behavior = match res {
+            Ok(behavior) => behavior,
+            Err(err) => {
+                error!("Failed to reload behavior {}: {}", file_name, err);
+                continue;
+            }
+        };
+
+        // Assets are modified only if loaded from this document
+        let ids = behavior_assets
+            .iter()
+            .filter(|(_, asset)| asset.file_name.as_deref() == Some(file_name))
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        for id in ids {
+            if let Some(behavior_asset) = behavior_assets.get_mut(&Handle::weak(id)) {
+                info!("Reloaded behavior: {}", file_name);
+                behavior_asset.behavior = behavior.clone();
+            }
+        }
+    }
+}
+
+/// Simulation control pausing every behavior tree of a type, e.g. to pause the whole mission.
+///
+/// While paused, all nodes of those trees, subtrees included, are marked BehaviorSuspended. They
+/// keep their cursor and running state, and resume where they were when unpaused. Time based
+/// nodes measure time from when they started, so timers keep advancing while paused and may
+/// complete on the first tick after resuming.
+#[derive(Resource)]
+pub struct BehaviorTreePaused<T>
+where
+    T: BehaviorFactory,
+{
+    pub paused: bool,
+    pub phantom: std::marker::PhantomData<T>,
+}
+
+impl<T> Default for BehaviorTreePaused<T>
+where
+    T: BehaviorFactory,
+{
+    fn default() -> Self {
+        Self {
+            paused: false,
+            phantom: Default::default(),
+        }
+    }
+}
+
+/// Pause or resume all nodes of trees of a type, following BehaviorTreePaused
+pub fn behavior_tree_pause<T>(
+    mut commands: Commands,
+    pause: Res<BehaviorTreePaused<T>>,
+    trees: Query<(), With<BehaviorTree<T>>>,
+    nodes: Query<(Entity, &BehaviorNode, Option<&BehaviorSuspended>)>,
+) where
+    T: BehaviorFactory,
+{
+    // Keep pausing new or resumed nodes while paused, resume only once
+    if !pause.paused && !pause.is_changed() {
+        return;
+    }
+    for (entity, node, suspended) in &nodes {
+        // Subtree nodes belong to the subtree node, walk up to the agent
+        let mut tree = node.tree;
+        while let Ok((_, node, _)) = nodes.get(tree) {
+            if tree == node.tree {
+                break;
+            }
+            tree = node.tree;
+        }
+        if trees.get(tree).is_err() && trees.get(node.tree).is_err() {
+            continue;
+        }
+        match (pause.paused, suspended.is_some()) {
+            (true, false) => {
+                commands.entity(entity).insert(BehaviorSuspended);
+            }
+            (false, true) => {
+                commands.entity(entity).remove::<BehaviorSuspended>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Tear down node entities of behavior trees that were removed or despawned, along with the
+/// script context holding their blackboard
+pub fn behavior_tree_cleanup<T>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<BehaviorTree<T>>,
+    mut script_ctxs: ResMut<Assets<ScriptContext>>,
+    trees: Query<(), With<BehaviorTree<T>>>,
+    nodes: Query<(Entity, &BehaviorNode)>,
+    script_ctx_handles: Query<&Handle<ScriptContext>>,
+) where
+    T: BehaviorFactory,
+{
+    // Tree may have been removed and added back in the same frame
+    let removed: Vec<Entity> = removed
+        .iter()
+        .filter(|entity| trees.get(*entity).is_err())
+        .collect();
+    if removed.is_empty() {
+        return;
+    }
+
+    // Despawn nodes even if the tree entity was not despawned recursively
+    for (node_entity, node) in &nodes {
+        if removed.contains(&node.tree) {
+            commands.entity(node_entity).despawn_recursive();
+        }
+    }
+
+    // Agent may live on, clear any behavior state left on it
+    for entity in removed {
+        if let Ok(script_ctx_handle) = script_ctx_handles.get(entity) {
+            script_ctxs.remove(script_ctx_handle);
+        }
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<(
+                BehaviorTreeReset<T>,
+                Handle<ScriptContext>,
+                BehaviorCursor,
+                BehaviorRunning,
+                BehaviorStarted,
+                BehaviorSuccess,
+                BehaviorFailure,
+            )>();
+        }
+    }
+}
+
 pub fn behavior_document_to_asset<T>(
     mut commands: Commands,
     behavior_documents: Res<Assets<BehaviorDocument>>,
     mut behavior_assets: ResMut<Assets<BehaviorAsset<T>>>,
     mut script_ctxs: ResMut<Assets<ScriptContext>>,
     asset_server: Res<AssetServer>,
-    documents: Query<(Entity, &Handle<BehaviorDocument>), With<BehaviorTree<T>>>,
+    documents: Query<
+        (
+            Entity,
+            &Handle<BehaviorDocument>,
+            Option<&BehaviorBlackboard>,
+        ),
+        With<BehaviorTree<T>>,
+    >,
 ) where
     T: BehaviorFactory + for<'de> Deserialize<'de>,
 {
-    for (entity, behavior_document_handle) in documents.iter() {
+    for (entity, behavior_document_handle, seed) in documents.iter() {
         // Convert document into behavior asset
         if let Some(behavior_document) = behavior_documents.get(behavior_document_handle) {
             // Remove document handle, if it fails to deserialize we wont keep trying
             commands.entity(entity).remove::<Handle<BehaviorDocument>>();
 
             // Deserialize behavior asset
-            let res = ron::de::from_str::<Behavior<T>>(&behavior_document);
+            let res = behavior_document
+                .to_behavior::<T>()
+                .and_then(|behavior| validate_inline(&behavior).map(|_| behavior));
             if let Ok(behavior) = res {
                 // Get file name
                 let path = asset_server.get_handle_path(behavior_document_handle);
                 let file_name = path.and_then(|path| {
                     let file_path = path.path().to_string_lossy();
-                    let file_name: Cow<'static, str> =
-                        file_path.trim_end_matches(".bht.ron").to_owned().into();
+                    let file_name: Cow<'static, str> = trim_extension(&file_path).to_owned().into();
                     Some(file_name)
                 });
 
@@ -181,8 +604,11 @@ pub fn behavior_document_to_asset<T>(
                     file_name,
                 });
 
-                // Create scripting scope
-                let script_ctx = BehaviorTree::<T>::create_script_context();
+                // Create scripting scope, seeded with the agent blackboard
+                let script_ctx = match seed {
+                    Some(seed) => BehaviorTree::<T>::create_script_context_with(seed),
+                    None => BehaviorTree::<T>::create_script_context(),
+                };
                 let script_ctx_handle = script_ctxs.add(script_ctx);
 
                 // and insert
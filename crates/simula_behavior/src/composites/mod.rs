@@ -1,9 +1,15 @@
 pub mod all;
 pub mod any;
+pub mod parallel;
+pub mod random_selector;
 pub mod selector;
 pub mod sequencer;
+pub mod switch;
 
 pub use all::All;
 pub use any::Any;
+pub use parallel::Parallel;
+pub use random_selector::RandomSelector;
 pub use selector::Selector;
 pub use sequencer::Sequencer;
+pub use switch::Switch;
@@ -0,0 +1,132 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Parallel will run all of its children in parallel, and succeed once `success_threshold` of
+/// them succeed, or fail once `failure_threshold` of them fail, or as soon as enough
+/// successes can no longer be reached. A threshold of zero means all children. Children
+/// still running when it completes are stopped.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Parallel {
+    #[serde(default)]
+    pub success_threshold: usize,
+    #[serde(default)]
+    pub failure_threshold: usize,
+}
+
+impl BehaviorSpec for Parallel {
+    const TYPE: BehaviorType = BehaviorType::Composite;
+    const NAME: &'static str = "Parallel";
+    const ICON: &'static str = "⫴";
+    const DESC: &'static str = "Run all of its children in parallel, succeed when enough of \
+    them succeed, fail when enough of them fail";
+}
+
+impl BehaviorUI for Parallel {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        _state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("success_threshold");
+                reflect_ui(self.success_threshold.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("failure_threshold");
+                reflect_ui(self.failure_threshold.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        property_ui_readonly!(self, success_threshold, state, ui, type_registry);
+        property_ui_readonly!(self, failure_threshold, state, ui, type_registry);
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    parallels: Query<(Entity, &BehaviorChildren, &Parallel), BehaviorRunQuery>,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
+) {
+    for (entity, children, parallel) in &parallels {
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+            continue;
+        }
+
+        let threshold = |threshold: usize| match threshold {
+            0 => children.len(),
+            threshold => threshold.min(children.len()),
+        };
+        let success_threshold = threshold(parallel.success_threshold);
+        let failure_threshold = threshold(parallel.failure_threshold);
+
+        let mut successes = 0;
+        let mut failures = 0;
+        for BehaviorChildQueryItem {
+            child_failure,
+            child_success,
+            ..
+        } in nodes.iter_many(children.iter())
+        {
+            if child_success.is_some() {
+                successes += 1;
+            } else if child_failure.is_some() {
+                failures += 1;
+            }
+        }
+
+        // Enough succeeded, so we succeed
+        if successes >= success_threshold {
+            commands.entity(entity).insert(BehaviorSuccess);
+            continue;
+        }
+        // Enough failed, or too many to still succeed, so we fail
+        if failures >= failure_threshold || children.len() - failures < success_threshold {
+            commands.entity(entity).insert(BehaviorFailure);
+            continue;
+        }
+
+        // Keep running the rest
+        for BehaviorChildQueryItem {
+            child_entity,
+            child_parent: _,
+            child_failure,
+            child_success,
+            child_running,
+        } in nodes.iter_many(children.iter())
+        {
+            if child_success.is_some() || child_failure.is_some() {
+                // Child completed, so we move to next child
+            } else if child_running.is_some() {
+                // Child running, so we move to next child
+                commands.entity(entity).remove::<BehaviorCursor>();
+            } else {
+                // Child is ready, pass on cursor
+                commands.entity(entity).remove::<BehaviorCursor>();
+                commands
+                    .entity(child_entity)
+                    .insert(BehaviorCursor::Delegate);
+            }
+        }
+    }
+}
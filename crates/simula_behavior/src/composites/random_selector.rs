@@ -0,0 +1,126 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// A random selector visits its children in a random order, picked every time it starts, and
+/// succeeds on the first child that succeeds. It fails if all children fail.
+///
+/// The order is drawn from `BehaviorRng`, or from its own rng when given a `seed`, so the
+/// sequence of orders is reproducible.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct RandomSelector {
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    pub rng: Option<StdRng>,
+    #[serde(skip)]
+    pub order: Vec<Entity>,
+}
+
+impl BehaviorSpec for RandomSelector {
+    const TYPE: BehaviorType = BehaviorType::Composite;
+    const NAME: &'static str = "Random Selector";
+    const ICON: &'static str = "🎲";
+    const DESC: &'static str = "Visit children in a random order, succeeding on the first \
+    child that succeeds, failing if all children fail";
+}
+
+impl BehaviorUI for RandomSelector {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        _state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        ui.horizontal(|ui| {
+            ui.label("seed");
+            reflect_ui(self.seed.as_reflect_mut(), ui, type_registry)
+        })
+        .inner
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        property_ui_readonly!(self, seed, state, ui, type_registry);
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut selectors: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut RandomSelector,
+            Option<&BehaviorStarted>,
+        ),
+        (With<RandomSelector>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut behavior_rng: ResMut<BehaviorRng>,
+) {
+    for (entity, children, mut selector, started) in &mut selectors {
+        // Pick a new order every run
+        if started.is_some() || selector.order.len() != children.len() {
+            let selector = selector.as_mut();
+            let mut order = children.0.clone();
+            match selector.seed {
+                Some(seed) => {
+                    let rng = selector
+                        .rng
+                        .get_or_insert_with(|| StdRng::seed_from_u64(seed));
+                    order.shuffle(rng);
+                }
+                None => order.shuffle(&mut behavior_rng.0),
+            }
+            selector.order = order;
+        }
+
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            let mut should_fail = true;
+            for BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            } in nodes.iter_many(selector.order.iter())
+            {
+                if child_failure.is_some() {
+                    // Child failed, so we move to next child
+                } else if child_success.is_some() {
+                    // Child succeeded, so we succeed
+                    commands.entity(entity).insert(BehaviorSuccess);
+                    should_fail = false;
+                    break;
+                } else {
+                    // Child is ready, pass on cursor
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                    should_fail = false;
+                    break;
+                }
+            }
+            // If all children failed, complete with failure
+            if should_fail {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
@@ -7,10 +7,16 @@ use serde::{Deserialize, Serialize};
 /// any further children. It will process the first child, and if it fails will
 /// process the second, until a success is reached, at which point it will instantly
 /// return success. It will fail if all children fail.
+///
+/// With memory, children that failed are not visited again until the selector restarts.
+/// Without memory the selector is reactive: while a child runs, the children before it are
+/// run again, and if one of them succeeds the selector succeeds and stops the running child.
 #[derive(Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
 pub struct Selector {
     #[serde(default)]
     random: bool,
+    #[serde(default = "default_memory")]
+    pub memory: bool,
     #[serde(skip)]
     #[reflect(ignore)]
     pub seed: u64,
@@ -20,11 +26,16 @@ impl Default for Selector {
     fn default() -> Self {
         Self {
             random: false,
+            memory: true,
             seed: rand::random(),
         }
     }
 }
 
+fn default_memory() -> bool {
+    true
+}
+
 impl BehaviorSpec for Selector {
     const TYPE: BehaviorType = BehaviorType::Composite;
     const NAME: &'static str = "Selector";
@@ -44,20 +55,13 @@ pub fn run(
         (Entity, &BehaviorChildren, &mut Selector),
         (With<Selector>, BehaviorRunQuery),
     >,
-    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    reactive_selectors: Query<
+        (&BehaviorChildren, &Selector),
+        (BehaviorIdleQuery, Without<BehaviorCursor>),
+    >,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
 ) {
     for (entity, children, mut selector) in &mut selectors {
-        // If selector is random, shuffle the children, deterministically
-        let mut rng = StdRng::seed_from_u64(selector.seed);
-        let mut random_children;
-        let children_iter = if selector.random {
-            random_children = children.0.clone();
-            random_children.shuffle(&mut rng);
-            random_children.iter()
-        } else {
-            children.iter()
-        };
-
         if children.is_empty() {
             commands.entity(entity).insert(BehaviorSuccess);
         } else {
@@ -67,8 +71,8 @@ pub fn run(
                 child_parent: _,
                 child_failure,
                 child_success,
-                child_running: _,
-            } in nodes.iter_many(children_iter)
+                child_running,
+            } in nodes.iter_many(selector.children(children).iter())
             {
                 if child_failure.is_some() {
                     // Child failed, so we move to next child
@@ -80,6 +84,11 @@ pub fn run(
                     }
                     should_fail = false;
                     break;
+                } else if child_running.is_some() {
+                    // Child still running, an earlier child was run again, so we wait
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    should_fail = false;
+                    break;
                 } else {
                     // Child is ready, pass on cursor
                     commands.entity(entity).remove::<BehaviorCursor>();
@@ -99,4 +108,47 @@ pub fn run(
             }
         }
     }
+
+    // Without memory, run again the children that failed before the running one
+    for (children, selector) in &reactive_selectors {
+        if selector.memory {
+            continue;
+        }
+        let mut failed = vec![];
+        for BehaviorChildQueryItem {
+            child_entity,
+            child_parent: _,
+            child_failure,
+            child_success: _,
+            child_running,
+        } in nodes.iter_many(selector.children(children).iter())
+        {
+            if child_failure.is_some() {
+                failed.push(child_entity);
+            } else {
+                if child_running.is_some() {
+                    for child_entity in failed.drain(..) {
+                        commands
+                            .entity(child_entity)
+                            .remove::<BehaviorFailure>()
+                            .remove::<BehaviorOutcome>()
+                            .insert(BehaviorCursor::Delegate);
+                    }
+                }
+                break;
+            }
+        }
+    }
+}
+
+impl Selector {
+    // Children in visiting order, shuffled deterministically if random
+    fn children(&self, children: &BehaviorChildren) -> Vec<Entity> {
+        let mut children = children.0.clone();
+        if self.random {
+            let mut rng = StdRng::seed_from_u64(self.seed);
+            children.shuffle(&mut rng);
+        }
+        children
+    }
 }
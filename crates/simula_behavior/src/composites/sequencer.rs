@@ -7,10 +7,16 @@ use serde::{Deserialize, Serialize};
 /// succeeds will call the second, and so on down the list of children. If any child
 /// fails it will immediately return failure to the parent. If the last child in the
 /// sequence succeeds, then the sequence will return success to its parent.
+///
+/// With memory, children that succeeded are not visited again until the sequence restarts.
+/// Without memory the sequence is reactive: while a child runs, the children before it are
+/// run again, and if one of them fails the sequence fails and stops the running child.
 #[derive(Debug, Component, Reflect, FromReflect, Clone, Deserialize, Serialize)]
 pub struct Sequencer {
     #[serde(default)]
     random: bool,
+    #[serde(default = "default_memory")]
+    pub memory: bool,
     #[serde(skip)]
     #[reflect(ignore)]
     pub seed: u64,
@@ -20,11 +26,16 @@ impl Default for Sequencer {
     fn default() -> Self {
         Self {
             random: false,
+            memory: true,
             seed: rand::random(),
         }
     }
 }
 
+fn default_memory() -> bool {
+    true
+}
+
 impl BehaviorSpec for Sequencer {
     const TYPE: BehaviorType = BehaviorType::Composite;
     const NAME: &'static str = "Sequencer";
@@ -44,20 +55,13 @@ pub fn run(
         (Entity, &BehaviorChildren, &mut Sequencer),
         (With<Sequencer>, BehaviorRunQuery),
     >,
-    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    reactive_sequences: Query<
+        (&BehaviorChildren, &Sequencer),
+        (BehaviorIdleQuery, Without<BehaviorCursor>),
+    >,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
 ) {
     for (entity, children, mut sequence) in &mut sequences {
-        // If sequence is random, shuffle the children, deterministically
-        let mut rng = StdRng::seed_from_u64(sequence.seed);
-        let mut random_children;
-        let children_iter = if sequence.random {
-            random_children = children.0.clone();
-            random_children.shuffle(&mut rng);
-            random_children.iter()
-        } else {
-            children.iter()
-        };
-
         if children.is_empty() {
             commands.entity(entity).insert(BehaviorSuccess);
         } else {
@@ -67,8 +71,8 @@ pub fn run(
                 child_parent: _,
                 child_failure,
                 child_success,
-                child_running: _,
-            } in nodes.iter_many(children_iter)
+                child_running,
+            } in nodes.iter_many(sequence.children(children).iter())
             {
                 if child_failure.is_some() {
                     // Child failed, so we fail
@@ -80,6 +84,11 @@ pub fn run(
                     break;
                 } else if child_success.is_some() {
                     // Child succeeded, so we move to next child
+                } else if child_running.is_some() {
+                    // Child still running, an earlier child was run again, so we wait
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    should_succeed = false;
+                    break;
                 } else {
                     // Child is ready, pass on cursor
                     commands.entity(entity).remove::<BehaviorCursor>();
@@ -99,4 +108,47 @@ pub fn run(
             }
         }
     }
+
+    // Without memory, run again the children that succeeded before the running one
+    for (children, sequence) in &reactive_sequences {
+        if sequence.memory {
+            continue;
+        }
+        let mut succeeded = vec![];
+        for BehaviorChildQueryItem {
+            child_entity,
+            child_parent: _,
+            child_failure: _,
+            child_success,
+            child_running,
+        } in nodes.iter_many(sequence.children(children).iter())
+        {
+            if child_success.is_some() {
+                succeeded.push(child_entity);
+            } else {
+                if child_running.is_some() {
+                    for child_entity in succeeded.drain(..) {
+                        commands
+                            .entity(child_entity)
+                            .remove::<BehaviorSuccess>()
+                            .remove::<BehaviorOutcome>()
+                            .insert(BehaviorCursor::Delegate);
+                    }
+                }
+                break;
+            }
+        }
+    }
+}
+
+impl Sequencer {
+    // Children in visiting order, shuffled deterministically if random
+    fn children(&self, children: &BehaviorChildren) -> Vec<Entity> {
+        let mut children = children.0.clone();
+        if self.random {
+            let mut rng = StdRng::seed_from_u64(self.seed);
+            children.shuffle(&mut rng);
+        }
+        children
+    }
 }
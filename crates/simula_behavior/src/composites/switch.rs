@@ -0,0 +1,129 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A switch runs exactly one of its children, picked by index, and completes with the result
+/// of that child. It fails if the index is out of range.
+///
+/// The index is evaluated every time the switch starts, so it can be read from the blackboard
+/// to route between states.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Switch {
+    #[serde(default)]
+    pub index: BehaviorPropGeneric<i64>,
+}
+
+impl BehaviorSpec for Switch {
+    const TYPE: BehaviorType = BehaviorType::Composite;
+    const NAME: &'static str = "Switch";
+    const ICON: &'static str = "🔀";
+    const DESC: &'static str = "Run the child at an index and complete with its result, \
+    failing if the index is out of range";
+}
+
+impl BehaviorUI for Switch {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        behavior_ui!(self, index, state, ui, type_registry)
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, index, state, ui, type_registry);
+    }
+}
+
+impl Switch {
+    /// Index of the child to run, if set to a value rather than a script
+    pub fn index_value(&self) -> Option<i64> {
+        match self.index.prop {
+            BehaviorEval::Value(index) => Some(index),
+            BehaviorEval::Eval { .. } => None,
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut switches: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut Switch,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        (With<Switch>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, children, mut switch, node, started) in &mut switches {
+        // Pick the child again every run
+        if started.is_some() {
+            switch.index.value = BehaviorPropValue::None;
+        }
+
+        if let BehaviorPropValue::None = switch.index.value {
+            let result = switch.index.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        // Script is still busy
+        let BehaviorPropValue::Some(index) = switch.index.value else {
+            continue;
+        };
+
+        // Index out of range, complete with failure
+        let Some(child_entity) = usize::try_from(index)
+            .ok()
+            .and_then(|index| children.get(index))
+        else {
+            commands.entity(entity).insert(BehaviorFailure);
+            continue;
+        };
+
+        if let Ok(BehaviorChildQueryItem {
+            child_entity,
+            child_parent: _,
+            child_failure,
+            child_success,
+            child_running: _,
+        }) = nodes.get(*child_entity)
+        {
+            // Child failed, so we fail
+            if child_failure.is_some() {
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+            // Child succeeded, so we succeed
+            else if child_success.is_some() {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            // Child is ready, pass on cursor
+            else {
+                commands.entity(entity).remove::<BehaviorCursor>();
+                commands
+                    .entity(child_entity)
+                    .insert(BehaviorCursor::Delegate);
+            }
+        }
+    }
+}
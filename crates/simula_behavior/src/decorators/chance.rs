@@ -0,0 +1,147 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Result forced by a Chance node when the roll hits
+#[derive(Debug, Default, Reflect, FromReflect, Clone, PartialEq, Deserialize, Serialize)]
+pub enum ChanceMode {
+    #[default]
+    ForceFailure,
+    ForceSuccess,
+}
+
+/// Run a child, but with probability `p` force the result regardless of the child
+///
+/// Rolls are drawn from `BehaviorRng`, so seeding it makes the forced results reproducible.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Chance {
+    #[serde(default)]
+    pub p: BehaviorPropGeneric<f64>,
+    #[serde(default)]
+    pub mode: ChanceMode,
+    #[serde(skip)]
+    pub roll: Option<f64>,
+}
+
+impl BehaviorSpec for Chance {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Chance";
+    const ICON: &'static str = "🎲";
+    const DESC: &'static str = "Run the child, but with probability p force the result to \
+    failure or success, depending on mode";
+}
+
+impl BehaviorUI for Chance {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, p, state, ui, type_registry);
+        changed |= ui
+            .horizontal(|ui| {
+                ui.label("mode");
+                reflect_ui(self.mode.as_reflect_mut(), ui, type_registry)
+            })
+            .inner;
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, p, state, ui, type_registry);
+        property_ui_readonly!(self, mode, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, roll, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut chances: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut Chance,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        (With<Chance>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut scripts: ScriptQueries,
+    mut rng: ResMut<BehaviorRng>,
+) {
+    for (entity, children, mut chance, node, started) in &mut chances {
+        if let BehaviorPropValue::None = chance.p.value {
+            let result = chance.p.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(p) = chance.p.value {
+            if children.len() != 1 {
+                error!("Decorator node requires one child");
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+
+            // Roll once per run, so telemetry shows the roll while the child runs
+            if started.is_some() || chance.roll.is_none() {
+                chance.roll = Some(rng.0.gen::<f64>());
+            }
+            let hit = chance.roll.unwrap_or_default() < p;
+
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                // Child is done, the roll decides if we override its result
+                if child_failure.is_some() || child_success.is_some() {
+                    let success = match (hit, &chance.mode) {
+                        (true, ChanceMode::ForceFailure) => false,
+                        (true, ChanceMode::ForceSuccess) => true,
+                        (false, _) => child_success.is_some(),
+                    };
+                    if success {
+                        commands.entity(entity).insert(BehaviorSuccess);
+                    } else {
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                }
+                // Child is ready, pass on cursor
+                else {
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                }
+            }
+        }
+    }
+}
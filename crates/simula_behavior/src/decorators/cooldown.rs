@@ -0,0 +1,131 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Cooldown runs its child and passes on its result, then fails without running the child
+/// until `duration` seconds have passed since the child completed.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Cooldown {
+    #[serde(default)]
+    pub duration: BehaviorPropGeneric<f64>,
+    #[serde(skip)]
+    pub last_run: Option<f64>,
+}
+
+impl BehaviorSpec for Cooldown {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Cooldown";
+    const ICON: &'static str = "❄";
+    const DESC: &'static str = "Runs its child, then fails for a duration after the child \
+    completes before allowing it to run again";
+}
+
+impl BehaviorUI for Cooldown {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, duration, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, duration, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, last_run, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut cooldowns: Query<
+        (
+            Entity,
+            &mut Cooldown,
+            &BehaviorChildren,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        (With<Cooldown>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, mut cooldown, children, node, started) in &mut cooldowns {
+        if let BehaviorPropValue::None = cooldown.duration.value {
+            let result = cooldown.duration.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::Some(duration) = cooldown.duration.value.clone() {
+            if children.len() != 1 {
+                error!("Decorator node requires one child");
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+
+            let elapsed = time.elapsed_seconds_f64();
+
+            // Still cooling down, fail without running the child
+            if started.is_some() {
+                if let Some(last_run) = cooldown.last_run {
+                    if elapsed - last_run < duration {
+                        commands.entity(entity).insert(BehaviorFailure);
+                        continue;
+                    }
+                }
+            }
+
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                // Child failed, so we fail and start cooling down
+                if child_failure.is_some() {
+                    cooldown.last_run = Some(elapsed);
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+                // Child succeeded, so we succeed and start cooling down
+                else if child_success.is_some() {
+                    cooldown.last_run = Some(elapsed);
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                // Child is ready, pass on cursor
+                else {
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_script::script::{Array, Dynamic};
+
+/// Run a child once per element of a blackboard list, binding each element to another
+/// blackboard key before the child runs. Succeed once all elements are processed, or fail on
+/// the first child failure unless `continue_on_failure` is set.
+///
+/// The list is read when the node starts, so changing it mid-iteration only takes effect the
+/// next time the node runs. An empty list succeeds without running the child.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct ForEach {
+    #[serde(default)]
+    pub list: BehaviorPropStr,
+    #[serde(default)]
+    pub item: BehaviorPropStr,
+    #[serde(default)]
+    pub continue_on_failure: bool,
+    #[serde(skip)]
+    #[reflect(ignore)]
+    pub items: Vec<Dynamic>,
+    /// Element being processed
+    #[serde(skip)]
+    pub index: Option<usize>,
+}
+
+impl BehaviorSpec for ForEach {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "ForEach";
+    const ICON: &'static str = "∀";
+    const DESC: &'static str = "Run a child once per element of a blackboard list, binding \
+    each element to a blackboard key";
+}
+
+impl BehaviorUI for ForEach {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, list, state, ui, type_registry);
+        changed |= behavior_ui!(self, item, state, ui, type_registry);
+        changed |= ui
+            .checkbox(&mut self.continue_on_failure, "continue_on_failure")
+            .changed();
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, list, state, ui, type_registry);
+        behavior_ui_readonly!(self, item, state, ui, type_registry);
+        property_ui_readonly!(self, continue_on_failure, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, index, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut for_eaches: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut ForEach,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        (With<ForEach>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, children, mut for_each, node, started) in &mut for_eaches {
+        if let BehaviorPropValue::None = for_each.list.value {
+            let result = for_each.list.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = for_each.item.value {
+            let result = for_each.item.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(list), BehaviorPropValue::Some(item)) =
+            (for_each.list.value.clone(), for_each.item.value.clone())
+        {
+            if children.len() != 1 {
+                error!("Decorator node requires one child");
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+
+            // Snapshot the list, it may change while iterating
+            if started.is_some() {
+                let items = match scripts.get_blackboard(node, &list) {
+                    Ok(Some(items)) => items.try_cast::<Array>(),
+                    Ok(None) => None,
+                    Err(err) => {
+                        error!("Cannot read {}: {}", list, err);
+                        None
+                    }
+                };
+                let Some(items) = items else {
+                    error!("Blackboard key {} is not a list", list);
+                    commands.entity(entity).insert(BehaviorFailure);
+                    continue;
+                };
+                for_each.items = items;
+                for_each.index = None;
+            }
+
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                let next = for_each.index.map_or(0, |index| index + 1);
+                // Child failed, so we fail unless we keep going
+                if child_failure.is_some() && !for_each.continue_on_failure {
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+                // Child completed, reset and run the next element
+                else if child_failure.is_some() || child_success.is_some() {
+                    if next < for_each.items.len() {
+                        commands.entity(entity).remove::<BehaviorRunning>();
+                    } else {
+                        commands.entity(entity).insert(BehaviorSuccess);
+                    }
+                }
+                // All elements processed, or none to start with
+                else if next >= for_each.items.len() {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                // Child is ready, bind the element and pass on cursor
+                else {
+                    let element = for_each.items[next].clone();
+                    if let Err(err) = scripts.set_blackboard(node, &item, element) {
+                        error!("Cannot write {}: {}", item, err);
+                        commands.entity(entity).insert(BehaviorFailure);
+                        continue;
+                    }
+                    for_each.index = Some(next);
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                }
+            }
+        }
+    }
+}
@@ -1,17 +1,27 @@
+pub mod chance;
+pub mod cooldown;
 pub mod delay;
+pub mod for_each;
 pub mod guard;
 pub mod identity;
 pub mod inverter;
 pub mod repeater;
+pub mod retry;
+pub mod retry_until_success;
 pub mod subtree;
 pub mod succeeder;
 pub mod timeout;
 
+pub use chance::{Chance, ChanceMode};
+pub use cooldown::Cooldown;
 pub use delay::Delay;
+pub use for_each::ForEach;
 pub use guard::Guard;
 pub use identity::Identity;
 pub use inverter::Inverter;
 pub use repeater::Repeater;
+pub use retry::Retry;
+pub use retry_until_success::RetryUntilSuccess;
 pub use subtree::Subtree;
 pub use succeeder::Succeeder;
 pub use timeout::Timeout;
@@ -0,0 +1,156 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Retry a failing child, waiting longer between each attempt.
+/// The wait before retry `n` is `backoff * 2^(n-1)` seconds.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct Retry {
+    #[serde(default)]
+    pub max_retries: BehaviorPropGeneric<i64>,
+    #[serde(default)]
+    pub backoff: BehaviorPropGeneric<f64>,
+    #[serde(skip)]
+    pub attempt: u64,
+    #[serde(skip)]
+    pub waiting: Option<f64>,
+}
+
+impl BehaviorSpec for Retry {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Retry";
+    const ICON: &'static str = "↺";
+    const DESC: &'static str = "Retry a failing child up to a number of times, waiting an \
+    increasing backoff delay between attempts";
+}
+
+impl BehaviorUI for Retry {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        let mut changed = false;
+        changed |= behavior_ui!(self, max_retries, state, ui, type_registry);
+        changed |= behavior_ui!(self, backoff, state, ui, type_registry);
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        behavior_ui_readonly!(self, max_retries, state, ui, type_registry);
+        behavior_ui_readonly!(self, backoff, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, attempt, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut retries: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut Retry,
+            &BehaviorNode,
+            Option<&BehaviorStarted>,
+        ),
+        (With<Retry>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    mut scripts: ScriptQueries,
+) {
+    for (entity, children, mut retry, node, started) in &mut retries {
+        if let BehaviorPropValue::None = retry.max_retries.value {
+            let result = retry.max_retries.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let BehaviorPropValue::None = retry.backoff.value {
+            let result = retry.backoff.fetch(node, &mut scripts);
+            if let Some(Err(err)) = result {
+                error!("Script errored: {:?}", err);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+
+        if let (BehaviorPropValue::Some(max_retries), BehaviorPropValue::Some(backoff)) =
+            (retry.max_retries.value.clone(), retry.backoff.value.clone())
+        {
+            if children.len() != 1 {
+                error!("Decorator node requires one child");
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+
+            if started.is_some() {
+                retry.attempt = 0;
+                retry.waiting = None;
+            }
+
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                // Child failed, retry unless we ran out of retries
+                if child_failure.is_some() {
+                    let retries = retry.attempt.saturating_sub(1);
+                    if retries >= max_retries.max(0) as u64 {
+                        commands.entity(entity).insert(BehaviorFailure);
+                        continue;
+                    }
+
+                    // Wait for the backoff delay before retrying
+                    let elapsed = time.elapsed_seconds_f64();
+                    let delay = backoff * 2.0_f64.powi(retries as i32);
+                    let waiting = *retry.waiting.get_or_insert(elapsed);
+                    if elapsed - waiting < delay - f64::EPSILON {
+                        continue;
+                    }
+
+                    // Reset and run the child again
+                    retry.waiting = None;
+                    commands.entity(entity).remove::<BehaviorRunning>();
+                }
+                // Child succeeded, so we succeed
+                else if child_success.is_some() {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                // Child is ready, pass on cursor
+                else {
+                    retry.attempt += 1;
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands
+                        .entity(child_entity)
+                        .insert(BehaviorCursor::Delegate);
+                }
+            }
+        }
+    }
+}
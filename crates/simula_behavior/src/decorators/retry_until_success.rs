@@ -0,0 +1,117 @@
+use crate::{prelude::*, property_ui_readonly};
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Run a child again every time it fails, succeeding as soon as it succeeds. After
+/// `max_attempts` failed attempts it fails, never when `max_attempts` is None. The child
+/// always runs at least once.
+#[derive(
+    Debug, Default, Component, Reflect, FromReflect, Clone, Deserialize, Serialize, InspectorOptions,
+)]
+#[reflect(InspectorOptions)]
+pub struct RetryUntilSuccess {
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    #[serde(skip)]
+    pub attempts: u32,
+}
+
+impl BehaviorSpec for RetryUntilSuccess {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Retry Until Success";
+    const ICON: &'static str = "⟲";
+    const DESC: &'static str = "Run a child again while it fails, up to a number of attempts, \
+    succeeding when it succeeds";
+}
+
+impl BehaviorUI for RetryUntilSuccess {
+    fn ui(
+        &mut self,
+        _label: Option<&str>,
+        _state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) -> bool {
+        ui.horizontal(|ui| {
+            ui.label("max_attempts");
+            reflect_ui(self.max_attempts.as_reflect_mut(), ui, type_registry)
+        })
+        .inner
+    }
+
+    fn ui_readonly(
+        &self,
+        _label: Option<&str>,
+        state: Option<protocol::BehaviorState>,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        type_registry: &bevy::reflect::TypeRegistry,
+    ) {
+        property_ui_readonly!(self, max_attempts, state, ui, type_registry);
+        match state {
+            Some(_) => {
+                property_ui_readonly!(self, attempts, state, ui, type_registry);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut retries: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut RetryUntilSuccess,
+            Option<&BehaviorStarted>,
+        ),
+        (With<RetryUntilSuccess>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    for (entity, children, mut retry, started) in &mut retries {
+        if children.len() != 1 {
+            error!("Decorator node requires one child");
+            commands.entity(entity).insert(BehaviorFailure);
+            continue;
+        }
+
+        // Entered from the parent, attempts start over
+        if started.is_some() {
+            retry.attempts = 0;
+        }
+
+        let child_entity = children[0]; // Safe because we checked for empty
+        if let Ok(BehaviorChildQueryItem {
+            child_entity,
+            child_parent: _,
+            child_failure,
+            child_success,
+            child_running: _,
+        }) = nodes.get(child_entity)
+        {
+            // Child failed, run it again unless out of attempts
+            if child_failure.is_some() {
+                let attempts = retry.attempts;
+                if retry.max_attempts.map_or(false, |max| attempts >= max) {
+                    commands.entity(entity).insert(BehaviorFailure);
+                } else {
+                    commands.entity(entity).remove::<BehaviorRunning>();
+                }
+            }
+            // Child succeeded, so we succeed
+            else if child_success.is_some() {
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            // Child is ready, pass on cursor
+            else {
+                retry.attempts = retry.attempts.saturating_add(1);
+                commands.entity(entity).remove::<BehaviorCursor>();
+                commands
+                    .entity(child_entity)
+                    .insert(BehaviorCursor::Delegate);
+            }
+        }
+    }
+}
@@ -1,10 +1,15 @@
-use crate::prelude::*;
+use crate::{asset::INLINE_PREFIX, prelude::*, BehaviorTree};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::fmt::Debug;
 
 /// Subtree connects a behavior subtree to the current behavior tree.
+/// Assets starting with `#` reference behaviors defined inline in the same file.
+///
+/// Opaque subtrees, the default, show up in telemetry as a single node with the overall state.
+/// Transparent ones also report the nodes of the subtree, shown inline under the subtree node.
+/// Either way, the parent only sees the subtree succeed or fail as a whole.
 #[derive(Debug, Component, Reflect, FromReflect, Clone, Default, Deserialize, Serialize)]
 pub struct Subtree<T: BehaviorFactory> {
     /// Behavior asset to load.
@@ -12,6 +17,9 @@ pub struct Subtree<T: BehaviorFactory> {
     /// Unload the subtree when completed.
     #[serde(default)]
     pub unload: bool,
+    /// Expose the subtree nodes in telemetry.
+    #[serde(default)]
+    pub transparent: bool,
     #[serde(skip)]
     #[reflect(ignore)]
     phantom: std::marker::PhantomData<T>,
@@ -41,17 +49,39 @@ pub fn run<T: BehaviorFactory>(
     mut subtrees: Query<
         (
             Entity,
-            &BehaviorChildren,
+            Option<&BehaviorChildren>,
             &Subtree<T>,
+            &BehaviorNode,
             Option<&BehaviorTree<T>>,
         ),
         BehaviorRunQuery,
     >,
     nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    trees: Query<(Option<&Handle<BehaviorAsset<T>>>, Option<&BehaviorNode>)>,
+    behavior_assets: Res<Assets<BehaviorAsset<T>>>,
     asset_server: Res<AssetServer>,
 ) {
-    for (entity, children, subtree, child_tree) in &mut subtrees {
+    for (entity, children, subtree, node, child_tree) in &mut subtrees {
+        let children = children.map(|children| children.as_slice()).unwrap_or(&[]);
         if child_tree.is_none() {
+            if let Some(name) = subtree.asset.strip_prefix(INLINE_PREFIX) {
+                // Inline behaviors live in the asset of the outermost tree
+                let Some(inline) = find_asset(node.tree, &trees)
+                    .and_then(|handle| behavior_assets.get(handle))
+                    .and_then(|asset| asset.behavior.inline().get(name))
+                else {
+                    error!("Unknown inline behavior: {}", name);
+                    commands.entity(entity).insert(BehaviorFailure);
+                    continue;
+                };
+                let root = BehaviorTree::insert_tree(entity, Some(entity), &mut commands, inline);
+                commands
+                    .entity(entity)
+                    .add_child(root)
+                    .insert(BehaviorChildren(vec![root]))
+                    .insert(BehaviorTree::<T>::default());
+                continue;
+            }
             let behavior_document: Handle<BehaviorDocument> =
                 asset_server.load(subtree.asset.as_ref());
             commands
@@ -107,3 +137,18 @@ pub fn run<T: BehaviorFactory>(
         }
     }
 }
+
+/// Walk up nested trees until one has a behavior asset
+fn find_asset<'a, T: BehaviorFactory>(
+    tree: Entity,
+    trees: &'a Query<(Option<&Handle<BehaviorAsset<T>>>, Option<&BehaviorNode>)>,
+) -> Option<&'a Handle<BehaviorAsset<T>>> {
+    let mut tree = tree;
+    loop {
+        match trees.get(tree) {
+            Ok((Some(handle), _)) => return Some(handle),
+            Ok((None, Some(node))) if node.tree != tree => tree = node.tree,
+            _ => return None,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{prelude::*, property_ui_readonly};
+use crate::{prelude::*, property_ui_readonly, reset_children};
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -15,7 +15,8 @@ pub struct Timeout {
     pub start: f64,
 }
 
-/// Timeout will fail if its child does not return within the given time limit.
+/// Timeout will fail if its child does not return within the given time limit. A child
+/// still running at the deadline is reset, so it does not keep running.
 impl BehaviorSpec for Timeout {
     const TYPE: BehaviorType = BehaviorType::Decorator;
     const NAME: &'static str = "Timeout";
@@ -60,7 +61,7 @@ pub fn run(
         (
             Entity,
             &mut Timeout,
-            &mut BehaviorChildren,
+            &BehaviorChildren,
             &BehaviorNode,
             Option<&BehaviorStarted>,
             Option<&BehaviorCursor>,
@@ -68,6 +69,15 @@ pub fn run(
         (With<Timeout>, BehaviorIdleQuery),
     >,
     nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    subtree: Query<
+        (Entity, Option<&BehaviorChildren>),
+        Or<(
+            With<BehaviorCursor>,
+            With<BehaviorRunning>,
+            With<BehaviorSuccess>,
+            With<BehaviorFailure>,
+        )>,
+    >,
     mut scripts: ScriptQueries,
 ) {
     for (entity, mut timeout, children, node, started, cursor) in &mut timeouts {
@@ -93,7 +103,9 @@ pub fn run(
             }
 
             if elapsed - timeout.start > timeout_duration - f64::EPSILON {
-                // Time limit reached, short circuit by forcing a cursor and fail
+                // Time limit reached, reset the child, then short circuit by forcing a cursor
+                // and fail
+                reset_children(&mut commands, children, &subtree);
                 commands
                     .entity(entity)
                     .insert(BehaviorCursor::Return)
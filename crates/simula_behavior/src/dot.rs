@@ -0,0 +1,109 @@
+use crate::{inspector::BehaviorStyles, Behavior, BehaviorFactory, BehaviorType};
+use std::fmt::Write;
+
+/// Export a behavior tree as a Graphviz DOT digraph, e.g. to render it with `dot -Tpng`.
+///
+/// Every behavior is a node labelled with its name and behavior label, shaped by type and
+/// colored like in the inspector. Edges go from parents to children, in child order.
+pub fn behavior_to_dot<T: BehaviorFactory>(behavior: &Behavior<T>) -> String {
+    let styles = BehaviorStyles::default();
+    let mut dot = String::new();
+    dot.push_str("digraph behavior {\n");
+    dot.push_str("    node [style=filled, fontcolor=white];\n");
+    write_node(&mut dot, &styles, behavior, &mut 0);
+    dot.push_str("}\n");
+    dot
+}
+
+// Write a node and its subtree, returning the node id
+fn write_node<T: BehaviorFactory>(
+    dot: &mut String,
+    styles: &BehaviorStyles,
+    behavior: &Behavior<T>,
+    count: &mut usize,
+) -> usize {
+    let id = *count;
+    *count += 1;
+
+    let data = behavior.data();
+    let shape = match data.typ() {
+        BehaviorType::Action => "ellipse",
+        BehaviorType::Composite => "box",
+        BehaviorType::Decorator => "hexagon",
+        BehaviorType::Subtree => "folder",
+    };
+    let [r, g, b, _] = styles.color(data.typ(), data.label()).as_rgba_u8();
+    let _ = writeln!(
+        dot,
+        "    n{} [label=\"{}\\n{}\", shape={}, fillcolor=\"#{:02x}{:02x}{:02x}\"];",
+        id,
+        escape(behavior.name()),
+        escape(data.label()),
+        shape,
+        r,
+        g,
+        b
+    );
+
+    for child in behavior.nodes() {
+        let child_id = write_node(dot, styles, child, count);
+        let _ = writeln!(dot, "    n{} -> n{};", id, child_id);
+    }
+    id
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test::TestBehavior};
+    use bevy::prelude::default;
+
+    #[test]
+    fn dot_has_nodes_and_edges() {
+        let behavior = Behavior::new(
+            "Patrol",
+            TestBehavior::Sequencer(Sequencer::default()),
+            default(),
+            vec![
+                Behavior::new(
+                    "Say \"hi\"",
+                    TestBehavior::Debug(Debug::default()),
+                    default(),
+                    vec![],
+                ),
+                Behavior::new(
+                    "Twice",
+                    TestBehavior::Repeater(Repeater::default()),
+                    default(),
+                    vec![Behavior::new(
+                        "Rest",
+                        TestBehavior::Wait(Wait::default()),
+                        default(),
+                        vec![],
+                    )],
+                ),
+            ],
+        );
+        let dot = behavior_to_dot(&behavior);
+        let lines = dot.lines().map(str::trim).collect::<Vec<_>>();
+
+        assert_eq!(lines.first(), Some(&"digraph behavior {"));
+        assert_eq!(lines.last(), Some(&"}"));
+        for line in [
+            r##"n0 [label="Patrol\nSequencer", shape=box, fillcolor="#225522"];"##,
+            r##"n1 [label="Say \"hi\"\nDebug", shape=ellipse, fillcolor="#223355"];"##,
+            r##"n2 [label="Twice\nRepeater", shape=hexagon, fillcolor="#444400"];"##,
+            r##"n3 [label="Rest\nWait", shape=ellipse, fillcolor="#223355"];"##,
+            "n0 -> n1;",
+            "n0 -> n2;",
+            "n2 -> n3;",
+        ] {
+            assert!(lines.contains(&line), "missing line: {}", line);
+        }
+        assert_eq!(lines.iter().filter(|line| line.contains("->")).count(), 3);
+    }
+}
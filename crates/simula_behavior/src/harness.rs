@@ -0,0 +1,268 @@
+use crate::{
+    asset::{trim_extension, validate_inline, BEHAVIOR_EXTENSIONS},
+    prelude::*,
+};
+use bevy::{app::AppExit, prelude::*};
+use serde::{Deserialize, Serialize};
+use simula_script::ScriptContext;
+use std::{marker::PhantomData, path::PathBuf};
+
+/// Run every behavior tree in a directory as a test, each to completion, and report which
+/// passed or failed. A tree passes when its root succeeds, and fails when it fails, with its
+/// root outcome as the reason. Trees still running after `max_ticks` updates time out and
+/// fail. Trees of type T need BehaviorPlugin and BehaviorTreePlugin<T> to run.
+///
+/// With `exit`, the process exits once all trees completed, with code 1 if any failed, for
+/// running behavior tests on CI from a small headless binary.
+pub struct BehaviorTestPlugin<T> {
+    pub dir: PathBuf,
+    pub max_ticks: u32,
+    pub exit: bool,
+    pub _phantom: PhantomData<T>,
+}
+
+impl<T> BehaviorTestPlugin<T> {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_ticks: 1000,
+            exit: true,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Plugin for BehaviorTestPlugin<T>
+where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    fn build(&self, app: &mut App) {
+        app.insert_resource(BehaviorTestConfig {
+            dir: self.dir.clone(),
+            max_ticks: self.max_ticks,
+            exit: self.exit,
+        })
+        .init_resource::<BehaviorTestReport>()
+        .add_startup_system(load::<T>)
+        .add_system(verdict.in_base_set(CoreSet::Last));
+    }
+}
+
+#[derive(Debug, Resource)]
+pub struct BehaviorTestConfig {
+    pub dir: PathBuf,
+    pub max_ticks: u32,
+    pub exit: bool,
+}
+
+/// Tree run as a test
+#[derive(Debug, Component)]
+pub struct BehaviorTestCase {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BehaviorVerdict {
+    Passed,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorTestResult {
+    pub name: String,
+    pub verdict: BehaviorVerdict,
+    pub ticks: u32,
+}
+
+/// Results of the trees run as tests, complete once nothing is pending
+#[derive(Debug, Default, Resource)]
+pub struct BehaviorTestReport {
+    pub results: Vec<BehaviorTestResult>,
+    pub pending: usize,
+    pub ticks: u32,
+    pub done: bool,
+}
+
+impl BehaviorTestReport {
+    pub fn passed(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.verdict == BehaviorVerdict::Passed)
+            .count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        if self.failed() > 0 {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// One line per test, in name order, followed by the totals
+    pub fn summary(&self) -> String {
+        let mut results = self.results.iter().collect::<Vec<_>>();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut summary = String::new();
+        for result in results {
+            match &result.verdict {
+                BehaviorVerdict::Passed => {
+                    summary += &format!("PASS {} ({} ticks)\n", result.name, result.ticks)
+                }
+                BehaviorVerdict::Failed(reason) => {
+                    summary += &format!(
+                        "FAIL {} ({} ticks): {}\n",
+                        result.name, result.ticks, reason
+                    )
+                }
+            }
+        }
+        summary += &format!("{} passed, {} failed", self.passed(), self.failed());
+        summary
+    }
+
+    fn record(&mut self, name: &str, verdict: BehaviorVerdict) {
+        self.results.push(BehaviorTestResult {
+            name: name.to_string(),
+            verdict,
+            ticks: self.ticks,
+        });
+    }
+}
+
+/// Load the test trees, trees failing to load fail right away
+fn load<T>(
+    mut commands: Commands,
+    config: Res<BehaviorTestConfig>,
+    mut report: ResMut<BehaviorTestReport>,
+    mut behavior_assets: ResMut<Assets<BehaviorAsset<T>>>,
+    mut script_ctxs: ResMut<Assets<ScriptContext>>,
+) where
+    T: BehaviorFactory + for<'de> Deserialize<'de>,
+{
+    let mut paths = match std::fs::read_dir(&config.dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                let path = path.to_string_lossy();
+                BEHAVIOR_EXTENSIONS
+                    .iter()
+                    .any(|ext| path.ends_with(&format!(".{}", ext)))
+            })
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            error!("Cannot read behavior tests {:?}: {}", config.dir, err);
+            vec![]
+        }
+    };
+    paths.sort();
+
+    for path in paths {
+        let name = path
+            .file_name()
+            .map(|file_name| trim_extension(&file_name.to_string_lossy()).to_string())
+            .unwrap_or_default();
+        let behavior = std::fs::read(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| BehaviorDocument::from_bytes(&bytes).map_err(|err| err.to_string()))
+            .and_then(|document| document.to_behavior::<T>())
+            .and_then(|behavior| validate_inline(&behavior).map(|_| behavior));
+        match behavior {
+            Ok(behavior) => {
+                let behavior_handle = behavior_assets.add(BehaviorAsset {
+                    behavior,
+                    file_name: Some(path.to_string_lossy().to_string().into()),
+                });
+                let script_ctx_handle = script_ctxs.add(BehaviorTree::<T>::create_script_context());
+                commands.spawn((
+                    Name::new(format!("Test: {}", name)),
+                    BehaviorTestCase { name },
+                    behavior_handle,
+                    script_ctx_handle,
+                    BehaviorTree::<T>::default(),
+                    BehaviorTreeReset::<T>::default(),
+                ));
+                report.pending += 1;
+            }
+            Err(err) => {
+                report.record(
+                    &name,
+                    BehaviorVerdict::Failed(format!("Cannot load: {}", err)),
+                );
+            }
+        }
+    }
+}
+
+/// Record completed trees, time out the rest, and report once all are done
+fn verdict(
+    mut commands: Commands,
+    config: Res<BehaviorTestConfig>,
+    mut report: ResMut<BehaviorTestReport>,
+    cases: Query<(Entity, &BehaviorTestCase)>,
+    roots: Query<
+        (
+            &BehaviorNode,
+            Option<&BehaviorSuccess>,
+            Option<&BehaviorOutcome>,
+            &Name,
+        ),
+        (
+            Without<BehaviorParent>,
+            Or<(With<BehaviorSuccess>, With<BehaviorFailure>)>,
+        ),
+    >,
+    mut exit: EventWriter<AppExit>,
+) {
+    if report.done {
+        return;
+    }
+    report.ticks += 1;
+
+    let mut completed = vec![];
+    for (node, success, outcome, name) in &roots {
+        let Ok((entity, case)) = cases.get(node.tree) else {
+            continue;
+        };
+        let verdict = match (success, outcome) {
+            (Some(_), _) => BehaviorVerdict::Passed,
+            (None, Some(outcome)) if !outcome.reason.is_empty() => {
+                BehaviorVerdict::Failed(outcome.reason.clone())
+            }
+            (None, _) => BehaviorVerdict::Failed(format!("{} failed", name)),
+        };
+        report.record(&case.name, verdict);
+        report.pending -= 1;
+        commands.entity(entity).despawn_recursive();
+        completed.push(entity);
+    }
+
+    if report.pending > 0 && report.ticks >= config.max_ticks {
+        for (entity, case) in &cases {
+            if completed.contains(&entity) {
+                continue;
+            }
+            let reason = format!("Timed out after {} ticks", config.max_ticks);
+            report.record(&case.name, BehaviorVerdict::Failed(reason));
+            commands.entity(entity).despawn_recursive();
+        }
+        report.pending = 0;
+    }
+
+    if report.pending == 0 {
+        report.done = true;
+        if report.failed() > 0 {
+            error!("Behavior tests failed\n{}", report.summary());
+        } else {
+            info!("Behavior tests passed\n{}", report.summary());
+        }
+        if config.exit {
+            exit.send(AppExit);
+            std::process::exit(report.exit_code());
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::prelude::*;
-use bevy::{prelude::*, reflect::TypeRegistry};
+use bevy::{
+    prelude::*,
+    reflect::{ReflectMut, TypeRegistry},
+};
 
 pub trait BehaviorUI
 where
@@ -13,12 +16,23 @@ where
         ui: &mut bevy_inspector_egui::egui::Ui,
         type_registry: &TypeRegistry,
     ) -> bool {
-        let type_registry = type_registry.read();
-        bevy_inspector_egui::reflect_inspector::ui_for_value(
-            self.as_reflect_mut(),
-            ui,
-            &type_registry,
-        )
+        let ReflectMut::Struct(value) = self.reflect_mut() else {
+            return reflect_ui(self.as_reflect_mut(), ui, type_registry);
+        };
+        let mut changed = false;
+        for index in 0..value.field_len() {
+            let name = value.name_at(index).map(str::to_string);
+            let Some(field) = value.field_at_mut(index) else {
+                continue;
+            };
+            ui.horizontal(|ui| {
+                if let Some(name) = name {
+                    ui.label(name);
+                }
+                changed |= reflect_ui(field, ui, type_registry);
+            });
+        }
+        changed
     }
 
     /// ui readonly inspector for behavior properties
@@ -0,0 +1,280 @@
+use crate::{
+    inspector::{
+        graph::{
+            BehaviorData, BehaviorDataType, BehaviorEditorState, BehaviorGraphState,
+            BehaviorNodeData, BehaviorNodeTemplate,
+        },
+        utils,
+    },
+    BehaviorFactory, BehaviorType,
+};
+use bevy::utils::HashSet;
+use egui_node_graph::{NodeId, NodeTemplateTrait};
+use simula_inspector::egui;
+
+/// A node copied to the clipboard
+#[derive(Clone)]
+pub struct BehaviorClip<T: BehaviorFactory> {
+    pub name: String,
+    pub data: T,
+    /// Position relative to the top left of the copied nodes
+    pub offset: egui::Vec2,
+}
+
+/// Nodes copied from a graph, with the connections among them. The root node is never
+/// copied, and connections to nodes left out of the copy are dropped.
+#[derive(Clone)]
+pub struct BehaviorClipboard<T: BehaviorFactory> {
+    pub nodes: Vec<BehaviorClip<T>>,
+    /// Parent and child indices into `nodes`, in output order of the parent
+    pub connections: Vec<(usize, usize)>,
+}
+
+impl<T: BehaviorFactory> BehaviorClipboard<T> {
+    /// Copy nodes of a graph, with their subtrees when `subtree` is set. None when there is
+    /// nothing to copy.
+    pub fn copy(editor: &BehaviorEditorState<T>, nodes: &[NodeId], subtree: bool) -> Option<Self> {
+        let mut copied: Vec<NodeId> = vec![];
+        let mut pending = nodes.to_vec();
+        pending.reverse();
+        while let Some(node_id) = pending.pop() {
+            let Some(node) = editor.graph.nodes.get(node_id) else {
+                continue;
+            };
+            if let BehaviorData::Root = node.user_data.data {
+                continue;
+            }
+            if copied.contains(&node_id) {
+                continue;
+            }
+            copied.push(node_id);
+            if subtree {
+                let mut children = utils::get_flow_children(&editor.graph, node_id);
+                children.reverse();
+                pending.extend(children);
+            }
+        }
+        if copied.is_empty() {
+            return None;
+        }
+
+        let positions = copied
+            .iter()
+            .map(|node_id| {
+                editor
+                    .node_positions
+                    .get(*node_id)
+                    .copied()
+                    .unwrap_or(egui::Pos2::ZERO)
+            })
+            .collect::<Vec<_>>();
+        let min = positions
+            .iter()
+            .fold(egui::pos2(f32::INFINITY, f32::INFINITY), |min, pos| {
+                min.min(*pos)
+            });
+
+        let mut clipboard = Self {
+            nodes: vec![],
+            connections: vec![],
+        };
+        for (node_id, position) in copied.iter().zip(positions) {
+            let node = &editor.graph.nodes[*node_id];
+            if let BehaviorData::Behavior(data) = &node.user_data.data {
+                clipboard.nodes.push(BehaviorClip {
+                    name: node.label.clone(),
+                    data: data.clone(),
+                    offset: position - min,
+                });
+            }
+        }
+        for (parent, node_id) in copied.iter().enumerate() {
+            for child_id in utils::get_flow_children(&editor.graph, *node_id) {
+                if let Some(child) = copied.iter().position(|id| *id == child_id) {
+                    clipboard.connections.push((parent, child));
+                }
+            }
+        }
+        Some(clipboard)
+    }
+
+    /// Paste new nodes with their top left at a position, connected like the copied nodes.
+    /// Pasted nodes become the selection, returns their ids.
+    pub fn paste(
+        &self,
+        editor: &mut BehaviorEditorState<T>,
+        graph_state: &mut BehaviorGraphState,
+        position: egui::Pos2,
+    ) -> Vec<NodeId> {
+        let mut pasted = vec![];
+        for clip in &self.nodes {
+            let template = BehaviorNodeTemplate::Behavior(clip.data.clone());
+            let node_data = BehaviorNodeData {
+                data: BehaviorData::Behavior(clip.data.clone()),
+                state: None,
+                entity: None,
+                cost: None,
+                duration: 0.0,
+                outcome: None,
+            };
+            let node_id = editor
+                .graph
+                .add_node(clip.name.clone(), node_data, |graph, node_id| {
+                    template.build_node(graph, graph_state, node_id)
+                });
+            editor
+                .node_positions
+                .insert(node_id, position + clip.offset);
+            editor.node_order.push(node_id);
+            pasted.push(node_id);
+        }
+
+        let mut connected = HashSet::new();
+        for (parent, child) in &self.connections {
+            let (parent_id, child_id) = (pasted[*parent], pasted[*child]);
+            let output_id = editor.graph.nodes[parent_id]
+                .output_ids()
+                .find(|output_id| !connected.contains(output_id));
+            let output_id =
+                match output_id {
+                    Some(output_id) => output_id,
+                    None if is_composite(&self.nodes[*parent].data) => editor
+                        .graph
+                        .add_output_param(parent_id, "".into(), BehaviorDataType::Flow),
+                    None => continue,
+                };
+            let Some(input_id) = editor.graph.nodes[child_id].input_ids().next() else {
+                continue;
+            };
+            editor.graph.add_connection(output_id, input_id);
+            connected.insert(output_id);
+        }
+
+        // composites keep one free output to connect more children
+        for (node_id, clip) in pasted.iter().zip(&self.nodes) {
+            let free = editor.graph.nodes[*node_id]
+                .output_ids()
+                .any(|output_id| !connected.contains(&output_id));
+            if is_composite(&clip.data) && !free {
+                editor
+                    .graph
+                    .add_output_param(*node_id, "".into(), BehaviorDataType::Flow);
+            }
+        }
+
+        editor.selected_nodes = pasted.clone();
+        pasted
+    }
+}
+
+fn is_composite<T: BehaviorFactory>(data: &T) -> bool {
+    data.typ() == BehaviorType::Composite
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::TestBehavior, Behavior};
+
+    fn editor() -> BehaviorEditorState<TestBehavior> {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(
+            r#"
+            (
+                "Sequence",
+                Sequencer(()),
+                [
+                    ("First", Debug(()), [], (pos: (100.0, 0.0))),
+                    ("Second", Wait(()), [], (pos: (100.0, 50.0))),
+                ],
+            )
+            "#,
+        )
+        .unwrap();
+        utils::behavior_graph(&mut BehaviorGraphState::default(), &behavior)
+    }
+
+    fn nodes(editor: &BehaviorEditorState<TestBehavior>, name: &str) -> Vec<NodeId> {
+        editor
+            .graph
+            .nodes
+            .iter()
+            .filter(|(_, node)| node.label == name)
+            .map(|(node_id, _)| node_id)
+            .collect()
+    }
+
+    fn data(editor: &BehaviorEditorState<TestBehavior>, node_id: NodeId) -> String {
+        match &editor.graph.nodes[node_id].user_data.data {
+            BehaviorData::Behavior(data) => ron::to_string(data).unwrap(),
+            BehaviorData::Root => "Root".into(),
+        }
+    }
+
+    #[test]
+    fn copy_paste_nodes() {
+        let mut editor = editor();
+        let sequence = nodes(&editor, "Sequence")[0];
+        let first = nodes(&editor, "First")[0];
+        let root = editor.graph.nodes[sequence].inputs[0].1;
+        let root = editor.graph.connection(root);
+
+        let clipboard = BehaviorClipboard::copy(&editor, &[sequence, first], false).unwrap();
+        assert_eq!(clipboard.nodes.len(), 2);
+        assert_eq!(clipboard.connections, vec![(0, 1)]);
+
+        let mut graph_state = BehaviorGraphState::default();
+        let pasted = clipboard.paste(&mut editor, &mut graph_state, egui::pos2(0.0, 300.0));
+        assert_eq!(pasted.len(), 2);
+        assert!(!pasted.contains(&sequence) && !pasted.contains(&first));
+        assert_eq!(editor.selected_nodes, pasted);
+        assert_eq!(data(&editor, pasted[0]), data(&editor, sequence));
+        assert_eq!(data(&editor, pasted[1]), data(&editor, first));
+        assert_eq!(editor.node_positions[pasted[0]], egui::pos2(0.0, 300.0));
+        assert_eq!(editor.node_positions[pasted[1]], egui::pos2(100.0, 300.0));
+
+        // pasted nodes are connected among themselves only
+        assert_eq!(
+            utils::get_flow_children(&editor.graph, pasted[0]),
+            vec![pasted[1]]
+        );
+        let input = editor.graph.nodes[pasted[0]].inputs[0].1;
+        assert_eq!(editor.graph.connection(input), None);
+        assert_eq!(
+            editor
+                .graph
+                .connection(editor.graph.nodes[sequence].inputs[0].1),
+            root
+        );
+        assert_eq!(utils::get_flow_children(&editor.graph, sequence).len(), 2);
+
+        // editing a pasted node leaves the copied node alone
+        editor.graph.nodes[pasted[1]].user_data.data =
+            BehaviorData::Behavior(ron::from_str("Wait(())").unwrap());
+        assert_ne!(data(&editor, pasted[1]), data(&editor, first));
+        editor.graph.nodes[pasted[1]].label = "Pasted".into();
+        assert_eq!(editor.graph.nodes[first].label, "First");
+    }
+
+    #[test]
+    fn copy_subtree_not_root() {
+        let editor = editor();
+        let root = editor
+            .graph
+            .nodes
+            .iter()
+            .find(|(_, node)| matches!(node.user_data.data, BehaviorData::Root))
+            .map(|(node_id, _)| node_id)
+            .unwrap();
+        assert!(BehaviorClipboard::copy(&editor, &[root], true).is_none());
+
+        let sequence = nodes(&editor, "Sequence")[0];
+        let clipboard = BehaviorClipboard::copy(&editor, &[root, sequence], true).unwrap();
+        let names = clipboard
+            .nodes
+            .iter()
+            .map(|clip| clip.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Sequence", "First", "Second"]);
+        assert_eq!(clipboard.connections, vec![(0, 1), (0, 2)]);
+    }
+}
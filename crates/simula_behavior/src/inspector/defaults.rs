@@ -0,0 +1,58 @@
+use crate::BehaviorFactory;
+use bevy::{prelude::*, utils::HashMap};
+use std::mem::Discriminant;
+
+/// Behaviors that new nodes start as in the inspector node finder, replacing their `Default`.
+/// Set a project's conventions at startup, like new `Delay` nodes waiting half a second:
+///
+/// ```ignore
+/// app.world
+///     .resource_mut::<BehaviorDefaults<MyBehavior>>()
+///     .set(MyBehavior::Delay(Delay {
+///         duration: BehaviorPropGeneric {
+///             prop: BehaviorEval::Value(0.5),
+///             ..default()
+///         },
+///         ..default()
+///     }));
+/// ```
+#[derive(Resource)]
+pub struct BehaviorDefaults<T: BehaviorFactory> {
+    behaviors: HashMap<Discriminant<T>, T>,
+}
+
+impl<T: BehaviorFactory> Default for BehaviorDefaults<T> {
+    fn default() -> Self {
+        Self {
+            behaviors: HashMap::default(),
+        }
+    }
+}
+
+impl<T: BehaviorFactory> BehaviorDefaults<T> {
+    /// Start new nodes of this behavior variant as this behavior
+    pub fn set(&mut self, behavior: T) -> &mut Self {
+        self.behaviors
+            .insert(std::mem::discriminant(&behavior), behavior);
+        self
+    }
+
+    /// Go back to `Default` for new nodes of this behavior variant
+    pub fn reset(&mut self, behavior: &T) -> &mut Self {
+        self.behaviors.remove(&std::mem::discriminant(behavior));
+        self
+    }
+
+    /// Get the behavior new nodes of this variant start as, if set
+    pub fn get(&self, behavior: &T) -> Option<&T> {
+        self.behaviors.get(&std::mem::discriminant(behavior))
+    }
+
+    /// List all behaviors, as new nodes start
+    pub fn list(&self) -> Vec<T> {
+        T::list()
+            .into_iter()
+            .map(|behavior| self.get(&behavior).cloned().unwrap_or(behavior))
+            .collect()
+    }
+}
@@ -1,8 +1,14 @@
 use crate::{
+    inspector::{group::BehaviorGroup, timeline::BehaviorTimeline, undo::BehaviorUndo},
     prelude::*,
     protocol::{BehaviorState, RemoteEntity},
 };
-use bevy::{log::debug, prelude::*, reflect::TypeRegistryArc};
+use bevy::{
+    log::debug,
+    prelude::*,
+    reflect::TypeRegistryArc,
+    utils::{HashMap, HashSet},
+};
 use bevy_inspector_egui::egui::{self, Widget};
 use egui_node_graph::{
     DataTypeTrait, Graph, GraphEditorState, InputParamKind, NodeDataTrait, NodeId, NodeResponse,
@@ -14,11 +20,17 @@ use std::borrow::Cow;
 /// The NodeData holds a custom data struct inside each node. It's useful to
 /// store additional information that doesn't live in parameters. For this
 /// example, the node data stores the template (i.e. the "type") of the node.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BehaviorNodeData<T: BehaviorFactory> {
     pub data: BehaviorData<T>,
     pub state: Option<BehaviorState>,
     pub entity: Option<RemoteEntity>,
+    /// Rolling average tick cost in seconds, when profiling
+    pub cost: Option<f32>,
+    /// Seconds running since last entered, when timing
+    pub duration: f32,
+    /// Why the behavior completed, if known
+    pub outcome: Option<BehaviorOutcome>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -30,7 +42,7 @@ pub enum BehaviorData<T: BehaviorFactory> {
 /// `DataType`s are what defines the possible range of connections when
 /// attaching two ports together. The graph UI will make sure to not allow
 /// attaching incompatible datatypes.
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum BehaviorDataType {
     Flow,
 }
@@ -90,6 +102,22 @@ pub struct BehaviorGraphState {
     pub time: Time,
     pub blinker: SignalGenerator,
     pub root_node: Option<NodeId>,
+    /// When set, nodes outside of this set are dimmed
+    pub isolated: Option<HashSet<NodeId>>,
+    /// When set, show entity index and component count of nodes
+    pub entity_info: Option<HashMap<NodeId, (u32, usize)>>,
+    /// When set, color nodes by their cost relative to the most expensive node
+    pub heat: Option<HashMap<NodeId, f32>>,
+    /// Labeled boxes organizing nodes into regions
+    pub groups: Vec<BehaviorGroup>,
+    /// Nodes inside collapsed groups, dimmed
+    pub collapsed: HashSet<NodeId>,
+    /// Recent node states, recorded from telemetry
+    pub timeline: BehaviorTimeline,
+    /// Nodes of transparent subtrees, shown while running but not part of the behavior
+    pub expanded: HashSet<NodeId>,
+    /// Node colors and icons, from the `BehaviorStyles` resource
+    pub styles: BehaviorStyles,
 }
 
 impl Default for BehaviorGraphState {
@@ -107,6 +135,14 @@ impl Default for BehaviorGraphState {
                 ..default()
             },
             root_node: None,
+            isolated: None,
+            entity_info: None,
+            heat: None,
+            groups: vec![],
+            collapsed: HashSet::default(),
+            timeline: BehaviorTimeline::default(),
+            expanded: HashSet::default(),
+            styles: BehaviorStyles::default(),
         }
     }
 }
@@ -124,6 +160,12 @@ where
         graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
         user_state: &mut BehaviorGraphState,
     ) -> egui::Color32 {
+        // Dim connections outside the isolated branch
+        if let Some(isolated) = &user_state.isolated {
+            if !isolated.contains(&node_id) {
+                return egui::Color32::DARK_GRAY.linear_multiply(0.25);
+            }
+        }
         match self {
             BehaviorDataType::Flow => {
                 if let Some(node) = graph.nodes.get(node_id) {
@@ -172,11 +214,13 @@ where
     type UserState = BehaviorGraphState;
     type CategoryType = &'static str;
 
-    fn node_finder_label(&self, _user_state: &mut Self::UserState) -> Cow<'_, str> {
+    fn node_finder_label(&self, user_state: &mut Self::UserState) -> Cow<'_, str> {
         match self {
             BehaviorNodeTemplate::Root => Cow::Borrowed("Root"),
             BehaviorNodeTemplate::Behavior(behavior) => {
-                let label = format!("{} {}", behavior.icon(), behavior.label());
+                let styles = &user_state.styles;
+                let icon = styles.icon(behavior.typ(), behavior.label(), behavior.icon());
+                let label = format!("{} {}", icon, behavior.label());
                 label.into()
             }
         }
@@ -206,6 +250,9 @@ where
             },
             state: None,
             entity: None,
+            cost: None,
+            duration: 0.0,
+            outcome: None,
         }
     }
 
@@ -269,14 +316,64 @@ where
     }
 }
 
-#[derive(Default)]
+/// Behaviors offered by the node finder, as new nodes start
 pub struct BehaviorNodeTemplates<T> {
-    _marker: std::marker::PhantomData<T>,
+    pub kinds: Vec<T>,
 }
 
-impl<T> NodeTemplateIter for BehaviorNodeTemplates<T>
+impl<T> Default for BehaviorNodeTemplates<T>
 where
     T: BehaviorFactory,
+{
+    fn default() -> Self {
+        Self { kinds: T::list() }
+    }
+}
+
+/// Behaviors matching a node finder query, by label or category, case insensitive. Best
+/// matches come first: labels starting with the query, labels containing it, categories
+/// containing it, then labels holding its letters in order. All behaviors for no query.
+pub fn search_templates<T>(query: &str, kinds: &[T]) -> Vec<T>
+where
+    T: BehaviorFactory + BehaviorInspectable,
+{
+    let query = query.trim().to_lowercase();
+    let mut matches = kinds
+        .iter()
+        .filter_map(|kind| {
+            let label = kind.label().to_lowercase();
+            let rank = if label.starts_with(&query) {
+                0
+            } else if label.contains(&query) {
+                1
+            } else if kind
+                .categories()
+                .iter()
+                .any(|category| category.to_lowercase().contains(&query))
+            {
+                2
+            } else {
+                // fuzzy, every query letter in order
+                let mut letters = label.chars();
+                if !query.chars().all(|c| letters.any(|l| l == c)) {
+                    return None;
+                }
+                3
+            };
+            Some((rank, label.len(), kind))
+        })
+        .collect::<Vec<_>>();
+    // shorter labels are closer matches, ties keep the list order
+    matches.sort_by_key(|(rank, len, _)| (*rank, *len));
+    matches
+        .into_iter()
+        .map(|(_, _, kind)| kind.clone())
+        .collect()
+}
+
+impl<T> NodeTemplateIter for BehaviorNodeTemplates<T>
+where
+    T: BehaviorFactory + BehaviorInspectable,
 {
     type Item = BehaviorNodeTemplate<T>;
 
@@ -284,14 +381,24 @@ where
         // This function must return a list of node kinds, which the node finder
         // will use to display it to the user. Crates like strum can reduce the
         // boilerplate in enumerating all variants of an enum.
-        let kinds: Vec<BehaviorNodeTemplate<T>> = T::list()
-            .into_iter()
-            .map(|t| BehaviorNodeTemplate::Behavior(t))
+        let kinds: Vec<BehaviorNodeTemplate<T>> = self
+            .kinds
+            .iter()
+            .map(|t| BehaviorNodeTemplate::Behavior(t.clone()))
             .collect();
         // Do not add Root node to kinds
         // kinds.extend(vec![BehaviorNodeTemplate::Root]);
         kinds
     }
+
+    fn search(&self, query: &str) -> Option<Vec<Self::Item>> {
+        Some(
+            search_templates(query, &self.kinds)
+                .into_iter()
+                .map(BehaviorNodeTemplate::Behavior)
+                .collect(),
+        )
+    }
 }
 
 impl<T> WidgetValueTrait for BehaviorValueType<T>
@@ -359,16 +466,41 @@ where
         }
     }
 
+    fn dimmed(
+        &self,
+        node_id: NodeId,
+        _graph: &Graph<Self, Self::DataType, Self::ValueType>,
+        user_state: &mut Self::UserState,
+    ) -> bool {
+        user_state.collapsed.contains(&node_id)
+            || user_state
+                .isolated
+                .as_ref()
+                .map_or(false, |isolated| !isolated.contains(&node_id))
+    }
+
     fn titlebar_color(
         &self,
         _ui: &egui::Ui,
-        _node_id: NodeId,
+        node_id: NodeId,
         _graph: &Graph<Self, Self::DataType, Self::ValueType>,
-        _user_state: &mut Self::UserState,
+        user_state: &mut Self::UserState,
     ) -> Option<egui::Color32> {
         match &self.data {
             BehaviorData::Root => None,
-            BehaviorData::Behavior(behavior) => Some(to_bytes(&behavior.color())),
+            BehaviorData::Behavior(behavior) => {
+                let color = user_state.styles.color(behavior.typ(), behavior.label());
+                match user_state.heat.as_ref().and_then(|heat| heat.get(&node_id)) {
+                    // blend towards red by relative cost
+                    Some(heat) => Some(to_bytes(&Color::rgba(
+                        color.r() + (1.0 - color.r()) * heat,
+                        color.g() * (1.0 - heat),
+                        color.b() * (1.0 - heat),
+                        color.a(),
+                    ))),
+                    None => Some(to_bytes(&color)),
+                }
+            }
         }
     }
 
@@ -454,7 +586,9 @@ where
             match &node.user_data.data {
                 BehaviorData::Behavior(behavior) => {
                     // Behavior label
-                    let label = format!("{} {}", behavior.icon(), behavior.label());
+                    let styles = &user_state.styles;
+                    let icon = styles.icon(behavior.typ(), behavior.label(), behavior.icon());
+                    let label = format!("{} {}", icon, behavior.label());
                     let label = egui::RichText::new(label).color(egui::Color32::DARK_GRAY);
 
                     // Behavior label with tooltip
@@ -517,7 +651,7 @@ where
         ui: &mut egui::Ui,
         node_id: NodeId,
         _graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
-        _user_state: &mut Self::UserState,
+        user_state: &mut Self::UserState,
     ) -> Vec<NodeResponse<BehaviorResponse<T>, BehaviorNodeData<T>>>
     where
         T: BehaviorFactory,
@@ -525,6 +659,49 @@ where
     {
         let mut responses = vec![];
 
+        if user_state.expanded.contains(&node_id) {
+            ui.label(egui::RichText::new("⤷ subtree").small().weak());
+        }
+
+        // debug overlay, to find the node entity in the world inspector
+        if let Some((index, count)) = user_state
+            .entity_info
+            .as_ref()
+            .and_then(|entity_info| entity_info.get(&node_id))
+        {
+            ui.label(
+                egui::RichText::new(format!("[{}] {} components", index, count))
+                    .small()
+                    .weak(),
+            );
+        }
+
+        if let (Some(BehaviorState::Success | BehaviorState::Failure), Some(outcome)) =
+            (self.state, &self.outcome)
+        {
+            ui.label(
+                egui::RichText::new(format!("{} ({})", outcome.reason, outcome.code))
+                    .small()
+                    .weak(),
+            );
+        }
+
+        if let (Some(_), Some(cost)) = (&user_state.heat, self.cost) {
+            ui.label(
+                egui::RichText::new(format!("{:.3} ms", cost * 1000.0))
+                    .small()
+                    .weak(),
+            );
+        }
+
+        if self.duration > 0.0 {
+            ui.label(
+                egui::RichText::new(format!("⏱ {:.2} s", self.duration))
+                    .small()
+                    .weak(),
+            );
+        }
+
         match &self.data {
             BehaviorData::Root => (),
             BehaviorData::Behavior(behavior) => {
@@ -540,13 +717,74 @@ where
     }
 }
 
-#[derive(Default, Component, Deref, DerefMut)]
+type BehaviorGraphEditorState<T> = GraphEditorState<
+    BehaviorNodeData<T>,
+    BehaviorDataType,
+    BehaviorValueType<T>,
+    BehaviorNodeTemplate<T>,
+    BehaviorGraphState,
+>;
+
+/// Graph editor state, with the undo history of its edits
+#[derive(Default, Component)]
 pub struct BehaviorEditorState<T: BehaviorFactory>(
-    pub  GraphEditorState<
-        BehaviorNodeData<T>,
-        BehaviorDataType,
-        BehaviorValueType<T>,
-        BehaviorNodeTemplate<T>,
-        BehaviorGraphState,
-    >,
+    pub BehaviorGraphEditorState<T>,
+    pub BehaviorUndo<T>,
 );
+
+impl<T: BehaviorFactory> std::ops::Deref for BehaviorEditorState<T> {
+    type Target = BehaviorGraphEditorState<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: BehaviorFactory> std::ops::DerefMut for BehaviorEditorState<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestBehavior;
+
+    fn search(query: &str) -> Vec<String> {
+        search_templates(query, &TestBehavior::list())
+            .iter()
+            .map(|kind| kind.label().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn search_ranks_prefix_first() {
+        assert_eq!(search("").len(), TestBehavior::list().len());
+
+        // case insensitive, prefix matches before matches further in the label
+        let found = search("SEL");
+        assert_eq!(found[0], "Selector");
+        let random = found.iter().position(|label| label == "Random Selector");
+        assert!(random.unwrap() > 0);
+
+        // fuzzy matches come last
+        let found = search("cldw");
+        assert_eq!(found, vec!["Cooldown", "CooldownReady"]);
+        assert!(search("zzz").is_empty());
+    }
+
+    #[test]
+    fn search_matches_categories() {
+        let found = search("decorator");
+        assert!(!found.is_empty());
+        for label in &found {
+            let kind = TestBehavior::list()
+                .into_iter()
+                .find(|kind| kind.label() == label)
+                .unwrap();
+            assert_eq!(kind.typ(), BehaviorType::Decorator);
+        }
+        assert!(found.iter().any(|label| label == "Inverter"));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{inspector::graph::BehaviorEditorState, BehaviorFactory};
+use bevy::utils::HashSet;
+use egui_node_graph::NodeId;
+use simula_inspector::egui;
+
+const HEADER_HEIGHT: f32 = 22.0;
+const GRIP_SIZE: f32 = 12.0;
+const MIN_SIZE: egui::Vec2 = egui::vec2(120.0, 60.0);
+
+/// Labeled box drawn behind a region of nodes. Groups only exist in the editor,
+/// they are not part of the behavior.
+#[derive(Clone, Debug)]
+pub struct BehaviorGroup {
+    pub label: String,
+    /// Area covered, in graph coordinates
+    pub rect: egui::Rect,
+    pub color: egui::Color32,
+    pub collapsed: bool,
+}
+
+impl BehaviorGroup {
+    pub fn new(label: impl Into<String>, rect: egui::Rect) -> Self {
+        Self {
+            label: label.into(),
+            rect,
+            color: egui::Color32::from_rgb(70, 90, 120),
+            collapsed: false,
+        }
+    }
+
+    /// Group around node positions, padded to fit the nodes
+    pub fn around(
+        label: impl Into<String>,
+        positions: impl IntoIterator<Item = egui::Pos2>,
+    ) -> Option<Self> {
+        let rect = positions
+            .into_iter()
+            .map(|pos| egui::Rect::from_min_size(pos, egui::vec2(200.0, 100.0)))
+            .reduce(|rect, other| rect.union(other))?;
+        Some(Self::new(
+            label,
+            egui::Rect::from_min_max(
+                rect.min - egui::vec2(20.0, HEADER_HEIGHT + 20.0),
+                rect.max + egui::vec2(20.0, 20.0),
+            ),
+        ))
+    }
+
+    /// Area drawn, only the header when collapsed
+    pub fn visible_rect(&self) -> egui::Rect {
+        if self.collapsed {
+            egui::Rect::from_min_size(self.rect.min, egui::vec2(self.rect.width(), HEADER_HEIGHT))
+        } else {
+            self.rect
+        }
+    }
+
+    /// Nodes positioned inside the group
+    pub fn nodes<T: BehaviorFactory>(&self, editor: &BehaviorEditorState<T>) -> Vec<NodeId> {
+        editor
+            .node_positions
+            .iter()
+            .filter(|(_, pos)| self.rect.contains(**pos))
+            .map(|(node_id, _)| node_id)
+            .collect()
+    }
+}
+
+/// Nodes inside collapsed groups
+pub fn collapsed_nodes<T: BehaviorFactory>(
+    groups: &[BehaviorGroup],
+    editor: &BehaviorEditorState<T>,
+) -> HashSet<NodeId> {
+    groups
+        .iter()
+        .filter(|group| group.collapsed)
+        .flat_map(|group| group.nodes(editor))
+        .collect()
+}
+
+/// Paint groups, before drawing the graph so they stay behind the nodes
+pub fn paint_groups(ui: &egui::Ui, groups: &[BehaviorGroup], offset: egui::Vec2) {
+    let painter = ui.painter();
+    for group in groups {
+        let rect = group.visible_rect().translate(offset);
+        let header = egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), HEADER_HEIGHT));
+        let [r, g, b, _] = group.color.to_array();
+        painter.rect(
+            rect,
+            4.0,
+            egui::Color32::from_rgba_unmultiplied(r, g, b, 40),
+            egui::Stroke::new(1.0, group.color),
+        );
+        painter.rect_filled(header, 4.0, group.color);
+        painter.text(
+            header.left_center() + egui::vec2(6.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            format!(
+                "{} {}",
+                if group.collapsed { "⏵" } else { "⏷" },
+                group.label
+            ),
+            egui::FontId::proportional(14.0),
+            egui::Color32::WHITE,
+        );
+        if !group.collapsed {
+            let corner = rect.right_bottom();
+            painter.line_segment(
+                [
+                    corner - egui::vec2(GRIP_SIZE, 2.0),
+                    corner - egui::vec2(2.0, GRIP_SIZE),
+                ],
+                egui::Stroke::new(1.0, group.color),
+            );
+        }
+    }
+}
+
+/// Handle group interaction, after drawing the graph so headers take input over the
+/// graph background. Dragging a header moves the group and its nodes, double clicking
+/// collapses it, the corner grip resizes it and the context menu edits it.
+pub fn interact_groups<T: BehaviorFactory>(
+    ui: &mut egui::Ui,
+    groups: &mut Vec<BehaviorGroup>,
+    editor: &mut BehaviorEditorState<T>,
+    offset: egui::Vec2,
+) {
+    let mut remove = None;
+    for (index, group) in groups.iter_mut().enumerate() {
+        let id = ui.id().with(("behavior_group", index));
+        let rect = group.visible_rect().translate(offset);
+        let header = egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), HEADER_HEIGHT));
+
+        let response = ui.interact(header, id, egui::Sense::click_and_drag());
+        if response.dragged() {
+            let delta = response.drag_delta();
+            for node_id in group.nodes(editor) {
+                editor.node_positions[node_id] += delta;
+            }
+            group.rect = group.rect.translate(delta);
+        }
+        if response.double_clicked() {
+            group.collapsed = !group.collapsed;
+        }
+        response.context_menu(|ui| {
+            ui.text_edit_singleline(&mut group.label);
+            ui.horizontal(|ui| {
+                ui.label("Color");
+                ui.color_edit_button_srgba(&mut group.color);
+            });
+            ui.checkbox(&mut group.collapsed, "Collapsed");
+            if ui.button("Delete").clicked() {
+                remove = Some(index);
+                ui.close_menu();
+            }
+        });
+
+        if !group.collapsed {
+            let grip = egui::Rect::from_min_max(
+                rect.right_bottom() - egui::vec2(GRIP_SIZE, GRIP_SIZE),
+                rect.right_bottom(),
+            );
+            let response = ui.interact(grip, id.with("resize"), egui::Sense::drag());
+            if response.dragged() {
+                group.rect.max =
+                    (group.rect.max + response.drag_delta()).max(group.rect.min + MIN_SIZE);
+            }
+        }
+    }
+    if let Some(index) = remove {
+        groups.remove(index);
+    }
+}
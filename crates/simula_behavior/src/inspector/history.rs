@@ -0,0 +1,112 @@
+use crate::{inspector::timeline::TIMELINE_HISTORY, protocol::BehaviorTelemetry, BehaviorFactory};
+use simula_inspector::egui;
+use std::{collections::VecDeque, time::Duration};
+
+/// Most telemetry frames kept, on top of the time bound
+pub const HISTORY_FRAMES: usize = 2000;
+
+/// Telemetry frames of a running behavior, so the graph can step back and show the tree as
+/// it was at a past frame. Frames are kept for as long as the timeline history.
+#[derive(Clone, Debug)]
+pub struct BehaviorHistory<T: BehaviorFactory> {
+    pub frames: VecDeque<(Duration, BehaviorTelemetry<T>)>,
+    /// Time of the frame shown, none follows live
+    pub viewing: Option<Duration>,
+}
+
+impl<T: BehaviorFactory> Default for BehaviorHistory<T> {
+    fn default() -> Self {
+        Self {
+            frames: VecDeque::new(),
+            viewing: None,
+        }
+    }
+}
+
+impl<T: BehaviorFactory> BehaviorHistory<T> {
+    /// Keep a telemetry frame received at a time
+    pub fn record(&mut self, now: Duration, telemetry: BehaviorTelemetry<T>) {
+        self.frames.push_back((now, telemetry));
+        let oldest = now.saturating_sub(TIMELINE_HISTORY);
+        while self.frames.len() > HISTORY_FRAMES
+            || self
+                .frames
+                .front()
+                .map_or(false, |(time, _)| *time < oldest)
+        {
+            self.frames.pop_front();
+        }
+        // the frame being viewed went away, show the oldest one left
+        if let (Some(viewing), Some((oldest, _))) = (self.viewing, self.frames.front()) {
+            if viewing < *oldest {
+                self.viewing = Some(*oldest);
+            }
+        }
+    }
+
+    /// Whether a past frame is shown instead of live telemetry
+    pub fn is_past(&self) -> bool {
+        self.viewing.is_some()
+    }
+
+    /// The frame to show, the last one at or before the viewed time, or the latest when live
+    pub fn frame(&self) -> Option<&(Duration, BehaviorTelemetry<T>)> {
+        match self.viewing {
+            Some(viewing) => self
+                .frames
+                .iter()
+                .rev()
+                .find(|(time, _)| *time <= viewing)
+                .or_else(|| self.frames.front()),
+            None => self.frames.back(),
+        }
+    }
+
+    /// Draw the frame slider, returns true if another frame is to be shown
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            let Some(((first, _), (latest, _))) = self.frames.front().zip(self.frames.back())
+            else {
+                ui.label("No frames recorded");
+                return;
+            };
+            let (first, latest) = (*first, *latest);
+            let last = self.frames.len() - 1;
+            let mut index = match self.viewing {
+                Some(viewing) => self
+                    .frames
+                    .iter()
+                    .rposition(|(time, _)| *time <= viewing)
+                    .unwrap_or(0),
+                None => last,
+            };
+            ui.label("Frame");
+            let slider = egui::Slider::new(&mut index, 0..=last).custom_formatter(|index, _| {
+                let time = self.frames[index as usize].0;
+                format!("-{:.2}s", (latest - time).as_secs_f32())
+            });
+            if ui.add_enabled(first != latest, slider).changed() {
+                self.viewing = (index < last).then(|| self.frames[index].0);
+                changed = true;
+            }
+            if ui
+                .add_enabled(self.is_past(), egui::Button::new("⏩ Live"))
+                .clicked()
+            {
+                self.viewing = None;
+                changed = true;
+            }
+            if self.is_past() {
+                ui.label(
+                    egui::RichText::new(format!("⏪ Past: frame {} of {}", index + 1, last + 1))
+                        .color(egui::Color32::WHITE)
+                        .background_color(egui::Color32::from_rgb(140, 80, 0)),
+                );
+            } else {
+                ui.label("Live");
+            }
+        });
+        changed
+    }
+}
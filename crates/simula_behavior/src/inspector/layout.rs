@@ -0,0 +1,214 @@
+use crate::{
+    inspector::{
+        graph::{BehaviorDataType, BehaviorNodeData, BehaviorValueType},
+        utils,
+    },
+    BehaviorFactory,
+};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use egui_node_graph::{Graph, NodeId};
+use simula_inspector::egui;
+
+/// Spacing of the inspector auto layout, a top-down tree with the root at the origin.
+/// Insert or modify it at startup to space trees differently.
+#[derive(Clone, Debug, Resource)]
+pub struct BehaviorLayout {
+    /// Size reserved for every node
+    pub node_size: egui::Vec2,
+    /// Horizontal space between siblings, and between neighboring subtrees
+    pub sibling_gap: f32,
+    /// Vertical space between a parent and its children
+    pub level_gap: f32,
+}
+
+impl Default for BehaviorLayout {
+    fn default() -> Self {
+        Self {
+            node_size: egui::vec2(160.0, 100.0),
+            sibling_gap: 40.0,
+            level_gap: 60.0,
+        }
+    }
+}
+
+// A laid out subtree, relative to its root at x zero
+struct Subtree {
+    // x and depth of every node
+    nodes: Vec<(NodeId, f32, usize)>,
+    // leftmost and rightmost x at every depth
+    contour: Vec<(f32, f32)>,
+}
+
+impl BehaviorLayout {
+    /// Lay out the tree under a node along flow connections, Reingold-Tilford style: every
+    /// parent is centered over its children, and subtrees are pushed apart just enough for
+    /// their nodes not to overlap at any depth. The node is placed at the origin, nodes
+    /// outside its tree are left out.
+    pub fn layout_tree<T: BehaviorFactory>(
+        &self,
+        graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+        root: NodeId,
+    ) -> HashMap<NodeId, egui::Pos2> {
+        let mut visited = HashSet::new();
+        let subtree = self.layout_subtree(graph, root, &mut visited);
+        let (width, height) = (
+            self.node_size.x + self.sibling_gap,
+            self.node_size.y + self.level_gap,
+        );
+        subtree
+            .nodes
+            .into_iter()
+            .map(|(node_id, x, depth)| (node_id, egui::pos2(x * width, depth as f32 * height)))
+            .collect()
+    }
+
+    // Lay out a subtree in units of node slots, a node visited twice through a cycle is
+    // left out the second time
+    fn layout_subtree<T: BehaviorFactory>(
+        &self,
+        graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+        node_id: NodeId,
+        visited: &mut HashSet<NodeId>,
+    ) -> Subtree {
+        visited.insert(node_id);
+        let mut children: Vec<(f32, Subtree)> = vec![];
+        let mut contour: Vec<(f32, f32)> = vec![];
+        for child_id in utils::get_flow_children(graph, node_id) {
+            if visited.contains(&child_id) {
+                continue;
+            }
+            let child = self.layout_subtree(graph, child_id, visited);
+            // shift right of everything placed so far, at every depth both reach
+            let shift = if children.is_empty() {
+                0.0
+            } else {
+                contour
+                    .iter()
+                    .zip(&child.contour)
+                    .map(|((_, right), (left, _))| right + 1.0 - left)
+                    .fold(f32::MIN, f32::max)
+            };
+            for (depth, (left, right)) in child.contour.iter().enumerate() {
+                let (left, right) = (left + shift, right + shift);
+                match contour.get_mut(depth) {
+                    Some(bounds) => bounds.1 = right,
+                    None => contour.push((left, right)),
+                }
+            }
+            children.push((shift, child));
+        }
+
+        // center the parent over its first and last child
+        let center = match (children.first(), children.last()) {
+            (Some((first, _)), Some((last, _))) => (first + last) / 2.0,
+            _ => 0.0,
+        };
+        let mut subtree = Subtree {
+            nodes: vec![(node_id, 0.0, 0)],
+            contour: vec![(0.0, 0.0)],
+        };
+        for (shift, child) in children {
+            subtree.nodes.extend(
+                child
+                    .nodes
+                    .into_iter()
+                    .map(|(node_id, x, depth)| (node_id, x + shift - center, depth + 1)),
+            );
+        }
+        subtree.contour.extend(
+            contour
+                .into_iter()
+                .map(|(left, right)| (left - center, right - center)),
+        );
+        subtree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inspector::graph::{BehaviorData, BehaviorGraphState},
+        test::TestBehavior,
+        Behavior,
+    };
+
+    fn layout(behavior: &str) -> Vec<(String, egui::Pos2)> {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+        let editor = utils::behavior_graph(&mut BehaviorGraphState::default(), &behavior);
+        let root = editor
+            .graph
+            .nodes
+            .iter()
+            .find(|(_, node)| matches!(node.user_data.data, BehaviorData::Root))
+            .map(|(node_id, _)| node_id)
+            .unwrap();
+        let positions = BehaviorLayout::default().layout_tree(&editor.graph, root);
+        assert_eq!(positions.len(), editor.graph.nodes.len());
+        let mut positions = positions
+            .into_iter()
+            .map(|(node_id, pos)| (editor.graph.nodes[node_id].label.clone(), pos))
+            .collect::<Vec<_>>();
+        positions.sort_by(|a, b| a.0.cmp(&b.0));
+        positions
+    }
+
+    #[test]
+    fn layout_centers_parents() {
+        let positions = layout(
+            r#"
+            (
+                "Sequence",
+                Sequencer(()),
+                [("A", Debug(())), ("B", Debug(())), ("C", Debug(()))],
+            )
+            "#,
+        );
+        let (width, height) = (200.0, 160.0);
+        assert_eq!(
+            positions,
+            vec![
+                ("A".to_owned(), egui::pos2(-width, 2.0 * height)),
+                ("B".to_owned(), egui::pos2(0.0, 2.0 * height)),
+                ("C".to_owned(), egui::pos2(width, 2.0 * height)),
+                ("Root".to_owned(), egui::pos2(0.0, 0.0)),
+                ("Sequence".to_owned(), egui::pos2(0.0, height)),
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_separates_subtrees() {
+        let positions = layout(
+            r#"
+            (
+                "Sequence",
+                Sequencer(()),
+                [
+                    ("Left", Sequencer(()), [("A", Debug(())), ("B", Debug(()))]),
+                    ("Middle", Debug(())),
+                    ("Right", Sequencer(()), [("C", Debug(())), ("D", Debug(()))]),
+                ],
+            )
+            "#,
+        );
+        let get = |name: &str| positions.iter().find(|(n, _)| n == name).unwrap().1;
+        let width = 200.0;
+
+        // leaves of neighboring subtrees are a slot apart, parents centered over them
+        assert_eq!(get("C").x - get("B").x, width);
+        assert_eq!(get("Left").x, (get("A").x + get("B").x) / 2.0);
+        assert_eq!(get("Right").x, (get("C").x + get("D").x) / 2.0);
+        assert_eq!(get("Sequence").x, (get("Left").x + get("Right").x) / 2.0);
+        assert_eq!(get("Root"), egui::pos2(0.0, 0.0));
+        assert_eq!(get("Sequence").x, 0.0);
+
+        // siblings never closer than a slot
+        let mut middle_row = vec![get("Left").x, get("Middle").x, get("Right").x];
+        middle_row.sort_by(f32::total_cmp);
+        assert!(middle_row.windows(2).all(|w| w[1] - w[0] >= width));
+    }
+}
@@ -1,10 +1,14 @@
 use crate::{
-    inspector::{utils, BehaviorInspector, BehaviorInspectorItem, BehaviorInspectorState},
+    asset::validate_inline,
+    inspector::{
+        graph::BehaviorEditorState, utils, workspace, BehaviorInspector, BehaviorInspectorItem,
+        BehaviorInspectorState, BehaviorNodeInspectable, BehaviorWorkspaceDir,
+    },
     protocol::{
         BehaviorClient, BehaviorFileId, BehaviorFileName, BehaviorProtocolClient, StartOption,
         StopOption,
     },
-    BehaviorFactory,
+    Behavior, BehaviorFactory,
 };
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -13,7 +17,9 @@ use simula_inspector::egui;
 pub fn ui<T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>>(
     ui: &mut egui::Ui,
     world: &mut World,
-) {
+) where
+    <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
+{
     ui.push_id(T::TYPE_UUID, |ui| {
         let elapsed = world.get_resource::<Time>().unwrap().elapsed();
 
@@ -39,6 +45,21 @@ pub fn ui<T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>>(
                         start_option: StartOption::Spawn,
                         stop_option: StopOption::Despawn,
                         modified: true,
+                        restart: false,
+                        isolate: false,
+                        minimap: false,
+                        debug: false,
+                        profile: false,
+                        timing: false,
+                        read_only: false,
+                        timeline: false,
+                        history: Default::default(),
+                        errors: vec![],
+                        agent: None,
+                        subscribed: false,
+                        saved: None,
+                        auto_saved: elapsed,
+                        pan: None,
                     },
                 );
                 behavior_inspector.selected = Some(file_id.clone());
@@ -59,6 +80,120 @@ pub fn ui<T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>>(
                     }
                 }
             }
+
+            // copy selected behavior as RON to the clipboard
+            let behavior_inspector = world.resource::<BehaviorInspector<T>>();
+            let selected_item = behavior_inspector
+                .selected
+                .as_ref()
+                .and_then(|file_id| behavior_inspector.behaviors.get(file_id))
+                .and_then(|item| Some((item.entity?, item.behavior.clone())));
+            if let Some((entity, previous)) = selected_item {
+                if ui.add(egui::Button::new("📋 Copy RON")).clicked() {
+                    let mut editor_states = world.query::<&BehaviorEditorState<T>>();
+                    let ron = editor_states
+                        .get(world, entity)
+                        .map_err(|err| err.to_string())
+                        .and_then(|editor_state| utils::graph_to_behavior(editor_state, None))
+                        .and_then(|behavior| {
+                            let behavior = utils::keep_inline(behavior, &previous);
+                            ron::ser::to_string_pretty(&behavior, ron::ser::PrettyConfig::default())
+                                .map_err(|err| err.to_string())
+                        });
+                    match ron {
+                        Ok(ron) => ui.output_mut(|output| output.copied_text = ron),
+                        Err(err) => error!("Failed to copy behavior: {}", err),
+                    }
+                    ui.close_menu();
+                }
+            }
+
+            // save or restore open behaviors and how they are shown
+            ui.separator();
+            if ui.add(egui::Button::new("🗔 Save Workspace")).clicked() {
+                let workspace_dir = world.resource::<BehaviorWorkspaceDir>().clone();
+                let workspace = workspace::capture::<T>(world);
+                if let Err(err) = workspace::save::<T>(&workspace_dir, &workspace) {
+                    error!("Cannot save workspace: {}", err);
+                }
+                ui.close_menu();
+            }
+            if ui.add(egui::Button::new("🗔 Load Workspace")).clicked() {
+                let workspace_dir = world.resource::<BehaviorWorkspaceDir>().clone();
+                match workspace::load::<T>(&workspace_dir) {
+                    Ok(Some(workspace)) => {
+                        let mut behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
+                        workspace::restore(&mut behavior_inspector, workspace, elapsed);
+                    }
+                    Ok(None) => warn!("No workspace saved"),
+                    Err(err) => error!("Cannot load workspace: {}", err),
+                }
+                ui.close_menu();
+            }
+
+            // create a new behavior from pasted RON
+            ui.menu_button("📥 Paste RON", |ui| {
+                let mut behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
+                ui.add(
+                    egui::TextEdit::multiline(&mut behavior_inspector.paste)
+                        .hint_text("paste behavior RON here")
+                        .code_editor()
+                        .desired_rows(8),
+                );
+                if let Some(paste_error) = &behavior_inspector.paste_error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, paste_error);
+                }
+                if ui.add(egui::Button::new("✚ Create")).clicked() {
+                    let behavior = ron::de::from_str::<Behavior<T>>(&behavior_inspector.paste)
+                        .map_err(|err| err.to_string())
+                        .and_then(|behavior| validate_inline(&behavior).map(|_| behavior));
+                    match behavior {
+                        Ok(behavior) => {
+                            let file_id = BehaviorFileId::new();
+                            let file_name =
+                                BehaviorFileName(format!("bht/u/bt_{}", *file_id).into());
+                            behavior_inspector.behaviors.insert(
+                                file_id.clone(),
+                                BehaviorInspectorItem {
+                                    entity: None,
+                                    name: file_name,
+                                    state: BehaviorInspectorState::New,
+                                    collapsed: false,
+                                    behavior: Some(behavior),
+                                    instances: vec![],
+                                    orphans: vec![],
+                                    start_option: StartOption::Spawn,
+                                    stop_option: StopOption::Despawn,
+                                    modified: true,
+                                    restart: false,
+                                    isolate: false,
+                                    minimap: false,
+                                    debug: false,
+                                    profile: false,
+                                    timing: false,
+                                    read_only: false,
+                                    timeline: false,
+                                    history: Default::default(),
+                                    errors: vec![],
+                                    agent: None,
+                                    subscribed: false,
+                                    saved: None,
+                                    auto_saved: elapsed,
+                                    pan: None,
+                                },
+                            );
+                            behavior_inspector.selected = Some(file_id);
+                            behavior_inspector.paste.clear();
+                            behavior_inspector.paste_error = None;
+                            refresh_orphans = true;
+                            ui.close_menu();
+                        }
+                        Err(err) => {
+                            behavior_inspector.paste_error = Some(err);
+                        }
+                    }
+                }
+            });
         });
 
         let behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
@@ -1,7 +1,12 @@
 use crate::{
-    inspector::graph::{
-        BehaviorData, BehaviorEditorState, BehaviorGraphState, BehaviorNodeData,
-        BehaviorNodeTemplate,
+    inspector::{
+        clipboard::BehaviorClipboard,
+        graph::{
+            BehaviorData, BehaviorEditorState, BehaviorGraphState, BehaviorNodeData,
+            BehaviorNodeTemplate,
+        },
+        history::BehaviorHistory,
+        utils::GraphValidationError,
     },
     protocol::{
         BehaviorClient, BehaviorFileId, BehaviorFileName, BehaviorProtocolClient,
@@ -12,17 +17,31 @@ use crate::{
 pub use behavior::BehaviorUI;
 use bevy::{prelude::*, utils::HashMap};
 use crossbeam_channel::unbounded;
+pub use defaults::BehaviorDefaults;
 use egui_node_graph::NodeTemplateTrait;
+pub use layout::BehaviorLayout;
+pub use property::reflect_ui;
 use serde::{Deserialize, Serialize};
 use simula_inspector::{egui, Inspector, Inspectors};
 use std::time::Duration;
+pub use style::{BehaviorStyle, BehaviorStyles};
+pub use workspace::{BehaviorWorkspace, BehaviorWorkspaceDir, BehaviorWorkspaceItem};
 
 mod behavior;
+pub mod clipboard;
+mod defaults;
 pub mod graph;
+pub mod group;
+pub mod history;
+mod layout;
 mod menu;
 mod property;
+mod style;
+pub mod timeline;
+pub mod undo;
 mod utils;
 mod window;
+mod workspace;
 
 #[derive(Default)]
 pub struct BehaviorInspectorPlugin<T: BehaviorFactory>(pub std::marker::PhantomData<T>);
@@ -50,6 +69,11 @@ where
         app.insert_resource(client)
             .insert_resource(server)
             .insert_resource(BehaviorInspector::<T>::default())
+            .init_resource::<BehaviorAutoSave>()
+            .init_resource::<BehaviorWorkspaceDir>()
+            .init_resource::<BehaviorStyles>()
+            .init_resource::<BehaviorLayout>()
+            .init_resource::<BehaviorDefaults<T>>()
             .add_startup_system(setup::<T>)
             .add_system(update::<T>);
     }
@@ -61,10 +85,6 @@ pub trait BehaviorNodeInspectable<T: BehaviorFactory> {
 }
 
 pub trait BehaviorInspectable {
-    fn color(&self) -> Color {
-        panic!("BehaviorInspectable::color() not implemented")
-    }
-
     fn categories(&self) -> Vec<&'static str> {
         panic!("BehaviorInspectable::categories() not implemented")
     }
@@ -98,28 +118,98 @@ pub(self) struct BehaviorInspectorItem<T: BehaviorFactory> {
     pub start_option: StartOption,
     pub stop_option: StopOption,
     pub modified: bool,
+    pub restart: bool,
+    pub isolate: bool,
+    pub minimap: bool,
+    pub debug: bool,
+    pub profile: bool,
+    /// Show how long nodes have been running
+    pub timing: bool,
+    /// Presentation mode, the graph can be viewed and run but not edited
+    pub read_only: bool,
+    /// Show a timeline of recent node states
+    pub timeline: bool,
+    /// Recent telemetry, to show the tree at a past frame
+    pub history: BehaviorHistory<T>,
+    /// Why the graph could not be started, shown until it starts
+    pub errors: Vec<GraphValidationError>,
+    pub agent: Option<RemoteEntity>,
+    pub subscribed: bool,
+    /// When the behavior was last confirmed saved
+    pub saved: Option<Duration>,
+    /// When the last save, manual or automatic, was requested
+    pub auto_saved: Duration,
+    /// Pan restored from a workspace, applied once the behavior is loaded
+    pub pan: Option<egui::Vec2>,
 }
 
 #[derive(Default, Clone, Resource)]
 pub(self) struct BehaviorInspector<T: BehaviorFactory> {
     pub selected: Option<BehaviorFileId>,
     pub behaviors: HashMap<BehaviorFileId, BehaviorInspectorItem<T>>,
+    /// RON text being pasted to create a new behavior
+    pub paste: String,
+    pub paste_error: Option<String>,
+    /// Whether node profiling was last requested from the server
+    pub profiling: bool,
+    /// Whether node running time was last requested from the server
+    pub timing: bool,
+    /// Last behavior window position and size
+    pub window_rect: Option<egui::Rect>,
+    /// Behavior window position and size restored from a workspace, applied once
+    pub restore_window: Option<egui::Rect>,
+    /// Workspace being restored, and when it started
+    pub workspace: Option<(BehaviorWorkspace, Duration)>,
+    /// Nodes last copied in a graph, to paste in any behavior
+    pub clipboard: Option<BehaviorClipboard<T>>,
 }
 
-fn setup<T>(mut inspectors: ResMut<Inspectors>)
-where
+/// Auto-save of edited behaviors in the inspector. Behaviors with unsaved changes are saved
+/// to their file every `interval` while editing, never while running.
+#[derive(Clone, Resource)]
+pub struct BehaviorAutoSave {
+    pub enabled: bool,
+    pub interval: Duration,
+}
+
+impl Default for BehaviorAutoSave {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(30),
+        }
+    }
+}
+
+fn setup<T>(
+    mut inspectors: ResMut<Inspectors>,
+    mut behavior_inspector: ResMut<BehaviorInspector<T>>,
+    workspace_dir: Res<BehaviorWorkspaceDir>,
+) where
     T: BehaviorFactory + BehaviorInspectable + Serialize + for<'de> Deserialize<'de>,
+    <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
 {
     inspectors.inspectors.push(Inspector {
         menu_ui: menu::ui::<T>,
         window_ui: window::ui::<T>,
     });
+
+    // reopen the last editing session
+    match workspace::load::<T>(&workspace_dir) {
+        Ok(Some(workspace)) => {
+            workspace::restore(&mut behavior_inspector, workspace, Duration::ZERO)
+        }
+        Ok(None) => {}
+        Err(err) => error!("Cannot load workspace: {}", err),
+    }
 }
 
 fn update<T>(
     mut commands: Commands,
     time: Res<Time>,
     type_registry: Res<AppTypeRegistry>,
+    styles: Res<BehaviorStyles>,
+    auto_save: Res<BehaviorAutoSave>,
     mut behavior_inspector: ResMut<BehaviorInspector<T>>,
     behavior_client: Res<BehaviorClient<T>>,
     mut graph_states: Query<&mut BehaviorGraphState>,
@@ -131,9 +221,12 @@ fn update<T>(
     // Get now
     let elapsed = time.elapsed();
 
-    // provide time for all graph states
+    // provide time and node styles for all graph states
     for mut graph_state in graph_states.iter_mut() {
         graph_state.time = time.clone();
+        if styles.is_changed() || graph_state.is_added() {
+            graph_state.styles = styles.clone();
+        }
     }
 
     let mut selected = behavior_inspector.selected.clone();
@@ -142,8 +235,16 @@ fn update<T>(
         match &behavior_inspector_item.state {
             // behavior is only listed, no need to do anything
             BehaviorInspectorState::Listing => {}
-            // behavior is editing, no need to do anything
-            BehaviorInspectorState::Editing => {}
+            // behavior is editing, auto-save it if it has unsaved changes
+            BehaviorInspectorState::Editing => {
+                if auto_save.enabled
+                    && behavior_inspector_item.modified
+                    && elapsed - behavior_inspector_item.auto_saved >= auto_save.interval
+                {
+                    info!("Auto-saving behavior: {}", *behavior_inspector_item.name);
+                    behavior_inspector_item.state = BehaviorInspectorState::Save;
+                }
+            }
             // If behavior item is Load, load it
             BehaviorInspectorState::Load => {
                 info!("Loading behavior: {}", *behavior_inspector_item.name);
@@ -176,25 +277,38 @@ fn update<T>(
                     type_registry: type_registry.0.clone(),
                     ..Default::default()
                 };
-                let mut editor_state = BehaviorEditorState::<T>::default();
 
-                // create the only root node allowed
-                let root_node_data = BehaviorNodeData {
-                    data: BehaviorData::Root,
-                    state: None,
-                    entity: None,
-                };
-                let root_node =
+                // pasted behaviors start with their nodes, otherwise only a root node
+                let editor_state = if let Some(behavior) = &behavior_inspector_item.behavior {
+                    let mut editor_state = utils::behavior_graph::<T>(&mut graph_state, behavior);
+                    utils::layout_graph_if_tight(&mut editor_state);
                     editor_state
-                        .graph
-                        .add_node("Root".into(), root_node_data, |graph, node_id| {
+                } else {
+                    let mut editor_state = BehaviorEditorState::<T>::default();
+
+                    // create the only root node allowed
+                    let root_node_data = BehaviorNodeData {
+                        data: BehaviorData::Root,
+                        state: None,
+                        entity: None,
+                        cost: None,
+                        duration: 0.0,
+                        outcome: None,
+                    };
+                    let root_node = editor_state.graph.add_node(
+                        "Root".into(),
+                        root_node_data,
+                        |graph, node_id| {
                             BehaviorNodeTemplate::Root.build_node(graph, &mut graph_state, node_id)
-                        });
+                        },
+                    );
 
-                editor_state
-                    .node_positions
-                    .insert(root_node, egui::Pos2::new(0.0, 0.0));
-                editor_state.node_order.push(root_node);
+                    editor_state
+                        .node_positions
+                        .insert(root_node, egui::Pos2::new(0.0, 0.0));
+                    editor_state.node_order.push(root_node);
+                    editor_state
+                };
 
                 let entity = commands
                     .spawn(Name::new(format!("BHI: {}", *behavior_inspector_item.name)))
@@ -209,14 +323,20 @@ fn update<T>(
                 info!("Saving behavior: {}", *behavior_inspector_item.name);
                 // set Editing in case anything goes wrong
                 behavior_inspector_item.state = BehaviorInspectorState::Editing;
+                behavior_inspector_item.auto_saved = elapsed;
                 // if we have an entity, we can save
                 if let Some(entity) = behavior_inspector_item.entity {
                     if let Ok(editor_state) = editor_states.get(entity) {
-                        let behavior = utils::graph_to_behavior(&editor_state, None);
+                        let behavior =
+                            utils::graph_to_behavior(&editor_state, None).map(|behavior| {
+                                utils::keep_inline(behavior, &behavior_inspector_item.behavior)
+                            });
                         if let Ok(behavior) = behavior {
                             debug!("behavior: {:#?}", behavior);
                             behavior_inspector_item.behavior = Some(behavior.clone());
                             behavior_inspector_item.state = BehaviorInspectorState::Saving(elapsed);
+                            // edits made while saving mark the behavior modified again
+                            behavior_inspector_item.modified = false;
                             behavior_client
                                 .sender
                                 .send(BehaviorProtocolClient::SaveFile(
@@ -249,6 +369,7 @@ fn update<T>(
                         *behavior_inspector_item.name
                     );
                     behavior_inspector_item.state = BehaviorInspectorState::Editing;
+                    behavior_inspector_item.modified = true;
                 }
             }
             // if behavior item should Start, start it
@@ -260,7 +381,12 @@ fn update<T>(
                 if let Some(entity) = behavior_inspector_item.entity {
                     if let Ok(editor_state) = editor_states.get(entity) {
                         let mut behavior_option = None;
-                        let mut error = false;
+                        // stay editing, with the errors shown, if the graph cannot run
+                        behavior_inspector_item.errors = utils::validate_graph(&editor_state.graph);
+                        let mut error = !behavior_inspector_item.errors.is_empty();
+                        for e in &behavior_inspector_item.errors {
+                            error!("{} for behavior: {}", e, *behavior_inspector_item.name);
+                        }
                         // only send a copy of behavior if it has been modified,
                         // is StartOption::Spawn or StartOption::Insert
                         let send_behavior = match behavior_inspector_item.start_option {
@@ -268,8 +394,11 @@ fn update<T>(
                             StartOption::Attach(_) => false,
                             StartOption::Insert(_) => false,
                         };
-                        if behavior_inspector_item.modified || send_behavior {
-                            let behavior = utils::graph_to_behavior(&editor_state, None);
+                        if !error && (behavior_inspector_item.modified || send_behavior) {
+                            let behavior =
+                                utils::graph_to_behavior(&editor_state, None).map(|behavior| {
+                                    utils::keep_inline(behavior, &behavior_inspector_item.behavior)
+                                });
                             if let Ok(behavior) = behavior {
                                 behavior_option = Some(behavior.clone());
                                 debug!("behavior: {:#?}", behavior);
@@ -337,6 +466,7 @@ fn update<T>(
                         *behavior_inspector_item.name
                     );
                     behavior_inspector_item.state = BehaviorInspectorState::Editing;
+                    behavior_inspector_item.restart = false;
                 }
             }
         }
@@ -344,6 +474,56 @@ fn update<T>(
 
     behavior_inspector.selected = selected;
 
+    workspace::update(&mut behavior_inspector, elapsed);
+
+    // only stream telemetry while a running behavior is shown in the window
+    let selected = behavior_inspector.selected.clone();
+    for (file_id, behavior_inspector_item) in behavior_inspector.behaviors.iter_mut() {
+        let subscribe = selected.as_ref() == Some(file_id)
+            && !behavior_inspector_item.collapsed
+            && matches!(
+                behavior_inspector_item.state,
+                BehaviorInspectorState::Running
+                    | BehaviorInspectorState::Stop
+                    | BehaviorInspectorState::Stopping(_)
+            );
+        if subscribe != behavior_inspector_item.subscribed {
+            behavior_inspector_item.subscribed = subscribe;
+            let msg = if subscribe {
+                BehaviorProtocolClient::Subscribe(file_id.clone())
+            } else {
+                BehaviorProtocolClient::Unsubscribe(file_id.clone())
+            };
+            behavior_client.sender.send(msg).unwrap();
+        }
+    }
+
+    // profile nodes while any streamed behavior asks for it
+    let profiling = behavior_inspector
+        .behaviors
+        .values()
+        .any(|item| item.subscribed && item.profile);
+    if profiling != behavior_inspector.profiling {
+        behavior_inspector.profiling = profiling;
+        behavior_client
+            .sender
+            .send(BehaviorProtocolClient::Profile(profiling))
+            .unwrap();
+    }
+
+    // time running nodes while any streamed behavior asks for it
+    let timing = behavior_inspector
+        .behaviors
+        .values()
+        .any(|item| item.subscribed && item.timing);
+    if timing != behavior_inspector.timing {
+        behavior_inspector.timing = timing;
+        behavior_client
+            .sender
+            .send(BehaviorProtocolClient::Timing(timing))
+            .unwrap();
+    }
+
     while let Ok(server_msg) = behavior_client.receiver.try_recv() {
         match server_msg {
             // Receive behavior file name
@@ -363,6 +543,21 @@ fn update<T>(
                             start_option: StartOption::Spawn,
                             stop_option: StopOption::Despawn,
                             modified: false,
+                            restart: false,
+                            isolate: false,
+                            minimap: false,
+                            debug: false,
+                            profile: false,
+                            timing: false,
+                            read_only: false,
+                            timeline: false,
+                            history: Default::default(),
+                            errors: vec![],
+                            agent: None,
+                            subscribed: false,
+                            saved: None,
+                            auto_saved: Duration::ZERO,
+                            pan: None,
                         },
                     );
                 }
@@ -402,56 +597,17 @@ fn update<T>(
 
                         behavior_inspector_item.behavior = Some(behavior.clone());
                         behavior_inspector_item.modified = false;
+                        behavior_inspector_item.auto_saved = elapsed;
 
                         let mut graph_state = BehaviorGraphState {
                             type_registry: type_registry.0.clone(),
                             ..Default::default()
                         };
-                        let mut editor_state = BehaviorEditorState::<T>::default();
-
-                        // create the only root node allowed
-                        let root_node_data = BehaviorNodeData {
-                            data: BehaviorData::Root,
-                            state: None,
-                            entity: None,
-                        };
-                        let root_node = editor_state.graph.add_node(
-                            "Root".into(),
-                            root_node_data,
-                            |graph, node_id| {
-                                BehaviorNodeTemplate::Root.build_node(
-                                    graph,
-                                    &mut graph_state,
-                                    node_id,
-                                )
-                            },
-                        );
-
-                        editor_state
-                            .node_positions
-                            .insert(root_node, egui::Pos2::new(0.0, 0.0));
-                        editor_state.node_order.push(root_node);
-
-                        utils::behavior_into_graph(
-                            &mut editor_state,
-                            &mut graph_state,
-                            root_node,
-                            &behavior,
-                        );
-
-                        // calculate min and max positions
-                        let mut min_pos = egui::pos2(f32::MAX, f32::MAX);
-                        let mut max_pos = egui::pos2(f32::MIN, f32::MIN);
-                        editor_state.node_positions.iter().for_each(|(_, pos)| {
-                            min_pos = egui::pos2(pos.x.min(min_pos.x), pos.y.min(min_pos.y));
-                            max_pos = egui::pos2(pos.x.max(max_pos.x), pos.y.max(max_pos.y));
-                        });
-
-                        // auto layout graph if it's too tight
-                        let size_pos = (max_pos - min_pos).length_sq();
-                        if size_pos < 1000.0 {
-                            let mut child = 0;
-                            utils::layout_graph(&mut editor_state, None, 0, &mut child);
+                        let mut editor_state =
+                            utils::behavior_graph::<T>(&mut graph_state, &behavior);
+                        utils::layout_graph_if_tight(&mut editor_state);
+                        if let Some(pan) = behavior_inspector_item.pan.take() {
+                            editor_state.pan_zoom.pan = pan;
                         }
 
                         // spawn an entity for this behavior graph
@@ -473,19 +629,28 @@ fn update<T>(
                 {
                     if let BehaviorInspectorState::Saving(_) = behavior_inspector_item.state {
                         behavior_inspector_item.state = BehaviorInspectorState::Editing;
+                        behavior_inspector_item.saved = Some(elapsed);
                     }
                 } else {
                     error!("Unexpected file saved: {:?}", file_id);
                 }
             }
             // Behavior started
-            BehaviorProtocolServer::Started(file_id) => {
-                info!("Received Started: {:?}", file_id);
+            BehaviorProtocolServer::Started(file_id, agent) => {
+                info!("Received Started: {:?} {:?}", file_id, agent);
                 if let Some(behavior_inspector_item) =
                     behavior_inspector.behaviors.get_mut(&file_id)
                 {
                     if let BehaviorInspectorState::Starting(_) = &behavior_inspector_item.state {
                         behavior_inspector_item.state = BehaviorInspectorState::Running;
+                        behavior_inspector_item.agent = Some(agent);
+                        // start a fresh timeline and history for this run
+                        behavior_inspector_item.history = Default::default();
+                        if let Some(entity) = behavior_inspector_item.entity {
+                            if let Ok(mut graph_state) = graph_states.get_mut(entity) {
+                                graph_state.timeline = Default::default();
+                            }
+                        }
                     }
                 } else {
                     error!("Unexpected behavior started: {:?}", file_id);
@@ -500,6 +665,11 @@ fn update<T>(
                     if let Some(behavior) = behavior_inspector_item.behavior.clone() {
                         if let Some(entity) = behavior_inspector_item.entity {
                             if let Ok(mut editor_state) = editor_states.get_mut(entity) {
+                                // subtree nodes shown while running go away
+                                if let Ok(mut graph_state) = graph_states.get_mut(entity) {
+                                    utils::collapse_subtrees(&mut editor_state, &mut graph_state);
+                                    graph_state.timeline.marker = None;
+                                }
                                 if let Err(e) =
                                     utils::behavior_to_graph(&mut editor_state, None, &behavior)
                                 {
@@ -508,7 +678,18 @@ fn update<T>(
                             }
                         }
                     }
+                    behavior_inspector_item.agent = None;
+                    behavior_inspector_item.history.viewing = None;
                     if let BehaviorInspectorState::Stopping(_) = behavior_inspector_item.state {
+                        // if a re-run was requested, start again right away
+                        if behavior_inspector_item.restart {
+                            behavior_inspector_item.restart = false;
+                            behavior_inspector_item.state = BehaviorInspectorState::Start;
+                        } else {
+                            behavior_inspector_item.state = BehaviorInspectorState::Editing;
+                        }
+                    } else if let BehaviorInspectorState::Running = behavior_inspector_item.state {
+                        // agent went away while running
                         behavior_inspector_item.state = BehaviorInspectorState::Editing;
                     }
                 } else {
@@ -516,20 +697,42 @@ fn update<T>(
                 }
             }
             // Behavior telemetry
-            BehaviorProtocolServer::Telemetry(file_id, telemetry) => {
-                trace!("Received Telemetry: {:#?}", telemetry);
+            BehaviorProtocolServer::Telemetry(file_id, agent, telemetry) => {
+                trace!("Received Telemetry: {:?} {:#?}", agent, telemetry);
                 if let Some(behavior_inspector_item) =
                     behavior_inspector.behaviors.get_mut(&file_id)
                 {
-                    if let BehaviorInspectorState::Running = behavior_inspector_item.state {
+                    // only show telemetry from the agent this item is running on
+                    let from_agent = behavior_inspector_item
+                        .agent
+                        .as_ref()
+                        .map_or(false, |item_agent| item_agent.bits == agent.bits);
+                    if !from_agent {
+                        trace!("Ignored telemetry from agent: {:?}", agent);
+                    } else if let BehaviorInspectorState::Running = behavior_inspector_item.state {
                         if let Some(entity) = behavior_inspector_item.entity {
-                            if let Ok(mut editor_state) = editor_states.get_mut(entity) {
-                                if let Err(e) = utils::behavior_telemerty_to_graph(
-                                    &mut editor_state.graph,
-                                    None,
+                            if let (Ok(mut editor_state), Ok(mut graph_state)) =
+                                (editor_states.get_mut(entity), graph_states.get_mut(entity))
+                            {
+                                utils::show_telemetry(
+                                    &mut editor_state,
+                                    &mut graph_state,
                                     &telemetry,
-                                ) {
-                                    error!("Failed to apply telemetry: {}", e);
+                                );
+                                graph_state.timeline.record(&editor_state.graph, elapsed);
+
+                                // stepped back, keep showing the past frame
+                                let history = &mut behavior_inspector_item.history;
+                                history.record(elapsed, telemetry);
+                                if let (true, Some((time, past))) =
+                                    (history.is_past(), history.frame())
+                                {
+                                    utils::show_telemetry(
+                                        &mut editor_state,
+                                        &mut graph_state,
+                                        past,
+                                    );
+                                    graph_state.timeline.marker = Some(*time);
                                 }
                             }
                         }
@@ -1,5 +1,11 @@
 use crate::prelude::*;
-use bevy::{prelude::*, reflect::TypeRegistry};
+use bevy::{
+    prelude::*,
+    reflect::{
+        DynamicEnum, DynamicStruct, DynamicTuple, DynamicVariant, EnumInfo, ReflectMut, TypeInfo,
+        TypeRegistry, VariantInfo,
+    },
+};
 use bevy_inspector_egui::{egui, reflect_inspector};
 use simula_core::epath::EPath;
 use std::str::FromStr;
@@ -33,6 +39,114 @@ const PROP_FRAME_OUTER_MARGIN: egui::Margin = egui::Margin {
 const PROP_VALUE_ICON: &str = "=";
 const PROP_EVAL_ICON: &str = "λ";
 
+/// Build an enum value with the given variant, its fields set to their registered defaults
+pub fn enum_variant(
+    info: &EnumInfo,
+    variant: &str,
+    type_registry: &TypeRegistry,
+) -> Result<DynamicEnum, String> {
+    let type_registry = type_registry.read();
+    let default = |type_id, type_name: &str| {
+        type_registry
+            .get(type_id)
+            .and_then(|registration| registration.data::<ReflectDefault>())
+            .map(|default| default.default())
+            .ok_or_else(|| format!("{} has no registered default", type_name))
+    };
+    let dynamic = match info.variant(variant) {
+        None => return Err(format!("{} has no variant {}", info.type_name(), variant)),
+        Some(VariantInfo::Unit(_)) => DynamicVariant::Unit,
+        Some(VariantInfo::Tuple(tuple)) => {
+            let mut fields = DynamicTuple::default();
+            for field in tuple.iter() {
+                fields.insert_boxed(default(field.type_id(), field.type_name())?);
+            }
+            DynamicVariant::Tuple(fields)
+        }
+        Some(VariantInfo::Struct(named)) => {
+            let mut fields = DynamicStruct::default();
+            for field in named.iter() {
+                fields.insert_boxed(field.name(), default(field.type_id(), field.type_name())?);
+            }
+            DynamicVariant::Struct(fields)
+        }
+    };
+    Ok(DynamicEnum::new(info.type_name(), variant, dynamic))
+}
+
+/// Reflection editor for a value. Enums get a variant dropdown followed by the fields of the
+/// current variant, options get a checkbox followed by the inner value. Anything else is left
+/// to the reflect inspector.
+pub fn reflect_ui(
+    value: &mut dyn Reflect,
+    ui: &mut egui::Ui,
+    type_registry: &TypeRegistry,
+) -> bool {
+    let TypeInfo::Enum(info) = value.get_type_info() else {
+        return reflect_inspector::ui_for_value(value, ui, &type_registry.read());
+    };
+    let ReflectMut::Enum(value) = value.reflect_mut() else {
+        return false;
+    };
+
+    let mut changed = false;
+    let current = value.variant_name().to_string();
+    let mut switch = None;
+    ui.horizontal(|ui| {
+        if info.type_name().starts_with("core::option::Option<") {
+            let mut some = current == "Some";
+            if ui.checkbox(&mut some, "").changed() {
+                let variant = if some { "Some" } else { "None" };
+                match enum_variant(info, variant, type_registry) {
+                    Ok(dynamic) => switch = Some(dynamic),
+                    Err(err) => error!("Cannot set option: {}", err),
+                }
+            }
+        } else {
+            egui::ComboBox::from_id_source(ui.next_auto_id())
+                .selected_text(&current)
+                .show_ui(ui, |ui| {
+                    for variant in info.variant_names() {
+                        let selected = *variant == current;
+                        match enum_variant(info, variant, type_registry) {
+                            Ok(dynamic) => {
+                                if ui.selectable_label(selected, *variant).clicked() && !selected {
+                                    switch = Some(dynamic);
+                                }
+                            }
+                            Err(err) => {
+                                ui.add_enabled(
+                                    false,
+                                    egui::SelectableLabel::new(selected, *variant),
+                                )
+                                .on_disabled_hover_text(err);
+                            }
+                        }
+                    }
+                });
+        }
+    });
+    if let Some(dynamic) = switch {
+        value.apply(&dynamic);
+        changed = true;
+    }
+
+    for index in 0..value.field_len() {
+        let name = value.name_at(index).map(str::to_string);
+        let Some(field) = value.field_at_mut(index) else {
+            continue;
+        };
+        ui.horizontal(|ui| {
+            ui.add_space(10.0);
+            if let Some(name) = name {
+                ui.label(egui::RichText::new(name).color(PROP_LABEL_COLOR));
+            }
+            changed |= reflect_ui(field, ui, type_registry);
+        });
+    }
+    changed
+}
+
 impl<ValueType, ScriptType> BehaviorUI for BehaviorPropGeneric<ValueType, ScriptType>
 where
     ValueType: FromReflect + Reflect + Default + Clone + From<ScriptType>,
@@ -91,12 +205,7 @@ where
 
                         changed |= match &mut self.prop {
                             BehaviorEval::Value(value) => {
-                                let type_registry = type_registry.read();
-                                reflect_inspector::ui_for_value(
-                                    value.as_reflect_mut(),
-                                    ui,
-                                    &type_registry,
-                                )
+                                reflect_ui(value.as_reflect_mut(), ui, type_registry)
                             }
                             BehaviorEval::Eval { .. } => ui
                                 .add(
@@ -112,7 +221,7 @@ where
                 if changed {
                     match &mut self.prop {
                         BehaviorEval::Value(_) => {
-                            // handled by reflect_inspector
+                            // handled by reflect_ui
                         }
                         BehaviorEval::Eval { eval, .. } => {
                             *eval = editing_text.to_owned().into();
@@ -671,3 +780,67 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Reflect, FromReflect)]
+    struct Unregistered;
+
+    #[derive(Reflect, FromReflect)]
+    enum Param {
+        Off,
+        Value(f64),
+        Range { min: f64, max: u64 },
+        Custom(Unregistered),
+    }
+
+    fn switch(value: &mut dyn Reflect, variant: &str, type_registry: &TypeRegistry) -> bool {
+        let TypeInfo::Enum(info) = value.get_type_info() else {
+            panic!("Expected enum");
+        };
+        match enum_variant(info, variant, type_registry) {
+            Ok(dynamic) => {
+                value.apply(&dynamic);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[test]
+    fn enum_variant_switches_with_default_fields() {
+        let type_registry = TypeRegistry::default();
+        type_registry.write().register::<f64>();
+        type_registry.write().register::<u64>();
+
+        let mut param = Param::Off;
+        assert!(switch(&mut param, "Value", &type_registry));
+        assert!(matches!(param, Param::Value(value) if value == 0.0));
+
+        param = Param::Value(3.0);
+        assert!(switch(&mut param, "Range", &type_registry));
+        assert!(matches!(param, Param::Range { min, max: 0 } if min == 0.0));
+
+        assert!(switch(&mut param, "Off", &type_registry));
+        assert!(matches!(param, Param::Off));
+
+        // Variants with fields that cannot be defaulted are refused
+        assert!(!switch(&mut param, "Custom", &type_registry));
+        assert!(!switch(&mut param, "Missing", &type_registry));
+        assert!(matches!(param, Param::Off));
+    }
+
+    #[test]
+    fn enum_variant_toggles_options() {
+        let type_registry = TypeRegistry::default();
+        type_registry.write().register::<f64>();
+
+        let mut value: Option<f64> = None;
+        assert!(switch(&mut value, "Some", &type_registry));
+        assert_eq!(value, Some(0.0));
+        assert!(switch(&mut value, "None", &type_registry));
+        assert_eq!(value, None);
+    }
+}
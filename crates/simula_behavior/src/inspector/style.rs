@@ -0,0 +1,88 @@
+use crate::BehaviorType;
+use bevy::{prelude::*, utils::HashMap};
+use std::borrow::Cow;
+
+/// How a behavior node looks in the inspector
+#[derive(Clone, Debug)]
+pub struct BehaviorStyle {
+    pub color: Color,
+    /// Replaces the behavior's own icon when set
+    pub icon: Option<Cow<'static, str>>,
+}
+
+impl BehaviorStyle {
+    pub fn new(color: Color) -> Self {
+        Self { color, icon: None }
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<Cow<'static, str>>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+}
+
+/// Node colors and icons for the inspector, by behavior type, with overrides by behavior
+/// name. Insert or modify it at startup to restyle nodes.
+#[derive(Clone, Debug, Resource)]
+pub struct BehaviorStyles {
+    pub types: HashMap<BehaviorType, BehaviorStyle>,
+    pub names: HashMap<Cow<'static, str>, BehaviorStyle>,
+}
+
+impl Default for BehaviorStyles {
+    fn default() -> Self {
+        let mut styles = Self {
+            types: HashMap::default(),
+            names: HashMap::default(),
+        };
+        styles
+            .set_type(BehaviorType::Action, hex("235"))
+            .set_type(BehaviorType::Composite, hex("252"))
+            .set_type(BehaviorType::Decorator, hex("440"))
+            .set_type(BehaviorType::Subtree, hex("530"))
+            .set_name("Selector", hex("522"))
+            .set_name("Random Selector", hex("522"))
+            .set_name("Any", hex("522"))
+            .set_name("Parallel", hex("255"));
+        styles
+    }
+}
+
+fn hex(hex: &str) -> BehaviorStyle {
+    BehaviorStyle::new(Color::hex(hex).unwrap())
+}
+
+impl BehaviorStyles {
+    /// Style all behaviors of a type
+    pub fn set_type(&mut self, typ: BehaviorType, style: BehaviorStyle) -> &mut Self {
+        self.types.insert(typ, style);
+        self
+    }
+
+    /// Style a behavior by name, over the style of its type
+    pub fn set_name(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        style: BehaviorStyle,
+    ) -> &mut Self {
+        self.names.insert(name.into(), style);
+        self
+    }
+
+    /// Get the color of a behavior
+    pub fn color(&self, typ: BehaviorType, name: &str) -> Color {
+        self.names
+            .get(name)
+            .or_else(|| self.types.get(&typ))
+            .map_or(Color::DARK_GRAY, |style| style.color)
+    }
+
+    /// Get the icon of a behavior, or its own icon if not overridden
+    pub fn icon<'a>(&'a self, typ: BehaviorType, name: &str, icon: &'a str) -> &'a str {
+        self.names
+            .get(name)
+            .and_then(|style| style.icon.as_deref())
+            .or_else(|| self.types.get(&typ).and_then(|style| style.icon.as_deref()))
+            .unwrap_or(icon)
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    inspector::{
+        graph::{BehaviorDataType, BehaviorNodeData, BehaviorValueType},
+        utils,
+    },
+    protocol::BehaviorState,
+    BehaviorFactory,
+};
+use bevy::utils::HashMap;
+use egui_node_graph::{Graph, NodeId};
+use simula_inspector::egui;
+use std::time::Duration;
+
+/// How long recorded node states are kept
+pub const TIMELINE_HISTORY: Duration = Duration::from_secs(30);
+/// Span of time shown at once
+pub const TIMELINE_VIEW: Duration = Duration::from_secs(5);
+
+const ROW_HEIGHT: f32 = 16.0;
+const LABEL_WIDTH: f32 = 160.0;
+const TICKS_HEIGHT: f32 = 14.0;
+
+/// A node state held over a span of time
+#[derive(Clone, Copy, Debug)]
+pub struct BehaviorTimelineSpan {
+    pub state: BehaviorState,
+    pub start: Duration,
+    pub end: Duration,
+}
+
+/// Recent history of node states, recorded from telemetry and drawn as a Gantt chart, one row
+/// per node in tree order.
+#[derive(Clone, Debug, Default)]
+pub struct BehaviorTimeline {
+    pub spans: HashMap<NodeId, Vec<BehaviorTimelineSpan>>,
+    /// Time of the last record
+    pub latest: Duration,
+    /// How far back from the last record the view ends, zero follows live
+    pub scrub: Duration,
+    /// Time of the frame shown on the graph, when stepped back
+    pub marker: Option<Duration>,
+}
+
+impl BehaviorTimeline {
+    /// Record the state of every graph node at a time. Nodes keeping their state extend
+    /// their last span, other states start a new one.
+    pub fn record<T: BehaviorFactory>(
+        &mut self,
+        graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+        now: Duration,
+    ) {
+        for (node_id, node) in graph.nodes.iter() {
+            let state = match node.user_data.state {
+                Some(BehaviorState::None) | None => continue,
+                Some(state) => state,
+            };
+            let spans = self.spans.entry(node_id).or_default();
+            match spans.last_mut() {
+                Some(span) if span.state == state && span.end == self.latest => span.end = now,
+                _ => spans.push(BehaviorTimelineSpan {
+                    state,
+                    start: now,
+                    end: now,
+                }),
+            }
+        }
+        self.latest = now;
+
+        // forget what is too old, or no longer in the graph
+        let oldest = now.saturating_sub(TIMELINE_HISTORY);
+        self.spans.retain(|node_id, spans| {
+            spans.retain(|span| span.end >= oldest);
+            !spans.is_empty() && graph.nodes.contains_key(*node_id)
+        });
+    }
+
+    /// Draw the chart, dragging it or the slider scrubs back through history
+    pub fn ui<T: BehaviorFactory>(
+        &mut self,
+        ui: &mut egui::Ui,
+        graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+    ) {
+        let history = self
+            .latest
+            .min(TIMELINE_HISTORY)
+            .saturating_sub(TIMELINE_VIEW);
+
+        ui.horizontal(|ui| {
+            let mut back = -self.scrub.as_secs_f32();
+            let slider = egui::Slider::new(&mut back, -history.as_secs_f32()..=0.0)
+                .suffix("s")
+                .fixed_decimals(1);
+            if ui.add(slider).changed() {
+                self.scrub = Duration::from_secs_f32(-back);
+            }
+            if ui
+                .add_enabled(!self.scrub.is_zero(), egui::Button::new("⏩ Live"))
+                .clicked()
+            {
+                self.scrub = Duration::ZERO;
+            }
+        });
+
+        // rows in tree order, indented by depth
+        let mut rows = vec![];
+        if let Some(root_child) = utils::get_root_child(graph) {
+            collect_rows(graph, root_child, 0, &mut rows);
+        }
+
+        let size = egui::vec2(
+            ui.available_width(),
+            rows.len() as f32 * ROW_HEIGHT + TICKS_HEIGHT,
+        );
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::drag());
+        let painter = ui.painter_at(rect);
+        let chart = egui::Rect::from_min_max(
+            egui::pos2(rect.min.x + LABEL_WIDTH, rect.min.y + TICKS_HEIGHT),
+            rect.max,
+        );
+        let view = TIMELINE_VIEW.as_secs_f32();
+        let secs_per_px = view / chart.width().max(1.0);
+
+        // drag right to go back in time
+        if response.dragged() {
+            let back = self.scrub.as_secs_f32() + response.drag_delta().x * secs_per_px;
+            self.scrub = Duration::from_secs_f32(back.clamp(0.0, history.as_secs_f32()));
+        }
+
+        let end = self.latest.saturating_sub(self.scrub).as_secs_f32();
+        let start = end - view;
+        let to_x = |time: f32| chart.min.x + (time - start) / secs_per_px;
+
+        // a tick per second, labeled by how long ago it was
+        let text_color = ui.visuals().weak_text_color();
+        let font = egui::FontId::proportional(10.0);
+        for second in (start.ceil() as i64)..=(end.floor() as i64) {
+            let x = to_x(second as f32);
+            painter.line_segment(
+                [egui::pos2(x, chart.min.y), egui::pos2(x, chart.max.y)],
+                egui::Stroke::new(1.0, egui::Color32::from_gray(50)),
+            );
+            let ago = self.latest.as_secs_f32() - second as f32;
+            painter.text(
+                egui::pos2(x, rect.min.y),
+                egui::Align2::CENTER_TOP,
+                format!("-{:.0}s", ago),
+                font.clone(),
+                text_color,
+            );
+        }
+
+        // the past frame shown on the graph
+        if let Some(marker) = self.marker {
+            let x = to_x(marker.as_secs_f32());
+            if (chart.min.x..=chart.max.x).contains(&x) {
+                painter.line_segment(
+                    [egui::pos2(x, rect.min.y), egui::pos2(x, chart.max.y)],
+                    egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 160, 0)),
+                );
+            }
+        }
+
+        let hover = response.hover_pos();
+        let mut hovered = None;
+        for (row, (node_id, depth)) in rows.iter().enumerate() {
+            let top = chart.min.y + row as f32 * ROW_HEIGHT;
+            let name = graph.nodes[*node_id].label.as_str();
+            painter.text(
+                egui::pos2(rect.min.x + *depth as f32 * 8.0, top + ROW_HEIGHT * 0.5),
+                egui::Align2::LEFT_CENTER,
+                name,
+                font.clone(),
+                ui.visuals().text_color(),
+            );
+
+            let Some(spans) = self.spans.get(node_id) else {
+                continue;
+            };
+            for span in spans {
+                let (span_start, span_end) = (span.start.as_secs_f32(), span.end.as_secs_f32());
+                if span_end < start || span_start > end {
+                    continue;
+                }
+                // keep instant spans visible
+                let min_x = to_x(span_start.max(start));
+                let max_x = to_x(span_end.min(end)).max(min_x + 2.0);
+                let span_rect = egui::Rect::from_min_max(
+                    egui::pos2(min_x, top + 2.0),
+                    egui::pos2(max_x, top + ROW_HEIGHT - 2.0),
+                );
+                painter.rect_filled(span_rect, 2.0, state_color(span.state));
+                if hover.map_or(false, |pos| span_rect.contains(pos)) {
+                    hovered = Some((name, *span));
+                }
+            }
+        }
+
+        if let Some((name, span)) = hovered {
+            egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("Behavior Timeline"), |ui| {
+                ui.label(format!(
+                    "{}: {:?} for {:.2}s",
+                    name,
+                    span.state,
+                    (span.end - span.start).as_secs_f32()
+                ));
+            });
+        }
+    }
+}
+
+fn collect_rows<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+    node_id: NodeId,
+    depth: usize,
+    rows: &mut Vec<(NodeId, usize)>,
+) {
+    rows.push((node_id, depth));
+    for child in utils::get_flow_children(graph, node_id) {
+        collect_rows(graph, child, depth + 1, rows);
+    }
+}
+
+fn state_color(state: BehaviorState) -> egui::Color32 {
+    match state {
+        BehaviorState::Cursor | BehaviorState::Running => egui::Color32::GREEN,
+        BehaviorState::Success => egui::Color32::DARK_GREEN,
+        BehaviorState::Failure => egui::Color32::DARK_RED,
+        BehaviorState::None => egui::Color32::from_rgb(80, 80, 80),
+    }
+}
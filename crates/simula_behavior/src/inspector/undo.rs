@@ -0,0 +1,324 @@
+use crate::{
+    inspector::graph::{
+        BehaviorData, BehaviorDataType, BehaviorEditorState, BehaviorNodeData, BehaviorValueType,
+    },
+    BehaviorFactory,
+};
+use bevy_inspector_egui::egui;
+use egui_node_graph::{Graph, InputId, NodeId, OutputId};
+use std::collections::VecDeque;
+
+/// Most edits kept for undo, by default
+pub const UNDO_DEPTH: usize = 100;
+
+pub type BehaviorGraph<T> = Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>;
+
+/// A reversible edit of the graph. Applying an edit returns the edit that reverses it.
+pub enum BehaviorEdit<T: BehaviorFactory> {
+    /// Put back a whole graph and node positions, used when nodes are added or removed, as
+    /// node ids cannot be reused once a node is removed
+    Restore {
+        graph: BehaviorGraph<T>,
+        positions: Vec<(NodeId, egui::Pos2)>,
+    },
+    /// Remove a node and its connections
+    RemoveNode { node_id: NodeId },
+    /// Connect an input to an output, replacing any connection of the input
+    Connect { input: InputId, output: OutputId },
+    /// Remove the connection of an input
+    Disconnect { input: InputId },
+    /// Replace the behavior of a node
+    Data { node_id: NodeId, data: T },
+    /// Rename a node
+    Rename { node_id: NodeId, name: String },
+}
+
+impl<T: BehaviorFactory> BehaviorEdit<T> {
+    /// An edit putting back the graph as it is now
+    pub fn restore(editor: &BehaviorEditorState<T>) -> Self {
+        Self::Restore {
+            graph: editor.graph.clone(),
+            positions: node_positions(editor),
+        }
+    }
+
+    /// Apply the edit, returns the edit reversing it, if anything changed
+    pub fn apply(self, editor: &mut BehaviorEditorState<T>) -> Option<Self> {
+        match self {
+            Self::Restore {
+                mut graph,
+                positions,
+            } => {
+                std::mem::swap(&mut editor.graph, &mut graph);
+                let previous = node_positions(editor);
+                editor.node_positions.clear();
+                for (node_id, position) in positions {
+                    editor.node_positions.insert(node_id, position);
+                }
+                // keep draw order and selection in line with the restored nodes
+                let nodes = &editor.0.graph.nodes;
+                editor
+                    .0
+                    .node_order
+                    .retain(|node_id| nodes.contains_key(*node_id));
+                for node_id in nodes.keys() {
+                    if !editor.0.node_order.contains(&node_id) {
+                        editor.0.node_order.push(node_id);
+                    }
+                }
+                editor
+                    .0
+                    .selected_nodes
+                    .retain(|node_id| nodes.contains_key(*node_id));
+                Some(Self::Restore {
+                    graph,
+                    positions: previous,
+                })
+            }
+            Self::RemoveNode { node_id } => {
+                if !editor.graph.nodes.contains_key(node_id) {
+                    return None;
+                }
+                let restore = Self::restore(editor);
+                editor.graph.remove_node(node_id);
+                editor.node_positions.remove(node_id);
+                editor.node_order.retain(|id| *id != node_id);
+                editor.selected_nodes.retain(|id| *id != node_id);
+                Some(restore)
+            }
+            Self::Connect { input, output } => {
+                if !editor.graph.inputs.contains_key(input)
+                    || !editor.graph.outputs.contains_key(output)
+                {
+                    return None;
+                }
+                let previous = editor.graph.connection(input);
+                editor.graph.add_connection(output, input);
+                Some(match previous {
+                    Some(output) => Self::Connect { input, output },
+                    None => Self::Disconnect { input },
+                })
+            }
+            Self::Disconnect { input } => editor
+                .graph
+                .remove_connection(input)
+                .map(|output| Self::Connect { input, output }),
+            Self::Data { node_id, data } => {
+                let node = editor.graph.nodes.get_mut(node_id)?;
+                match std::mem::replace(&mut node.user_data.data, BehaviorData::Behavior(data)) {
+                    BehaviorData::Behavior(data) => Some(Self::Data { node_id, data }),
+                    BehaviorData::Root => None,
+                }
+            }
+            Self::Rename { node_id, name } => {
+                let node = editor.graph.nodes.get_mut(node_id)?;
+                let name = std::mem::replace(&mut node.label, name);
+                Some(Self::Rename { node_id, name })
+            }
+        }
+    }
+}
+
+fn node_positions<T: BehaviorFactory>(
+    editor: &BehaviorEditorState<T>,
+) -> Vec<(NodeId, egui::Pos2)> {
+    editor
+        .node_positions
+        .iter()
+        .map(|(node_id, position)| (node_id, *position))
+        .collect()
+}
+
+/// Undo and redo stacks of a graph editor. Each step holds the edits reversing everything
+/// changed in one frame, in the order they happened.
+pub struct BehaviorUndo<T: BehaviorFactory> {
+    pub undo: VecDeque<Vec<BehaviorEdit<T>>>,
+    pub redo: Vec<Vec<BehaviorEdit<T>>>,
+    /// Most steps kept, the oldest are dropped
+    pub depth: usize,
+    /// Reversing edits of the step being recorded
+    step: Vec<BehaviorEdit<T>>,
+}
+
+impl<T: BehaviorFactory> Default for BehaviorUndo<T> {
+    fn default() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth: UNDO_DEPTH,
+            step: Vec::new(),
+        }
+    }
+}
+
+impl<T: BehaviorFactory> BehaviorUndo<T> {
+    /// Record an edit reversing a change just made
+    pub fn push(&mut self, inverse: BehaviorEdit<T>) {
+        self.step.push(inverse);
+    }
+
+    /// Close the step being recorded, a new change clears what can be redone
+    pub fn commit(&mut self) {
+        if self.step.is_empty() {
+            return;
+        }
+        self.undo.push_back(std::mem::take(&mut self.step));
+        while self.undo.len() > self.depth {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.step.clear();
+    }
+}
+
+// Apply a step of edits in reverse order, returns the step reversing it
+fn apply_step<T: BehaviorFactory>(
+    editor: &mut BehaviorEditorState<T>,
+    step: Vec<BehaviorEdit<T>>,
+) -> Vec<BehaviorEdit<T>> {
+    step.into_iter()
+        .rev()
+        .filter_map(|edit| edit.apply(editor))
+        .collect()
+}
+
+impl<T: BehaviorFactory> BehaviorEditorState<T> {
+    /// Reverse the last step, returns true if the graph changed
+    pub fn undo(&mut self) -> bool {
+        self.1.commit();
+        let Some(step) = self.1.undo.pop_back() else {
+            return false;
+        };
+        let redo = apply_step(self, step);
+        self.1.redo.push(redo);
+        true
+    }
+
+    /// Apply again the last undone step, returns true if the graph changed
+    pub fn redo(&mut self) -> bool {
+        self.1.commit();
+        let Some(step) = self.1.redo.pop() else {
+            return false;
+        };
+        let undo = apply_step(self, step);
+        self.1.undo.push_back(undo);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inspector::{graph::BehaviorGraphState, utils},
+        test::TestBehavior,
+        Behavior,
+    };
+
+    fn save(editor: &BehaviorEditorState<TestBehavior>) -> String {
+        let behavior = utils::graph_to_behavior(editor, None).unwrap();
+        ron::ser::to_string_pretty(&behavior, ron::ser::PrettyConfig::default()).unwrap()
+    }
+
+    fn node(editor: &BehaviorEditorState<TestBehavior>, name: &str) -> NodeId {
+        editor
+            .graph
+            .nodes
+            .iter()
+            .find(|(_, node)| node.label == name)
+            .map(|(node_id, _)| node_id)
+            .unwrap()
+    }
+
+    // Apply an edit the way the graph editor does, recording its reverse
+    fn edit(editor: &mut BehaviorEditorState<TestBehavior>, edit: BehaviorEdit<TestBehavior>) {
+        if let Some(inverse) = edit.apply(editor) {
+            editor.1.push(inverse);
+        }
+        editor.1.commit();
+    }
+
+    #[test]
+    fn undo_redo_edits() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(
+            r#"
+            (
+                "Sequence",
+                Sequencer(()),
+                [
+                    ("First", Debug(()), [], (pos: (100.0, 0.0))),
+                    ("Second", Wait(()), [], (pos: (100.0, 50.0))),
+                ],
+            )
+            "#,
+        )
+        .unwrap();
+        let mut editor = utils::behavior_graph(&mut BehaviorGraphState::default(), &behavior);
+        let original = save(&editor);
+
+        let first = node(&editor, "First");
+        let second = node(&editor, "Second");
+        let input = editor.graph.nodes[second].inputs[0].1;
+        let output = editor.graph.connection(input).unwrap();
+        edit(
+            &mut editor,
+            BehaviorEdit::Rename {
+                node_id: first,
+                name: "Renamed".into(),
+            },
+        );
+        edit(
+            &mut editor,
+            BehaviorEdit::Data {
+                node_id: first,
+                data: TestBehavior::default(),
+            },
+        );
+        edit(&mut editor, BehaviorEdit::Disconnect { input });
+        edit(&mut editor, BehaviorEdit::Connect { input, output });
+        edit(&mut editor, BehaviorEdit::RemoveNode { node_id: second });
+        let edited = save(&editor);
+        assert_ne!(edited, original);
+        assert!(!editor.graph.nodes.contains_key(second));
+
+        while editor.undo() {}
+        assert_eq!(save(&editor), original);
+        assert_eq!(editor.graph.nodes[first].label, "First");
+        assert_eq!(editor.node_order.len(), editor.graph.nodes.len());
+
+        while editor.redo() {}
+        assert_eq!(save(&editor), edited);
+    }
+
+    #[test]
+    fn undo_depth() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(r#"("Debug", Debug(()))"#).unwrap();
+        let mut editor = utils::behavior_graph(&mut BehaviorGraphState::default(), &behavior);
+        editor.1.depth = 2;
+        let node_id = node(&editor, "Debug");
+        for name in ["A", "B", "C"] {
+            edit(
+                &mut editor,
+                BehaviorEdit::Rename {
+                    node_id,
+                    name: name.into(),
+                },
+            );
+        }
+        while editor.undo() {}
+        assert_eq!(editor.graph.nodes[node_id].label, "A");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    composites::Switch,
     inspector::{
         graph::{
             BehaviorData, BehaviorDataType, BehaviorEditorState, BehaviorGraphState,
@@ -6,15 +7,17 @@ use crate::{
         },
         BehaviorInspectable, BehaviorInspector, BehaviorNodeInspectable,
     },
-    protocol::{BehaviorFileId, BehaviorTelemetry, RemoteEntity, StartOption, StopOption},
-    Behavior, BehaviorFactory, BehaviorType,
+    protocol::{
+        BehaviorFileId, BehaviorState, BehaviorTelemetry, RemoteEntity, StartOption, StopOption,
+    },
+    Behavior, BehaviorFactory, BehaviorOutcome, BehaviorType,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 use egui_node_graph::{Graph, InputId, NodeId, NodeTemplateTrait, OutputId};
 use simula_inspector::egui;
 use std::borrow::Cow;
 
-fn get_root_child<T: BehaviorFactory>(
+pub(super) fn get_root_child<T: BehaviorFactory>(
     graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
 ) -> Option<NodeId> {
     for node in graph.nodes.values() {
@@ -23,16 +26,7 @@ fn get_root_child<T: BehaviorFactory>(
             let root_child_id: Option<NodeId> = node
                 .outputs
                 .iter()
-                .filter_map(|(_, output_id)| {
-                    graph
-                        .connections
-                        .iter()
-                        .find(|(input_id, rhs_output_id)| {
-                            output_id == *rhs_output_id
-                                && graph.inputs[*input_id].typ == BehaviorDataType::Flow
-                        })
-                        .and_then(|(input_id, _)| Some(graph.inputs[input_id].node))
-                })
+                .filter_map(|(_, output_id)| get_flow_child(graph, *output_id))
                 .next();
             return root_child_id;
         }
@@ -40,6 +34,137 @@ fn get_root_child<T: BehaviorFactory>(
     None
 }
 
+// Get the node connected to a flow output. An output can be connected to many inputs,
+// pick the lowest node id so the result doesn't depend on connection iteration order.
+fn get_flow_child<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+    output_id: OutputId,
+) -> Option<NodeId> {
+    graph
+        .connections
+        .iter()
+        .filter(|(input_id, rhs_output_id)| {
+            output_id == **rhs_output_id && graph.inputs[*input_id].typ == BehaviorDataType::Flow
+        })
+        .map(|(input_id, _)| graph.inputs[input_id].node)
+        .min()
+}
+
+// Get the nodes connected to the flow outputs of a node, in output order
+pub(super) fn get_flow_children<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+    node_id: NodeId,
+) -> Vec<NodeId> {
+    graph.nodes[node_id]
+        .outputs
+        .iter()
+        .filter_map(|(_, output_id)| get_flow_child(graph, *output_id))
+        .collect()
+}
+
+// Least number of outputs a node keeps, a switch needs an output up to its index
+pub(super) fn min_outputs<T: BehaviorFactory>(behavior: &T) -> usize {
+    behavior
+        .inner_reflect()
+        .downcast_ref::<Switch>()
+        .and_then(Switch::index_value)
+        .map_or(0, |index| index.max(0) as usize + 1)
+}
+
+// Add outputs to a node until it has the least number it keeps
+pub(super) fn grow_outputs<T: BehaviorFactory>(
+    graph: &mut Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+    node_id: NodeId,
+) {
+    let Some(node) = graph.nodes.get(node_id) else {
+        return;
+    };
+    let BehaviorData::Behavior(behavior) = &node.user_data.data else {
+        return;
+    };
+    let missing = min_outputs(behavior).saturating_sub(node.outputs.len());
+    for _ in 0..missing {
+        graph.add_output_param(node_id, "".into(), BehaviorDataType::Flow);
+    }
+}
+
+// Find the node currently holding the cursor, if any
+pub(super) fn find_cursor<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+) -> Option<NodeId> {
+    graph.nodes.iter().find_map(|(node_id, node)| {
+        if let Some(BehaviorState::Cursor) = node.user_data.state {
+            Some(node_id)
+        } else {
+            None
+        }
+    })
+}
+
+// Get the final state of a tree that has no cursor and no running nodes.
+// Returns None while the tree is still running, or if no telemetry was received.
+pub(super) fn find_idle_state<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+) -> Option<(BehaviorState, Option<BehaviorOutcome>)> {
+    if find_cursor(graph).is_some() {
+        return None;
+    }
+    let running = graph
+        .nodes
+        .values()
+        .any(|node| matches!(node.user_data.state, Some(BehaviorState::Running)));
+    if running {
+        return None;
+    }
+    let root_child_id = get_root_child(graph)?;
+    let root_child = &graph.nodes[root_child_id].user_data;
+    match root_child.state {
+        Some(state @ (BehaviorState::Success | BehaviorState::Failure)) => {
+            Some((state, root_child.outcome.clone()))
+        }
+        _ => None,
+    }
+}
+
+// Nodes on the path from root to a node, plus the node subtree
+pub(super) fn get_branch<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+    node_id: NodeId,
+) -> HashSet<NodeId> {
+    let mut branch = HashSet::new();
+
+    // Walk up to the root
+    let mut parent_id = Some(node_id);
+    while let Some(current_id) = parent_id {
+        if !branch.insert(current_id) {
+            break;
+        }
+        parent_id = graph.nodes[current_id]
+            .inputs
+            .iter()
+            .filter(|(_, input_id)| graph.inputs[*input_id].typ == BehaviorDataType::Flow)
+            .find_map(|(_, input_id)| graph.connections.get(*input_id))
+            .map(|output_id| graph.outputs[*output_id].node);
+    }
+
+    // Walk down the subtree
+    let mut children = vec![node_id];
+    while let Some(current_id) = children.pop() {
+        for (_, output_id) in graph.nodes[current_id].outputs.iter() {
+            for (input_id, rhs_output_id) in graph.connections.iter() {
+                if output_id == rhs_output_id
+                    && graph.inputs[input_id].typ == BehaviorDataType::Flow
+                    && branch.insert(graph.inputs[input_id].node)
+                {
+                    children.push(graph.inputs[input_id].node);
+                }
+            }
+        }
+    }
+
+    branch
+}
+
 // Recursively build behavior from graph
 pub fn graph_to_behavior<T>(
     editor: &BehaviorEditorState<T>,
@@ -59,7 +184,9 @@ where
     };
 
     let node: &egui_node_graph::Node<BehaviorNodeData<T>> = &editor.graph.nodes[node_id];
-    let BehaviorData::Behavior(behavior) = &node.user_data.data else { return Err("Expected behavior node".to_owned()) };
+    let BehaviorData::Behavior(behavior) = &node.user_data.data else {
+        return Err("Expected behavior node".to_owned());
+    };
     let mut attribs = <T as BehaviorFactory>::Attributes::default();
     editor
         .node_positions
@@ -71,24 +198,135 @@ where
         attribs,
         Default::default(),
     );
-    for (_, output_id) in node.outputs.iter() {
-        let child_id = editor
-            .graph
-            .connections
-            .iter()
-            .find(|(input_id, rhs_output_id)| {
-                output_id == *rhs_output_id
-                    && editor.graph.inputs[*input_id].typ == BehaviorDataType::Flow
-            })
-            .and_then(|(input_id, _)| Some(editor.graph.inputs[input_id].node));
-        if let Some(child_id) = child_id {
-            if let Ok(child) = graph_to_behavior(editor, Some(child_id)) {
-                behavior.nodes_mut().push(child);
+    // children of subtrees are only shown while running, they are not part of the behavior
+    if behavior.data().typ() == BehaviorType::Subtree {
+        return Ok(behavior);
+    }
+    for child_id in get_flow_children(&editor.graph, node_id) {
+        let child = graph_to_behavior(editor, Some(child_id))?;
+        behavior.nodes_mut().push(child);
+    }
+
+    Ok(behavior)
+}
+
+/// Why a graph cannot be turned into a behavior
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphValidationError {
+    /// A node is its own ancestor, through the node
+    Cycle(NodeId),
+    /// More than one root node, all of them
+    MultipleRoots(Vec<NodeId>),
+    /// A root, decorator or action with more than one child, or a composite output
+    /// connected to more than one child
+    MultipleChildren(NodeId),
+}
+
+impl std::fmt::Display for GraphValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle(node_id) => write!(f, "Cycle through node {:?}", node_id),
+            Self::MultipleRoots(node_ids) => write!(f, "{} root nodes", node_ids.len()),
+            Self::MultipleChildren(node_id) => {
+                write!(f, "Node {:?} has too many children", node_id)
             }
         }
     }
+}
 
-    Ok(behavior)
+// Flow children of each output of a node, including all inputs an output is connected to
+fn get_output_children<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+    node_id: NodeId,
+) -> Vec<Vec<NodeId>> {
+    graph.nodes[node_id]
+        .outputs
+        .iter()
+        .map(|(_, output_id)| {
+            let mut children = graph
+                .connections
+                .iter()
+                .filter(|(input_id, rhs_output_id)| {
+                    output_id == *rhs_output_id
+                        && graph.inputs[*input_id].typ == BehaviorDataType::Flow
+                })
+                .map(|(input_id, _)| graph.inputs[input_id].node)
+                .collect::<Vec<_>>();
+            children.sort();
+            children
+        })
+        .collect()
+}
+
+/// Check a graph can be turned into a behavior: a single root, no cycles, and no node with
+/// more children than it can run. Empty when the graph is valid.
+pub fn validate_graph<T: BehaviorFactory>(
+    graph: &Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
+) -> Vec<GraphValidationError> {
+    let mut errors = vec![];
+    let mut node_ids = graph.nodes.keys().collect::<Vec<_>>();
+    node_ids.sort();
+
+    let roots = node_ids
+        .iter()
+        .copied()
+        .filter(|node_id| matches!(graph.nodes[*node_id].user_data.data, BehaviorData::Root))
+        .collect::<Vec<_>>();
+    if roots.len() > 1 {
+        errors.push(GraphValidationError::MultipleRoots(roots));
+    }
+
+    for node_id in node_ids.iter().copied() {
+        let outputs = get_output_children(graph, node_id);
+        let composite = match &graph.nodes[node_id].user_data.data {
+            BehaviorData::Root => false,
+            BehaviorData::Behavior(behavior) => behavior.typ() == BehaviorType::Composite,
+        };
+        let too_many = if composite {
+            outputs.iter().any(|children| children.len() > 1)
+        } else {
+            outputs.iter().map(Vec::len).sum::<usize>() > 1
+        };
+        if too_many {
+            errors.push(GraphValidationError::MultipleChildren(node_id));
+        }
+    }
+
+    // depth first walk from every node not yet visited, a child still on the path closes
+    // a cycle
+    let mut visited = HashSet::new();
+    for start in node_ids {
+        if visited.contains(&start) {
+            continue;
+        }
+        let mut path: Vec<(NodeId, Vec<NodeId>)> = vec![];
+        visited.insert(start);
+        path.push((start, get_output_children(graph, start).concat()));
+        while let Some((_, children)) = path.last_mut() {
+            let Some(child_id) = children.pop() else {
+                path.pop();
+                continue;
+            };
+            if path.iter().any(|(node_id, _)| *node_id == child_id) {
+                errors.push(GraphValidationError::Cycle(child_id));
+            } else if visited.insert(child_id) {
+                path.push((child_id, get_output_children(graph, child_id).concat()));
+            }
+        }
+    }
+
+    errors
+}
+
+// Inline behaviors are not part of the graph, carry them over from the loaded behavior
+pub(super) fn keep_inline<T: BehaviorFactory>(
+    mut behavior: Behavior<T>,
+    previous: &Option<Behavior<T>>,
+) -> Behavior<T> {
+    if let Some(previous) = previous {
+        *behavior.inline_mut() = previous.inline().clone();
+    }
+    behavior
 }
 
 // Recursively update graph from behavior
@@ -117,32 +355,57 @@ where
     node.user_data.data = BehaviorData::Behavior(behavior.data().clone());
     node.user_data.state = None;
 
-    // Get node children
-    let node_children: Vec<NodeId> = node
-        .outputs
-        .iter()
-        .filter_map(|(_, output_id)| {
-            graph
-                .connections
-                .iter()
-                .find(|(input_id, rhs_output_id)| {
-                    output_id == *rhs_output_id
-                        && graph.inputs[*input_id].typ == BehaviorDataType::Flow
-                })
-                .and_then(|(input_id, _)| Some(graph.inputs[input_id].node))
-        })
-        .collect();
-
-    // Zip and iterate over children
-    let node_children = node_children.iter().cloned();
-    let behavior_children = behavior.nodes().iter();
-    for (node_child, behavior_child) in node_children.zip(behavior_children) {
+    // Graph and behavior must have the same shape, children are matched in order
+    let node_children = get_flow_children(graph, node_id);
+    if node_children.len() != behavior.nodes().len() {
+        return Err(format!(
+            "Node {} has {} children in graph, but {} in behavior",
+            behavior.name(),
+            node_children.len(),
+            behavior.nodes().len()
+        ));
+    }
+    for (node_child, behavior_child) in node_children.into_iter().zip(behavior.nodes()) {
         behavior_to_graph(editor, Some(node_child), behavior_child)?;
     }
 
     Ok(())
 }
 
+// Create a graph with a root node, holding the behavior
+pub fn behavior_graph<T>(
+    graph_state: &mut BehaviorGraphState,
+    behavior: &Behavior<T>,
+) -> BehaviorEditorState<T>
+where
+    T: BehaviorFactory + BehaviorInspectable,
+    <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
+{
+    let mut editor = BehaviorEditorState::<T>::default();
+
+    // create the only root node allowed
+    let root_node_data = BehaviorNodeData {
+        data: BehaviorData::Root,
+        state: None,
+        entity: None,
+        cost: None,
+        duration: 0.0,
+        outcome: None,
+    };
+    let root_node = editor
+        .graph
+        .add_node("Root".into(), root_node_data, |graph, node_id| {
+            BehaviorNodeTemplate::Root.build_node(graph, graph_state, node_id)
+        });
+    editor
+        .node_positions
+        .insert(root_node, egui::Pos2::new(0.0, 0.0));
+    editor.node_order.push(root_node);
+
+    behavior_into_graph(&mut editor, graph_state, root_node, behavior);
+    editor
+}
+
 // Recursively create graph from behavior
 pub fn behavior_into_graph<T>(
     editor: &mut BehaviorEditorState<T>,
@@ -160,6 +423,9 @@ pub fn behavior_into_graph<T>(
         data: behavior_data.clone(),
         state: None,
         entity: None,
+        cost: None,
+        duration: 0.0,
+        outcome: None,
     };
     let node_id = editor
         .graph
@@ -218,7 +484,133 @@ pub fn behavior_into_graph<T>(
     }
 }
 
+// Create graph nodes for the nodes reported by transparent subtrees, laid out after the
+// subtree node. Created nodes are added to the graph state expanded set.
+pub(super) fn expand_subtrees<T>(
+    editor: &mut BehaviorEditorState<T>,
+    graph_state: &mut BehaviorGraphState,
+    node_id: Option<NodeId>,
+    telemetry: &BehaviorTelemetry<T>,
+) where
+    T: BehaviorFactory + BehaviorInspectable,
+    <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
+{
+    let Some(node_id) = node_id else {
+        if let Some(root_child_id) = get_root_child(&editor.graph) {
+            expand_subtrees(editor, graph_state, Some(root_child_id), telemetry);
+        }
+        return;
+    };
+
+    let node_children = get_flow_children(&editor.graph, node_id);
+    let subtree = match &editor.graph.nodes[node_id].user_data.data {
+        BehaviorData::Behavior(behavior) => behavior.typ() == BehaviorType::Subtree,
+        BehaviorData::Root => false,
+    };
+    if subtree && node_children.is_empty() {
+        let origin = editor.node_positions[node_id];
+        let mut row = 0;
+        let Some(behavior) = telemetry
+            .3
+            .first()
+            .and_then(|child| telemetry_to_behavior::<T>(child, origin, 1, &mut row))
+        else {
+            return;
+        };
+        // subtrees have no output, add one to connect the subtree root
+        editor
+            .graph
+            .add_output_param(node_id, "".into(), BehaviorDataType::Flow);
+        let before = editor.graph.nodes.keys().collect::<HashSet<_>>();
+        behavior_into_graph(editor, graph_state, node_id, &behavior);
+        let created = editor.graph.nodes.keys().filter(|id| !before.contains(id));
+        graph_state.expanded.extend(created);
+        return;
+    }
+
+    for (node_child, telemetry_child) in node_children.into_iter().zip(telemetry.3.iter()) {
+        expand_subtrees(editor, graph_state, Some(node_child), telemetry_child);
+    }
+}
+
+// Build a behavior out of telemetry, laid out like layout_graph from an origin
+fn telemetry_to_behavior<T>(
+    telemetry: &BehaviorTelemetry<T>,
+    origin: egui::Pos2,
+    depth: usize,
+    row: &mut usize,
+) -> Option<Behavior<T>>
+where
+    T: BehaviorFactory,
+    <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
+{
+    let BehaviorTelemetry(entity, _, Some(data), children, _, _, _) = telemetry else {
+        return None;
+    };
+    let name = entity
+        .as_ref()
+        .map_or_else(|| data.label().to_owned(), |entity| entity.name.to_string());
+    let mut attrs = <T as BehaviorFactory>::Attributes::default();
+    attrs.set_pos(Vec2::new(
+        origin.x + depth as f32 * NODE_WIDTH,
+        origin.y + *row as f32 * NODE_HEIGHT,
+    ));
+    let mut behavior = Behavior::new(name, data.clone(), attrs, vec![]);
+    for (idx, child) in children.iter().enumerate() {
+        if idx > 0 {
+            *row += 1;
+        }
+        behavior
+            .nodes_mut()
+            .extend(telemetry_to_behavior(child, origin, depth + 1, row));
+    }
+    Some(behavior)
+}
+
+// Remove the nodes created for transparent subtrees, and the outputs added to connect them
+pub(super) fn collapse_subtrees<T: BehaviorFactory>(
+    editor: &mut BehaviorEditorState<T>,
+    graph_state: &mut BehaviorGraphState,
+) {
+    for node_id in graph_state.expanded.drain() {
+        if editor.graph.nodes.contains_key(node_id) {
+            editor.graph.remove_node(node_id);
+        }
+        editor.node_positions.remove(node_id);
+        editor.node_order.retain(|id| *id != node_id);
+        editor.selected_nodes.retain(|id| *id != node_id);
+    }
+    let outputs = editor
+        .graph
+        .nodes
+        .values()
+        .filter(|node| match &node.user_data.data {
+            BehaviorData::Behavior(behavior) => behavior.typ() == BehaviorType::Subtree,
+            BehaviorData::Root => false,
+        })
+        .flat_map(|node| node.output_ids())
+        .collect::<Vec<_>>();
+    for output_id in outputs {
+        editor.graph.remove_output_param(output_id);
+    }
+}
+
 // Recursively update graph from behavior telemetry
+// Show telemetry on the graph, with the nodes of transparent subtrees
+pub(super) fn show_telemetry<T>(
+    editor: &mut BehaviorEditorState<T>,
+    graph_state: &mut BehaviorGraphState,
+    telemetry: &BehaviorTelemetry<T>,
+) where
+    T: BehaviorFactory + BehaviorInspectable,
+    <T as BehaviorFactory>::Attributes: BehaviorNodeInspectable<T>,
+{
+    expand_subtrees(editor, graph_state, None, telemetry);
+    if let Err(e) = behavior_telemerty_to_graph(&mut editor.graph, None, telemetry) {
+        error!("Failed to apply telemetry: {}", e);
+    }
+}
+
 pub fn behavior_telemerty_to_graph<T>(
     graph: &mut Graph<BehaviorNodeData<T>, BehaviorDataType, BehaviorValueType<T>>,
     node_id: Option<NodeId>,
@@ -238,27 +630,18 @@ where
 
     // Update graph node with behavior telemetry
     let node: &mut egui_node_graph::Node<BehaviorNodeData<T>> = &mut graph.nodes[node_id];
-    if let BehaviorTelemetry(entity, state, Some(behavior), _) = telemetry {
+    if let BehaviorTelemetry(entity, state, Some(behavior), _, cost, outcome, duration) = telemetry
+    {
         node.user_data.data = BehaviorData::Behavior(behavior.clone());
         node.user_data.state = Some(*state);
+        node.user_data.cost = *cost;
+        node.user_data.outcome = outcome.clone();
+        node.user_data.duration = *duration;
         node.user_data.entity = entity.clone();
     }
 
     // Get node children
-    let node_children: Vec<NodeId> = node
-        .outputs
-        .iter()
-        .filter_map(|(_, output_id)| {
-            graph
-                .connections
-                .iter()
-                .find(|(input_id, rhs_output_id)| {
-                    output_id == *rhs_output_id
-                        && graph.inputs[*input_id].typ == BehaviorDataType::Flow
-                })
-                .and_then(|(input_id, _)| Some(graph.inputs[input_id].node))
-        })
-        .collect();
+    let node_children = get_flow_children(graph, node_id);
 
     // children iterators
     let mut node_children = node_children.iter();
@@ -290,6 +673,10 @@ where
     Ok(())
 }
 
+// TODO: Make these dynamic
+const NODE_WIDTH: f32 = 200.0;
+const NODE_HEIGHT: f32 = 200.0;
+
 pub fn layout_graph<T>(
     editor: &mut BehaviorEditorState<T>,
     node_id: Option<NodeId>,
@@ -298,40 +685,21 @@ pub fn layout_graph<T>(
 ) where
     T: BehaviorFactory,
 {
-    // TODO: Make these dynamic
-    const NODE_WIDTH: f32 = 200.0;
-    const NODE_HEIGHT: f32 = 200.0;
-
     let Some(node_id) = node_id else {
-            let root_child_id = get_root_child(&editor.graph);
-            if let Some(root_child_id) = root_child_id {
-                layout_graph(editor, Some(root_child_id), 1, child);
-            } else {
-                error!("No root child");
-            }
-            return;
-        };
+        let root_child_id = get_root_child(&editor.graph);
+        if let Some(root_child_id) = root_child_id {
+            layout_graph(editor, Some(root_child_id), 1, child);
+        } else {
+            error!("No root child");
+        }
+        return;
+    };
 
     editor.node_positions[node_id] =
         egui::pos2((depth as f32) * NODE_WIDTH, (*child as f32) * NODE_HEIGHT);
 
     // Get node children
-    let graph = &mut editor.graph;
-    let node: &mut egui_node_graph::Node<BehaviorNodeData<T>> = &mut graph.nodes[node_id];
-    let node_children: Vec<NodeId> = node
-        .outputs
-        .iter()
-        .filter_map(|(_, output_id)| {
-            graph
-                .connections
-                .iter()
-                .find(|(input_id, rhs_output_id)| {
-                    output_id == *rhs_output_id
-                        && graph.inputs[*input_id].typ == BehaviorDataType::Flow
-                })
-                .and_then(|(input_id, _)| Some(graph.inputs[input_id].node))
-        })
-        .collect();
+    let node_children = get_flow_children(&editor.graph, node_id);
 
     // Zip and iterate over children
     let node_children = node_children.iter().enumerate();
@@ -343,12 +711,97 @@ pub fn layout_graph<T>(
     }
 }
 
-// For use with world.get_entity_component_reflect
-fn _components_of_entity(
-    world: &mut World,
+// Auto layout graph if node positions are too tight, e.g. a behavior without positions
+pub(super) fn layout_graph_if_tight<T: BehaviorFactory>(editor: &mut BehaviorEditorState<T>) {
+    // calculate min and max positions
+    let mut min_pos = egui::pos2(f32::MAX, f32::MAX);
+    let mut max_pos = egui::pos2(f32::MIN, f32::MIN);
+    editor.node_positions.iter().for_each(|(_, pos)| {
+        min_pos = egui::pos2(pos.x.min(min_pos.x), pos.y.min(min_pos.y));
+        max_pos = egui::pos2(pos.x.max(max_pos.x), pos.y.max(max_pos.y));
+    });
+
+    let size_pos = (max_pos - min_pos).length_sq();
+    if size_pos < 1000.0 {
+        let mut child = 0;
+        layout_graph(editor, None, 0, &mut child);
+    }
+}
+
+const MINIMAP_SIZE: egui::Vec2 = egui::vec2(160.0, 100.0);
+const MINIMAP_MARGIN: f32 = 8.0;
+const MINIMAP_NODE_SIZE: egui::Vec2 = egui::vec2(120.0, 80.0);
+
+// Draw a minimap of the whole graph in the bottom right corner of the editor,
+// clicking or dragging on it recenters the editor view
+pub(super) fn minimap<T>(
+    ui: &mut egui::Ui,
+    editor: &mut BehaviorEditorState<T>,
+    editor_rect: egui::Rect,
+) where
+    T: BehaviorFactory,
+{
+    let minimap_rect = egui::Rect::from_min_size(
+        editor_rect.max - MINIMAP_SIZE - egui::vec2(MINIMAP_MARGIN, MINIMAP_MARGIN),
+        MINIMAP_SIZE,
+    );
+
+    // Graph space covered by nodes and the current view
+    let view_rect = egui::Rect::from_min_size((-editor.pan_zoom.pan).to_pos2(), editor_rect.size());
+    let graph_rect = editor.node_positions.values().fold(view_rect, |rect, pos| {
+        rect.union(egui::Rect::from_min_size(*pos, MINIMAP_NODE_SIZE))
+    });
+    let scale = (MINIMAP_SIZE.x / graph_rect.width()).min(MINIMAP_SIZE.y / graph_rect.height());
+    let to_minimap = |pos: egui::Pos2| minimap_rect.min + (pos - graph_rect.min) * scale;
+
+    let painter = ui.painter_at(minimap_rect);
+    painter.rect_filled(
+        minimap_rect,
+        2.0,
+        egui::Color32::from_rgba_unmultiplied(0, 0, 0, 160),
+    );
+    for (node_id, pos) in editor.node_positions.iter() {
+        let state = editor
+            .graph
+            .nodes
+            .get(node_id)
+            .and_then(|node| node.user_data.state);
+        let color = match state {
+            Some(BehaviorState::Cursor) | Some(BehaviorState::Running) => egui::Color32::GREEN,
+            Some(BehaviorState::Success) => egui::Color32::DARK_GREEN,
+            Some(BehaviorState::Failure) => egui::Color32::DARK_RED,
+            _ => egui::Color32::GRAY,
+        };
+        let node_rect =
+            egui::Rect::from_min_max(to_minimap(*pos), to_minimap(*pos + MINIMAP_NODE_SIZE));
+        painter.rect_filled(node_rect, 0.0, color);
+    }
+    painter.rect_stroke(
+        egui::Rect::from_min_max(to_minimap(view_rect.min), to_minimap(view_rect.max)),
+        0.0,
+        egui::Stroke::new(1.0, egui::Color32::WHITE),
+    );
+
+    // Recenter the view on the clicked point
+    let response = ui.interact(
+        minimap_rect,
+        ui.id().with("Behavior Minimap"),
+        egui::Sense::click_and_drag(),
+    );
+    if response.clicked() || response.dragged() {
+        if let Some(pointer) = response.interact_pointer_pos() {
+            let target = graph_rect.min + (pointer - minimap_rect.min) / scale;
+            editor.pan_zoom.pan = editor_rect.size() / 2.0 - target.to_vec2();
+        }
+    }
+}
+
+// For use with world.get_entity_component_reflect, None if the entity does not exist
+pub(super) fn components_of_entity(
+    world: &World,
     entity: Entity,
-) -> Vec<(String, bevy::ecs::component::ComponentId, core::any::TypeId)> {
-    let entity_ref = world.get_entity(entity).unwrap();
+) -> Option<Vec<(String, bevy::ecs::component::ComponentId, core::any::TypeId)>> {
+    let entity_ref = world.get_entity(entity)?;
     let archetype = entity_ref.archetype();
     let mut components: Vec<_> = archetype
         .components()
@@ -359,7 +812,7 @@ fn _components_of_entity(
         })
         .collect();
     components.sort_by(|(name_a, ..), (name_b, ..)| name_a.cmp(name_b));
-    components
+    Some(components)
 }
 
 pub fn close_button(ui: &mut egui::Ui, node_rect: egui::Rect) -> egui::Response {
@@ -433,3 +886,253 @@ pub(super) fn get_label_from_stop_option(
         StopOption::Remove => format!("Remove{}", current_label).into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{TestBehavior, TestBehaviorAttributes};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn load(behavior: &Behavior<TestBehavior>) -> BehaviorEditorState<TestBehavior> {
+        behavior_graph(&mut BehaviorGraphState::default(), behavior)
+    }
+
+    fn save(editor: &BehaviorEditorState<TestBehavior>) -> String {
+        let behavior = graph_to_behavior(editor, None).unwrap();
+        ron::ser::to_string_pretty(&behavior, ron::ser::PrettyConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn save_is_deterministic() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(
+            r#"
+            (
+                "Sequence",
+                Sequencer(()),
+                [
+                    ("First", Debug(()), [], (pos: (100.0, 0.0))),
+                    ("Second", Wait(()), [], (pos: (100.0, 50.0))),
+                    (
+                        "Third",
+                        Selector(()),
+                        [
+                            ("Nested", Debug(()), [], (pos: (200.0, 100.0))),
+                        ],
+                        (pos: (100.0, 100.0)),
+                    ),
+                ],
+            )
+            "#,
+        )
+        .unwrap();
+
+        let editor = load(&behavior);
+        let first = save(&editor);
+        let second = save(&editor);
+        assert_eq!(first.as_bytes(), second.as_bytes());
+
+        // Reloading a saved document saves the same bytes
+        let reloaded = ron::from_str::<Behavior<TestBehavior>>(&first).unwrap();
+        let third = save(&load(&reloaded));
+        assert_eq!(first.as_bytes(), third.as_bytes());
+    }
+
+    // Build a random behavior, respecting how many children each node type can have
+    fn random_behavior(
+        rng: &mut StdRng,
+        depth: usize,
+        count: &mut usize,
+    ) -> Behavior<TestBehavior> {
+        *count += 1;
+        let name = format!("Node {}", count);
+        let attrs = TestBehaviorAttributes {
+            pos: Vec2::new(rng.gen_range(0..100) as f32, rng.gen_range(0..100) as f32) * 10.0,
+        };
+        let kind = if depth == 0 { 0 } else { rng.gen_range(0..5) };
+        let (data, children) = match kind {
+            0 => (TestBehavior::Debug(Default::default()), 0),
+            1 => (TestBehavior::Wait(Default::default()), 0),
+            2 => (TestBehavior::Inverter(Default::default()), 1),
+            3 => (TestBehavior::Sequencer(Default::default()), 4),
+            _ => (TestBehavior::Selector(Default::default()), 4),
+        };
+        let children = rng.gen_range(0..=children);
+        let nodes = (0..children)
+            .map(|_| random_behavior(rng, depth - 1, count))
+            .collect();
+        Behavior::new(name, data, attrs, nodes)
+    }
+
+    #[test]
+    fn graph_round_trip_is_lossless() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let behavior = random_behavior(&mut rng, 4, &mut 0);
+            let expected =
+                ron::ser::to_string_pretty(&behavior, ron::ser::PrettyConfig::default()).unwrap();
+            assert_eq!(save(&load(&behavior)), expected);
+        }
+    }
+
+    #[test]
+    fn graph_update_requires_same_shape() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(
+            r#"("Sequence", Sequencer(()), [("First", Debug(())), ("Second", Debug(()))])"#,
+        )
+        .unwrap();
+        let mut editor = load(&behavior);
+
+        // Same shape, data is updated in order
+        let updated = ron::from_str::<Behavior<TestBehavior>>(
+            r#"("Sequence", Sequencer(()), [("First", Wait(())), ("Second", Debug(()))])"#,
+        )
+        .unwrap();
+        assert!(behavior_to_graph(&mut editor, None, &updated).is_ok());
+        let saved = graph_to_behavior(&editor, None).unwrap();
+        assert!(matches!(saved.nodes()[0].data(), TestBehavior::Wait(_)));
+        assert!(matches!(saved.nodes()[1].data(), TestBehavior::Debug(_)));
+
+        // Different shape is an error, not a partial update
+        let shorter = ron::from_str::<Behavior<TestBehavior>>(
+            r#"("Sequence", Sequencer(()), [("First", Debug(()))])"#,
+        )
+        .unwrap();
+        assert!(behavior_to_graph(&mut editor, None, &shorter).is_err());
+    }
+
+    fn node(editor: &BehaviorEditorState<TestBehavior>, name: &str) -> NodeId {
+        editor
+            .graph
+            .nodes
+            .iter()
+            .find(|(_, node)| node.label == name)
+            .map(|(node_id, _)| node_id)
+            .unwrap()
+    }
+
+    fn add_node(
+        editor: &mut BehaviorEditorState<TestBehavior>,
+        name: &str,
+        template: BehaviorNodeTemplate<TestBehavior>,
+    ) -> NodeId {
+        let data = match &template {
+            BehaviorNodeTemplate::Root => BehaviorData::Root,
+            BehaviorNodeTemplate::Behavior(behavior) => BehaviorData::Behavior(behavior.clone()),
+        };
+        let node_data = BehaviorNodeData {
+            data,
+            state: None,
+            entity: None,
+            cost: None,
+            duration: 0.0,
+            outcome: None,
+        };
+        editor
+            .graph
+            .add_node(name.into(), node_data, |graph, node_id| {
+                template.build_node(graph, &mut BehaviorGraphState::default(), node_id)
+            })
+    }
+
+    #[test]
+    fn validate_valid_graph() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(
+            r#"
+            (
+                "Sequence",
+                Sequencer(()),
+                [
+                    ("Invert", Inverter(()), [("First", Debug(()))]),
+                    ("Second", Debug(())),
+                ],
+            )
+            "#,
+        )
+        .unwrap();
+        assert!(validate_graph(&load(&behavior).graph).is_empty());
+    }
+
+    #[test]
+    fn validate_cycle() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(
+            r#"("Outer", Sequencer(()), [("Inner", Sequencer(()), [("First", Debug(()))])])"#,
+        )
+        .unwrap();
+        let mut editor = load(&behavior);
+        let outer = node(&editor, "Outer");
+        let inner = node(&editor, "Inner");
+
+        // the free output of the inner sequence loops back to the outer one
+        let output = editor.graph.nodes[inner]
+            .output_ids()
+            .find(|output_id| !editor.graph.connections.values().any(|o| o == output_id))
+            .unwrap();
+        let input = editor.graph.nodes[outer].inputs[0].1;
+        editor.graph.add_connection(output, input);
+
+        let errors = validate_graph(&editor.graph);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            GraphValidationError::Cycle(node_id) if node_id == outer || node_id == inner
+        ));
+    }
+
+    #[test]
+    fn validate_multiple_roots() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(r#"("First", Debug(()))"#).unwrap();
+        let mut editor = load(&behavior);
+        let root = add_node(&mut editor, "Root", BehaviorNodeTemplate::Root);
+
+        let errors = validate_graph(&editor.graph);
+        assert_eq!(errors.len(), 1);
+        let GraphValidationError::MultipleRoots(roots) = &errors[0] else {
+            panic!("expected multiple roots, got {:?}", errors);
+        };
+        assert_eq!(roots.len(), 2);
+        assert!(roots.contains(&root));
+    }
+
+    #[test]
+    fn validate_multiple_children() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(
+            r#"("Invert", Inverter(()), [("First", Debug(()))])"#,
+        )
+        .unwrap();
+        let mut editor = load(&behavior);
+        let invert = node(&editor, "Invert");
+        let second = add_node(
+            &mut editor,
+            "Second",
+            BehaviorNodeTemplate::Behavior(ron::from_str("Debug(())").unwrap()),
+        );
+
+        // the decorator output also leads to a second child, which would be dropped
+        let output = editor.graph.nodes[invert].outputs[0].1;
+        let input = editor.graph.nodes[second].inputs[0].1;
+        editor.graph.add_connection(output, input);
+
+        assert_eq!(
+            validate_graph(&editor.graph),
+            vec![GraphValidationError::MultipleChildren(invert)]
+        );
+    }
+
+    #[test]
+    fn switch_index_grows_outputs() {
+        let behavior = ron::from_str::<Behavior<TestBehavior>>(
+            r#"("Route", Switch((index: (prop: Value(3)))), [("First", Debug(()))])"#,
+        )
+        .unwrap();
+        let mut editor = load(&behavior);
+        let route = node(&editor, "Route");
+        assert_eq!(editor.graph.nodes[route].outputs.len(), 2);
+
+        // an output for every index up to the selected one
+        grow_outputs(&mut editor.graph, route);
+        assert_eq!(editor.graph.nodes[route].outputs.len(), 4);
+        grow_outputs(&mut editor.graph, route);
+        assert_eq!(editor.graph.nodes[route].outputs.len(), 4);
+        assert_eq!(get_flow_children(&editor.graph, route).len(), 1);
+    }
+}
@@ -1,16 +1,20 @@
 use crate::{
     inspector::{
+        clipboard::BehaviorClipboard,
         graph::{
             BehaviorData, BehaviorDataType, BehaviorEditorState, BehaviorGraphState,
             BehaviorNodeTemplates, BehaviorResponse,
         },
-        utils, BehaviorInspectable, BehaviorInspector, BehaviorInspectorState,
+        group::{self, BehaviorGroup},
+        undo::BehaviorEdit,
+        utils, BehaviorDefaults, BehaviorInspectable, BehaviorInspector, BehaviorInspectorState,
+        BehaviorLayout,
     },
-    protocol::{BehaviorFileName, StartOption, StopOption},
+    protocol::{BehaviorFileName, BehaviorState, StartOption, StopOption},
     BehaviorFactory, BehaviorType,
 };
-use bevy::{prelude::*, window::PrimaryWindow};
-use egui_node_graph::NodeResponse;
+use bevy::{prelude::*, utils::HashMap, window::PrimaryWindow};
+use egui_node_graph::{NodeId, NodeResponse};
 use simula_inspector::egui;
 
 pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
@@ -23,12 +27,17 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
         .resource_mut::<BehaviorInspector<T>>()
         .selected
         .clone();
-    let Some(selected_behavior) = selected_behavior else { return;};
+    let Some(selected_behavior) = selected_behavior else {
+        return;
+    };
     let behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
-    let Some((file_name, inspector_item_state, entity))
-        = behavior_inspector.behaviors
+    let Some((file_name, inspector_item_state, entity)) = behavior_inspector
+        .behaviors
         .get(&selected_behavior)
-        .and_then(|item| Some((item.name.clone(), item.state.clone(), item.entity))) else { return;};
+        .and_then(|item| Some((item.name.clone(), item.state.clone(), item.entity)))
+    else {
+        return;
+    };
 
     match inspector_item_state {
         BehaviorInspectorState::Editing => {}
@@ -41,7 +50,9 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
         BehaviorInspectorState::Stopping(_) => {}
         _ => return,
     }
-    let Some(entity) = entity else { return;};
+    let Some(entity) = entity else {
+        return;
+    };
 
     let mut behavior_graphs = world.query::<(
         Entity,
@@ -50,11 +61,25 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
         &mut BehaviorEditorState<T>,
     )>();
 
+    // new nodes start with the project defaults
+    let templates = BehaviorNodeTemplates::<T> {
+        kinds: world.resource::<BehaviorDefaults<T>>().list(),
+    };
+
+    let read_only = world
+        .resource::<BehaviorInspector<T>>()
+        .behaviors
+        .get(&selected_behavior)
+        .map_or(false, |item| item.read_only);
     if let Ok((_, _, _graph_state, mut editor_state)) = behavior_graphs.get_mut(world, entity) {
         editor_state.editing = false;
+        if read_only {
+            // no node finder, it would create nodes
+            editor_state.node_finder = None;
+        }
         match inspector_item_state {
             BehaviorInspectorState::Editing => {
-                editor_state.editing = true;
+                editor_state.editing = !read_only;
             }
             BehaviorInspectorState::Save => {}
             BehaviorInspectorState::Saving(_) => {}
@@ -73,398 +98,829 @@ pub fn ui<T: BehaviorFactory + BehaviorInspectable>(
     let default_size = egui::vec2(window.width() * 0.7, window.height() * 0.7);
 
     let mut reset_graph_layout = false;
+    let mut add_group = false;
+    let mut show_timeline = false;
+    let mut show_frame = false;
 
     let mut open = true;
     let mut window_name = format!("{}", *file_name);
-    egui::Window::new(&format!("BHI:[{}]", *selected_behavior))
+    let restore_window = world
+        .resource_mut::<BehaviorInspector<T>>()
+        .restore_window
+        .take();
+    // copied nodes are shared by all behaviors, put aside while drawing
+    let mut clipboard = world
+        .resource_mut::<BehaviorInspector<T>>()
+        .clipboard
+        .take();
+    let mut window = egui::Window::new(&format!("BHI:[{}]", *selected_behavior))
         .id(T::TYPE_UUID.to_string().into())
-        .default_size(default_size)
+        .default_size(restore_window.map_or(default_size, |rect| rect.size()))
         .title_bar(false)
         .resizable(true)
         .frame(
             egui::Frame::none()
                 .fill(egui::Color32::from_rgba_unmultiplied(22, 20, 25, 200))
                 .inner_margin(3.0),
-        )
-        .show(context, |ui| {
-            let mut pan_reset = false;
-            let mut pan = egui::vec2(0.0, 0.0);
-            context.input(|i| {
-                pan = i.scroll_delta;
-            });
-            let mut pan_length = 0.0;
-            if let Ok((_, _, _graph_state, editor_state)) = behavior_graphs.get(world, entity) {
-                pan_length = editor_state.pan_zoom.pan.length_sq();
+        );
+    if let Some(rect) = restore_window {
+        window = window.current_pos(rect.min);
+    }
+    let window_response = window.show(context, |ui| {
+        let mut pan_reset = false;
+        let mut pan = egui::vec2(0.0, 0.0);
+        context.input(|i| {
+            pan = i.scroll_delta;
+        });
+        let mut pan_length = 0.0;
+        let mut idle_state = None;
+        if let Ok((_, _, _graph_state, editor_state)) = behavior_graphs.get(world, entity) {
+            pan_length = editor_state.pan_zoom.pan.length_sq();
+            idle_state = utils::find_idle_state(&editor_state.graph);
+        }
+
+        // gather node entity ids and component counts, only when debugging
+        let debug = world
+            .resource::<BehaviorInspector<T>>()
+            .behaviors
+            .get(&selected_behavior)
+            .map_or(false, |item| item.debug);
+        let mut entity_info = None;
+        if debug {
+            if let Ok((_, _, _, editor_state)) = behavior_graphs.get(world, entity) {
+                let node_entities: Vec<(NodeId, Entity)> = editor_state
+                    .graph
+                    .nodes
+                    .iter()
+                    .filter_map(|(node_id, node)| {
+                        let entity = node.user_data.entity.as_ref()?;
+                        Some((node_id, entity.to_entity()))
+                    })
+                    .collect();
+                entity_info = Some(
+                    node_entities
+                        .into_iter()
+                        .filter_map(|(node_id, entity)| {
+                            let components = utils::components_of_entity(world, entity)?;
+                            Some((node_id, (entity.index(), components.len())))
+                        })
+                        .collect::<HashMap<_, _>>(),
+                );
             }
+        }
 
-            ui.vertical(|ui| {
-                let mut behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
-                let behavior_inspector_item = behavior_inspector
-                    .behaviors
-                    .get_mut(&selected_behavior)
-                    .unwrap();
-
-                // if this behavior item has been modified in any way
-                // e.g. the graph has been edited, or renamed
-                let mut modified = behavior_inspector_item.modified;
-
-                ui.horizontal(|ui| {
-                    egui::menu::bar(ui, |ui| {
-                        if behavior_inspector_item.collapsed {
-                            if ui.add(egui::Button::new("▶").frame(false)).clicked() {
-                                behavior_inspector_item.collapsed = false;
-                            }
-                        } else {
-                            if ui.add(egui::Button::new("▼").frame(false)).clicked() {
-                                behavior_inspector_item.collapsed = true;
-                            }
+        ui.vertical(|ui| {
+            let mut behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
+            let behavior_inspector_item = behavior_inspector
+                .behaviors
+                .get_mut(&selected_behavior)
+                .unwrap();
+
+            // if this behavior item has been modified in any way
+            // e.g. the graph has been edited, or renamed
+            let mut modified = behavior_inspector_item.modified;
+
+            ui.horizontal(|ui| {
+                egui::menu::bar(ui, |ui| {
+                    if behavior_inspector_item.collapsed {
+                        if ui.add(egui::Button::new("▶").frame(false)).clicked() {
+                            behavior_inspector_item.collapsed = false;
                         }
-
-                        // add a save button
-                        let mut save_enabled = true;
-                        if !behavior_inspector_item.modified {
-                            save_enabled = false;
+                    } else {
+                        if ui.add(egui::Button::new("▼").frame(false)).clicked() {
+                            behavior_inspector_item.collapsed = true;
                         }
-                        if let BehaviorInspectorState::Saving(started) = inspector_item_state {
-                            let elapsed = elapsed - started;
-                            let waiting = if elapsed.as_millis() % 200 < 100 {
-                                "⌛"
-                            } else {
-                                "⏳"
-                            };
-                            ui.label(waiting);
-                        } else if ui
+                    }
+
+                    // add a save button
+                    let mut save_enabled = true;
+                    if !behavior_inspector_item.modified {
+                        save_enabled = false;
+                    }
+                    if let BehaviorInspectorState::Saving(started) = inspector_item_state {
+                        let elapsed = elapsed - started;
+                        let waiting = if elapsed.as_millis() % 200 < 100 {
+                            "⌛"
+                        } else {
+                            "⏳"
+                        };
+                        ui.label(waiting);
+                    } else if !behavior_inspector_item.read_only
+                        && ui
                             .add_enabled(save_enabled, egui::Button::new("💾"))
                             .clicked()
-                        {
-                            behavior_inspector_item.state = BehaviorInspectorState::Save;
-                        }
-
-                        // enable the center button if the pan is off centered
+                    {
+                        behavior_inspector_item.state = BehaviorInspectorState::Save;
+                    }
+                    if let Some(saved) = behavior_inspector_item.saved {
+                        let ago = (elapsed - saved).as_secs();
+                        ui.label(
+                            egui::RichText::new(format!("saved {}s ago", ago))
+                                .small()
+                                .color(egui::Color32::GRAY),
+                        );
+                    }
+
+                    // enable the center button if the pan is off centered
+                    if ui
+                        .add_enabled(pan_length > 1000.0, egui::Button::new("⨀").frame(true))
+                        .clicked()
+                    {
+                        pan_reset = true;
+                    }
+
+                    if !behavior_inspector_item.read_only {
+                        // lay out the tree under the root
                         if ui
-                            .add_enabled(pan_length > 1000.0, egui::Button::new("⨀").frame(true))
+                            .add_enabled(true, egui::Button::new("📐").frame(true))
+                            .on_hover_text("Auto layout")
                             .clicked()
                         {
-                            pan_reset = true;
+                            reset_graph_layout = true;
                         }
 
-                        // enable the center button if the pan is off centered
+                        // add a group box around the selected nodes
                         if ui
-                            .add_enabled(true, egui::Button::new("📐").frame(true))
+                            .add(egui::Button::new("▣"))
+                            .on_hover_text("Group selected nodes")
                             .clicked()
                         {
-                            reset_graph_layout = true;
+                            add_group = true;
                         }
-
-                        ui.add_space(20.0);
-
-                        if let BehaviorInspectorState::Editing = inspector_item_state {
-                            if ui.add(egui::Button::new("⏵").frame(true)).clicked() {
-                                behavior_inspector_item.state = BehaviorInspectorState::Start;
-                            }
-                            egui::ComboBox::from_id_source("Behavior Inspector Item StartOption")
-                                .width(250.0)
-                                .selected_text(utils::get_label_from_start_option(
-                                    &behavior_inspector_item.start_option,
-                                ))
-                                .show_ui(ui, |ui| {
-                                    let mut selectables = vec![StartOption::Spawn];
-                                    for instance in &behavior_inspector_item.instances {
-                                        selectables.push(StartOption::Attach(instance.clone()));
-                                    }
-                                    for instance in &behavior_inspector_item.orphans {
-                                        selectables.push(StartOption::Insert(instance.clone()));
-                                    }
-                                    for selectable in &selectables {
-                                        if ui
-                                            .selectable_label(
-                                                selectable == &behavior_inspector_item.start_option,
-                                                utils::get_label_from_start_option(selectable),
-                                            )
-                                            .clicked()
-                                        {
-                                            match selectable {
-                                                StartOption::Spawn => {
-                                                    behavior_inspector_item.stop_option =
-                                                        StopOption::Despawn
-                                                }
-                                                StartOption::Attach(_) => {
-                                                    behavior_inspector_item.stop_option =
-                                                        StopOption::Detach
-                                                }
-                                                StartOption::Insert(_) => {
-                                                    behavior_inspector_item.stop_option =
-                                                        StopOption::Remove
-                                                }
+                    }
+
+                    // dim nodes outside of the selected node branch
+                    if ui
+                        .add(egui::SelectableLabel::new(
+                            behavior_inspector_item.isolate,
+                            "◎",
+                        ))
+                        .on_hover_text("Isolate selected branch")
+                        .clicked()
+                    {
+                        behavior_inspector_item.isolate = !behavior_inspector_item.isolate;
+                    }
+
+                    if ui
+                        .add(egui::SelectableLabel::new(
+                            behavior_inspector_item.minimap,
+                            "🗺",
+                        ))
+                        .on_hover_text("Minimap")
+                        .clicked()
+                    {
+                        behavior_inspector_item.minimap = !behavior_inspector_item.minimap;
+                    }
+
+                    if ui
+                        .add(egui::SelectableLabel::new(
+                            behavior_inspector_item.debug,
+                            "🐞",
+                        ))
+                        .on_hover_text("Show node entities")
+                        .clicked()
+                    {
+                        behavior_inspector_item.debug = !behavior_inspector_item.debug;
+                    }
+
+                    if ui
+                        .add(egui::SelectableLabel::new(
+                            behavior_inspector_item.profile,
+                            "🔥",
+                        ))
+                        .on_hover_text("Profile node cost")
+                        .clicked()
+                    {
+                        behavior_inspector_item.profile = !behavior_inspector_item.profile;
+                    }
+
+                    if ui
+                        .add(egui::SelectableLabel::new(
+                            behavior_inspector_item.timing,
+                            "⏱",
+                        ))
+                        .on_hover_text("Time running nodes")
+                        .clicked()
+                    {
+                        behavior_inspector_item.timing = !behavior_inspector_item.timing;
+                    }
+
+                    // presentation mode, the graph can be viewed but not edited
+                    if ui
+                        .add(egui::SelectableLabel::new(
+                            behavior_inspector_item.read_only,
+                            "🔒",
+                        ))
+                        .on_hover_text("Read only")
+                        .clicked()
+                    {
+                        behavior_inspector_item.read_only = !behavior_inspector_item.read_only;
+                    }
+
+                    if ui
+                        .add(egui::SelectableLabel::new(
+                            behavior_inspector_item.timeline,
+                            "📊",
+                        ))
+                        .on_hover_text("Timeline")
+                        .clicked()
+                    {
+                        behavior_inspector_item.timeline = !behavior_inspector_item.timeline;
+                    }
+
+                    ui.add_space(20.0);
+
+                    if let BehaviorInspectorState::Editing = inspector_item_state {
+                        if ui.add(egui::Button::new("⏵").frame(true)).clicked() {
+                            behavior_inspector_item.state = BehaviorInspectorState::Start;
+                        }
+                        egui::ComboBox::from_id_source("Behavior Inspector Item StartOption")
+                            .width(250.0)
+                            .selected_text(utils::get_label_from_start_option(
+                                &behavior_inspector_item.start_option,
+                            ))
+                            .show_ui(ui, |ui| {
+                                let mut selectables = vec![StartOption::Spawn];
+                                for instance in &behavior_inspector_item.instances {
+                                    selectables.push(StartOption::Attach(instance.clone()));
+                                }
+                                for instance in &behavior_inspector_item.orphans {
+                                    selectables.push(StartOption::Insert(instance.clone()));
+                                }
+                                for selectable in &selectables {
+                                    if ui
+                                        .selectable_label(
+                                            selectable == &behavior_inspector_item.start_option,
+                                            utils::get_label_from_start_option(selectable),
+                                        )
+                                        .clicked()
+                                    {
+                                        match selectable {
+                                            StartOption::Spawn => {
+                                                behavior_inspector_item.stop_option =
+                                                    StopOption::Despawn
+                                            }
+                                            StartOption::Attach(_) => {
+                                                behavior_inspector_item.stop_option =
+                                                    StopOption::Detach
+                                            }
+                                            StartOption::Insert(_) => {
+                                                behavior_inspector_item.stop_option =
+                                                    StopOption::Remove
                                             }
-                                            behavior_inspector_item.start_option =
-                                                selectable.clone();
-                                            behavior_inspector_item.state =
-                                                BehaviorInspectorState::Start;
                                         }
+                                        behavior_inspector_item.start_option = selectable.clone();
+                                        behavior_inspector_item.state =
+                                            BehaviorInspectorState::Start;
                                     }
-                                });
+                                }
+                            });
+                    }
+
+                    if let BehaviorInspectorState::Running = inspector_item_state {
+                        if ui.add(egui::Button::new("⏹").frame(true)).clicked() {
+                            behavior_inspector_item.state = BehaviorInspectorState::Stop;
                         }
+                        egui::ComboBox::from_id_source("Behavior Inspector Item StopOption")
+                            .width(250.0)
+                            .selected_text(utils::get_label_from_stop_option(
+                                &behavior_inspector_item.start_option,
+                                &behavior_inspector_item.stop_option,
+                            ))
+                            .show_ui(ui, |ui| {
+                                let selectables = vec![
+                                    StopOption::Despawn,
+                                    StopOption::Detach,
+                                    StopOption::Remove,
+                                ];
+                                for selectable in &selectables {
+                                    if ui
+                                        .selectable_label(
+                                            selectable == &behavior_inspector_item.stop_option,
+                                            utils::get_label_from_stop_option(
+                                                &behavior_inspector_item.start_option,
+                                                selectable,
+                                            ),
+                                        )
+                                        .clicked()
+                                    {
+                                        behavior_inspector_item.stop_option = selectable.clone();
+                                        behavior_inspector_item.state =
+                                            BehaviorInspectorState::Stop;
+                                    }
+                                }
+                            });
 
-                        if let BehaviorInspectorState::Running = inspector_item_state {
-                            if ui.add(egui::Button::new("⏹").frame(true)).clicked() {
+                        // tree completed and no cursor remains, show idle badge and re-run
+                        if let Some((idle_state, outcome)) = &idle_state {
+                            let color = match idle_state {
+                                BehaviorState::Failure => egui::Color32::DARK_RED,
+                                _ => egui::Color32::DARK_GREEN,
+                            };
+                            let badge = egui::RichText::new("■ idle")
+                                .color(egui::Color32::WHITE)
+                                .background_color(color);
+                            let badge = ui.label(badge);
+                            if let Some(outcome) = outcome {
+                                badge.on_hover_text(format!(
+                                    "{} ({})",
+                                    outcome.reason, outcome.code
+                                ));
+                            }
+                            if ui.add(egui::Button::new("⟲").frame(true)).clicked() {
+                                behavior_inspector_item.restart = true;
                                 behavior_inspector_item.state = BehaviorInspectorState::Stop;
                             }
-                            egui::ComboBox::from_id_source("Behavior Inspector Item StopOption")
-                                .width(250.0)
-                                .selected_text(utils::get_label_from_stop_option(
-                                    &behavior_inspector_item.start_option,
-                                    &behavior_inspector_item.stop_option,
-                                ))
-                                .show_ui(ui, |ui| {
-                                    let selectables = vec![
-                                        StopOption::Despawn,
-                                        StopOption::Detach,
-                                        StopOption::Remove,
-                                    ];
-                                    for selectable in &selectables {
-                                        if ui
-                                            .selectable_label(
-                                                selectable == &behavior_inspector_item.stop_option,
-                                                utils::get_label_from_stop_option(
-                                                    &behavior_inspector_item.start_option,
-                                                    selectable,
-                                                ),
-                                            )
-                                            .clicked()
-                                        {
-                                            behavior_inspector_item.stop_option =
-                                                selectable.clone();
-                                            behavior_inspector_item.state =
-                                                BehaviorInspectorState::Stop;
-                                        }
-                                    }
-                                });
                         }
 
-                        ui.style_mut().visuals.extreme_bg_color =
-                            egui::Color32::from_rgba_premultiplied(0, 0, 0, 100);
-                        if ui
-                            .add(
-                                egui::TextEdit::singleline(&mut window_name)
-                                    .desired_width(250.0)
-                                    .clip_text(false),
-                            )
-                            .changed()
-                        {
-                            behavior_inspector_item.name =
-                                BehaviorFileName(window_name.clone().into());
-                            modified = true;
-                        }
-
-                        // Space for the little cross icon
-                        ui.add_space(8.0);
-                        ui.add_space(ui.available_width() - 12.0);
-                        if utils::close_button(ui, ui.available_rect_before_wrap()).clicked() {
-                            open = false;
+                        // stepped back to a past frame, click to go live
+                        if behavior_inspector_item.history.is_past() {
+                            let badge = egui::RichText::new("⏪ past")
+                                .color(egui::Color32::WHITE)
+                                .background_color(egui::Color32::from_rgb(140, 80, 0));
+                            if ui
+                                .add(egui::Label::new(badge).sense(egui::Sense::click()))
+                                .on_hover_text("Showing a past frame, click to go live")
+                                .clicked()
+                            {
+                                behavior_inspector_item.history.viewing = None;
+                                show_frame = true;
+                            }
                         }
-                    });
+                    }
+
+                    ui.style_mut().visuals.extreme_bg_color =
+                        egui::Color32::from_rgba_premultiplied(0, 0, 0, 100);
+                    if behavior_inspector_item.read_only {
+                        ui.label(window_name.as_str());
+                    } else if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut window_name)
+                                .desired_width(250.0)
+                                .clip_text(false),
+                        )
+                        .changed()
+                    {
+                        behavior_inspector_item.name = BehaviorFileName(window_name.clone().into());
+                        modified = true;
+                    }
+
+                    // Space for the little cross icon
+                    ui.add_space(8.0);
+                    ui.add_space(ui.available_width() - 12.0);
+                    if utils::close_button(ui, ui.available_rect_before_wrap()).clicked() {
+                        open = false;
+                    }
                 });
+            });
 
-                if !behavior_inspector_item.collapsed {
-                    egui::Frame::none()
-                        .fill(egui::Color32::from_rgba_unmultiplied(42, 40, 45, 140))
-                        .stroke(egui::Stroke::NONE)
-                        .inner_margin(egui::Margin {
-                            left: 10.0,
-                            right: 10.0,
-                            top: 10.0,
-                            bottom: 10.0,
-                        })
-                        .outer_margin(egui::Margin {
-                            left: -3.0,
-                            right: -3.0,
-                            top: 0.0,
-                            bottom: -3.0,
-                        })
-                        .show(ui, |ui| {
-                            let (mut graph_state, mut editor_state) =
-                                if let Ok((_, _, graph_state, editor_state)) =
-                                    behavior_graphs.get_mut(world, entity)
-                                {
-                                    (graph_state, editor_state)
-                                } else {
-                                    return;
-                                };
-
-                            // keep root node locked
-                            if let Some(root_node) = graph_state.root_node {
-                                let position =
-                                    editor_state.node_positions.get_mut(root_node).unwrap();
-                                *position = egui::pos2(0.0, 0.0);
+            // why the graph did not start
+            for error in &behavior_inspector_item.errors {
+                ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {}", error));
+            }
+
+            show_timeline = behavior_inspector_item.timeline;
+            let isolate = behavior_inspector_item.isolate;
+            let minimap = behavior_inspector_item.minimap;
+            let read_only = behavior_inspector_item.read_only;
+            let profile = behavior_inspector_item.profile;
+            if !behavior_inspector_item.collapsed {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(42, 40, 45, 140))
+                    .stroke(egui::Stroke::NONE)
+                    .inner_margin(egui::Margin {
+                        left: 10.0,
+                        right: 10.0,
+                        top: 10.0,
+                        bottom: 10.0,
+                    })
+                    .outer_margin(egui::Margin {
+                        left: -3.0,
+                        right: -3.0,
+                        top: 0.0,
+                        bottom: -3.0,
+                    })
+                    .show(ui, |ui| {
+                        let (mut graph_state, mut editor_state) =
+                            if let Ok((_, _, graph_state, editor_state)) =
+                                behavior_graphs.get_mut(world, entity)
+                            {
+                                (graph_state, editor_state)
                             } else {
-                                for (nodeid, node) in &editor_state.graph.nodes {
-                                    if let BehaviorData::Root = &node.user_data.data {
-                                        graph_state.root_node = Some(nodeid);
-                                        break;
-                                    }
+                                return;
+                            };
+
+                        // keep root node locked
+                        if let Some(root_node) = graph_state.root_node {
+                            let position = editor_state.node_positions.get_mut(root_node).unwrap();
+                            *position = egui::pos2(0.0, 0.0);
+                        } else {
+                            for (nodeid, node) in &editor_state.graph.nodes {
+                                if let BehaviorData::Root = &node.user_data.data {
+                                    graph_state.root_node = Some(nodeid);
+                                    break;
                                 }
                             }
+                        }
 
-                            // handle pan
-                            let scroll_rect = ui.available_rect_before_wrap();
-                            if ui.rect_contains_pointer(scroll_rect) {
-                                editor_state.pan_zoom.pan += pan;
-                            }
-                            if pan_reset {
-                                editor_state.pan_zoom.pan = egui::vec2(0.0, 0.0);
-                            }
+                        // handle pan
+                        let scroll_rect = ui.available_rect_before_wrap();
+                        if ui.rect_contains_pointer(scroll_rect) {
+                            editor_state.pan_zoom.pan += pan;
+                        }
+                        if pan_reset {
+                            editor_state.pan_zoom.pan = egui::vec2(0.0, 0.0);
+                        }
 
-                            // keep graph inside scroll rect
-                            let mut clip_rect = ui.available_rect_before_wrap();
-                            clip_rect.min.x -= 9.0;
-                            clip_rect.max.x += 9.0;
-                            clip_rect.min.y -= 9.0;
-                            clip_rect.max.y += 9.0;
-                            ui.set_clip_rect(clip_rect);
-
-                            // draw node graph
-                            let graph_response = ui
-                                .push_id(T::TYPE_UUID, |ui| {
-                                    editor_state.draw_graph_editor(
-                                        ui,
-                                        BehaviorNodeTemplates::<T>::default(),
-                                        &mut graph_state,
-                                        Vec::default(),
+                        // keep graph inside scroll rect
+                        let mut clip_rect = ui.available_rect_before_wrap();
+                        clip_rect.min.x -= 9.0;
+                        clip_rect.max.x += 9.0;
+                        clip_rect.min.y -= 9.0;
+                        clip_rect.max.y += 9.0;
+                        ui.set_clip_rect(clip_rect);
+
+                        // isolate the branch of the selected node, if any
+                        graph_state.isolated = match graph_state.active_node {
+                            Some(node_id)
+                                if isolate && editor_state.graph.nodes.contains_key(node_id) =>
+                            {
+                                Some(utils::get_branch(&editor_state.graph, node_id))
+                            }
+                            _ => None,
+                        };
+
+                        graph_state.entity_info = entity_info.clone();
+
+                        // color nodes by cost relative to the most expensive node
+                        graph_state.heat = if profile {
+                            let costs = editor_state
+                                .graph
+                                .nodes
+                                .iter()
+                                .filter_map(|(node_id, node)| Some((node_id, node.user_data.cost?)))
+                                .collect::<Vec<_>>();
+                            let max = costs.iter().fold(0.0, |max: f32, (_, cost)| max.max(*cost));
+                            (max > 0.0).then(|| {
+                                costs
+                                    .into_iter()
+                                    .map(|(node_id, cost)| (node_id, cost / max))
+                                    .collect()
+                            })
+                        } else {
+                            None
+                        };
+
+                        // draw groups behind the node graph
+                        let editor_rect = ui.max_rect();
+                        let offset = editor_rect.min.to_vec2() + editor_state.pan_zoom.pan;
+                        if add_group {
+                            let positions = editor_state
+                                .selected_nodes
+                                .iter()
+                                .filter_map(|node_id| editor_state.node_positions.get(*node_id))
+                                .copied();
+                            let group =
+                                BehaviorGroup::around("Group", positions).unwrap_or_else(|| {
+                                    BehaviorGroup::new(
+                                        "Group",
+                                        egui::Rect::from_center_size(
+                                            editor_rect.center() - offset,
+                                            egui::vec2(300.0, 200.0),
+                                        ),
                                     )
-                                })
-                                .inner;
-
-                            for response in graph_response.node_responses {
-                                trace!("response: {:?}", response);
-                                match response {
-                                    NodeResponse::CreatedNode(_) => {
-                                        modified = true;
-                                    }
-                                    NodeResponse::DeleteNodeFull {
-                                        node_id: _node_id,
-                                        node: _node,
-                                    } => {
-                                        modified = true;
-                                    }
-                                    NodeResponse::SelectNode(node_id) => {
-                                        graph_state.active_node = Some(node_id);
-                                    }
-                                    NodeResponse::DeselectNode => {
-                                        graph_state.active_node = None;
+                                });
+                            graph_state.groups.push(group);
+                        }
+                        graph_state.collapsed =
+                            group::collapsed_nodes(&graph_state.groups, &editor_state);
+                        group::paint_groups(ui, &graph_state.groups, offset);
+
+                        // deleted nodes cannot be put back with the same ids, so keep the
+                        // graph as it was before drawing, to undo a delete
+                        let mut before = (!read_only).then(|| BehaviorEdit::restore(&editor_state));
+
+                        // draw node graph
+                        let graph_response = ui
+                            .push_id(T::TYPE_UUID, |ui| {
+                                editor_state.draw_graph_editor(
+                                    ui,
+                                    templates,
+                                    &mut graph_state,
+                                    Vec::default(),
+                                )
+                            })
+                            .inner;
+
+                        if !read_only {
+                            group::interact_groups(
+                                ui,
+                                &mut graph_state.groups,
+                                &mut editor_state,
+                                offset,
+                            );
+                        }
+
+                        if minimap {
+                            utils::minimap(ui, &mut editor_state, editor_rect);
+                        }
+
+                        for response in graph_response.node_responses {
+                            trace!("response: {:?}", response);
+                            match response {
+                                NodeResponse::CreatedNode(node_id) => {
+                                    modified = true;
+                                    editor_state.1.push(BehaviorEdit::RemoveNode { node_id });
+                                }
+                                NodeResponse::DeleteNodeFull {
+                                    node_id: _node_id,
+                                    node: _node,
+                                } => {
+                                    modified = true;
+                                    if let Some(before) = before.take() {
+                                        editor_state.1.push(before);
                                     }
-                                    NodeResponse::ConnectEventEnded {
-                                        output: output_id,
-                                        input: input_id,
-                                    } => {
-                                        modified = true;
-
-                                        // Check if output is already connected, and if so, remove the previous connection
-                                        let mut removes = vec![];
-                                        for (other_input, other_output) in
-                                            editor_state.graph.connections.iter()
-                                        {
-                                            if *other_output == output_id {
-                                                if other_input != input_id {
-                                                    removes.push(other_input);
-                                                }
+                                }
+                                NodeResponse::SelectNode(node_id) => {
+                                    graph_state.active_node = Some(node_id);
+                                }
+                                NodeResponse::DeselectNode => {
+                                    graph_state.active_node = None;
+                                }
+                                NodeResponse::ConnectEventEnded {
+                                    output: output_id,
+                                    input: input_id,
+                                } => {
+                                    modified = true;
+
+                                    // Undo to the previous connection of the input, if any
+                                    let previous = match &before {
+                                        Some(BehaviorEdit::Restore { graph, .. }) => {
+                                            graph.connection(input_id)
+                                        }
+                                        _ => None,
+                                    };
+                                    editor_state.1.push(match previous {
+                                        Some(output) if output != output_id => {
+                                            BehaviorEdit::Connect {
+                                                input: input_id,
+                                                output,
                                             }
                                         }
-                                        for other_input in removes {
-                                            editor_state.graph.connections.remove(other_input);
+                                        _ => BehaviorEdit::Disconnect { input: input_id },
+                                    });
+
+                                    // Check if output is already connected, and if so, remove the previous connection
+                                    let mut removes = vec![];
+                                    for (other_input, other_output) in
+                                        editor_state.graph.connections.iter()
+                                    {
+                                        if *other_output == output_id {
+                                            if other_input != input_id {
+                                                removes.push(other_input);
+                                            }
                                         }
+                                    }
+                                    for other_input in removes {
+                                        editor_state.graph.connections.remove(other_input);
+                                        editor_state.1.push(BehaviorEdit::Connect {
+                                            input: other_input,
+                                            output: output_id,
+                                        });
+                                    }
 
-                                        // If composite type, dynamically adjust outputs of node
-                                        let node_id = editor_state.graph.outputs[output_id].node;
-                                        let node = editor_state.graph.nodes.get(node_id).unwrap();
-                                        if let BehaviorData::Behavior(behavior) =
-                                            &node.user_data.data
-                                        {
-                                            if behavior.typ() == BehaviorType::Composite {
-                                                // Get all unused outputs
-                                                let mut unused_outputs = vec![];
-                                                node.outputs.iter().for_each(|(_, output_id)| {
-                                                    let connected = editor_state
-                                                        .graph
-                                                        .connections
-                                                        .iter()
-                                                        .filter(|(_, other_output)| {
-                                                            *other_output == output_id
-                                                        })
-                                                        .count()
-                                                        > 0;
-                                                    if !connected {
-                                                        unused_outputs.push(*output_id);
-                                                    }
-                                                });
-
-                                                // If there are no unused outputs, add a new output
-                                                if unused_outputs.len() == 0 {
-                                                    editor_state.graph.add_output_param(
-                                                        node_id,
-                                                        "B".into(),
-                                                        BehaviorDataType::Flow,
-                                                    );
+                                    // If composite type, dynamically adjust outputs of node
+                                    let node_id = editor_state.graph.outputs[output_id].node;
+                                    let node = editor_state.graph.nodes.get(node_id).unwrap();
+                                    if let BehaviorData::Behavior(behavior) = &node.user_data.data {
+                                        if behavior.typ() == BehaviorType::Composite {
+                                            let min_outputs = utils::min_outputs(behavior);
+                                            let mut outputs = node.outputs.len();
+
+                                            // Get all unused outputs
+                                            let mut unused_outputs = vec![];
+                                            node.outputs.iter().for_each(|(_, output_id)| {
+                                                let connected = editor_state
+                                                    .graph
+                                                    .connections
+                                                    .iter()
+                                                    .filter(|(_, other_output)| {
+                                                        *other_output == output_id
+                                                    })
+                                                    .count()
+                                                    > 0;
+                                                if !connected {
+                                                    unused_outputs.push(*output_id);
                                                 }
+                                            });
+
+                                            // If there are no unused outputs, add a new output
+                                            if unused_outputs.len() == 0 {
+                                                editor_state.graph.add_output_param(
+                                                    node_id,
+                                                    "B".into(),
+                                                    BehaviorDataType::Flow,
+                                                );
+                                            }
 
-                                                // Remove all but one unused output
-                                                while unused_outputs.len() > 1 {
-                                                    if let Some(output_id) = unused_outputs.pop() {
-                                                        editor_state
-                                                            .graph
-                                                            .remove_output_param(output_id);
-                                                    }
+                                            // Remove all but one unused output, keeping the
+                                            // outputs a switch index needs
+                                            while unused_outputs.len() > 1 && outputs > min_outputs
+                                            {
+                                                if let Some(output_id) = unused_outputs.pop() {
+                                                    editor_state
+                                                        .graph
+                                                        .remove_output_param(output_id);
+                                                    outputs -= 1;
                                                 }
                                             }
                                         }
                                     }
-                                    NodeResponse::DisconnectEvent {
-                                        output: _output,
-                                        input: _input,
-                                    } => {
-                                        modified = true;
+                                }
+                                NodeResponse::DisconnectEvent { output, input } => {
+                                    modified = true;
+                                    editor_state.1.push(BehaviorEdit::Connect { input, output });
+                                }
+                                NodeResponse::MoveNode { node, drag_delta } if read_only => {
+                                    // undo the move, including other selected nodes
+                                    let mut moved = vec![node];
+                                    if editor_state.selected_nodes.contains(&node) {
+                                        moved.extend(
+                                            editor_state
+                                                .selected_nodes
+                                                .iter()
+                                                .filter(|other| **other != node)
+                                                .copied(),
+                                        );
                                     }
-                                    NodeResponse::User(BehaviorResponse::NodeEdited(
-                                        node_id,
-                                        data,
-                                    )) => {
-                                        modified = true;
-                                        if let Some(node) =
-                                            editor_state.graph.nodes.get_mut(node_id)
-                                        {
-                                            node.user_data.data = BehaviorData::Behavior(data);
-                                        }
+                                    for node_id in moved {
+                                        editor_state.node_positions[node_id] -= drag_delta;
                                     }
-                                    NodeResponse::User(BehaviorResponse::NameEdited(
-                                        node_id,
-                                        name,
-                                    )) => {
-                                        modified = true;
-                                        if let Some(node) =
-                                            editor_state.graph.nodes.get_mut(node_id)
-                                        {
-                                            node.label = name;
+                                }
+                                NodeResponse::User(BehaviorResponse::NodeEdited(node_id, data)) => {
+                                    modified = true;
+                                    if let Some(node) = editor_state.graph.nodes.get_mut(node_id) {
+                                        let previous = std::mem::replace(
+                                            &mut node.user_data.data,
+                                            BehaviorData::Behavior(data),
+                                        );
+                                        if let BehaviorData::Behavior(data) = previous {
+                                            editor_state
+                                                .1
+                                                .push(BehaviorEdit::Data { node_id, data });
                                         }
                                     }
-                                    _ => {}
+                                    // a switch index past its outputs grows them
+                                    utils::grow_outputs(&mut editor_state.graph, node_id);
+                                }
+                                NodeResponse::User(BehaviorResponse::NameEdited(node_id, name)) => {
+                                    modified = true;
+                                    if let Some(node) = editor_state.graph.nodes.get_mut(node_id) {
+                                        let name = std::mem::replace(&mut node.label, name);
+                                        editor_state.1.push(BehaviorEdit::Rename { node_id, name });
+                                    }
                                 }
+                                _ => {}
                             }
+                        }
+                        editor_state.1.commit();
+
+                        // undo with ctrl+z, redo with ctrl+shift+z
+                        if !read_only {
+                            let (undo, redo) = ui.input(|i| {
+                                let z = i.modifiers.command && i.key_pressed(egui::Key::Z);
+                                (z && !i.modifiers.shift, z && i.modifiers.shift)
+                            });
+                            if (undo && editor_state.undo()) || (redo && editor_state.redo()) {
+                                modified = true;
+                            }
+                        }
+
+                        // copy selected nodes with ctrl+c, with their subtrees with
+                        // ctrl+shift+c, and paste them at the pointer with ctrl+v
+                        let (copy, paste) = ui.input(|i| {
+                            let command = i.modifiers.command;
+                            (
+                                command && i.key_pressed(egui::Key::C),
+                                command && i.key_pressed(egui::Key::V),
+                            )
                         });
-                }
+                        if copy {
+                            let subtree = ui.input(|i| i.modifiers.shift);
+                            let selected = editor_state.selected_nodes.clone();
+                            if let Some(copied) =
+                                BehaviorClipboard::copy(&editor_state, &selected, subtree)
+                            {
+                                clipboard = Some(copied);
+                            }
+                        }
+                        if let (true, false, Some(clipboard)) = (paste, read_only, &clipboard) {
+                            let position = ui
+                                .ctx()
+                                .pointer_hover_pos()
+                                .filter(|pos| editor_rect.contains(*pos))
+                                .unwrap_or(editor_rect.center());
+                            let pasted = clipboard.paste(
+                                &mut editor_state,
+                                &mut graph_state,
+                                position - offset,
+                            );
+                            for node_id in pasted {
+                                editor_state.1.push(BehaviorEdit::RemoveNode { node_id });
+                            }
+                            editor_state.1.commit();
+                            modified = true;
+                        }
+                    });
+            }
 
-                let mut behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
-                let behavior_inspector_item = behavior_inspector
-                    .behaviors
-                    .get_mut(&selected_behavior)
-                    .unwrap();
-                behavior_inspector_item.modified = modified;
-            });
+            let mut behavior_inspector = world.resource_mut::<BehaviorInspector<T>>();
+            let behavior_inspector_item = behavior_inspector
+                .behaviors
+                .get_mut(&selected_behavior)
+                .unwrap();
+            behavior_inspector_item.modified = modified;
         });
+    });
+
+    if let Some(window_response) = window_response {
+        world.resource_mut::<BehaviorInspector<T>>().window_rect =
+            Some(window_response.response.rect);
+    }
+    world.resource_mut::<BehaviorInspector<T>>().clipboard = clipboard;
+
+    // telemetry frames are stepped through while the item is put aside
+    let mut history = world
+        .resource_mut::<BehaviorInspector<T>>()
+        .behaviors
+        .get_mut(&selected_behavior)
+        .map(|item| std::mem::take(&mut item.history))
+        .unwrap_or_default();
+
+    // node states over time, in a window of its own
+    if show_timeline {
+        let mut timeline_open = true;
+        if let Ok((_, _, mut graph_state, editor_state)) = behavior_graphs.get_mut(world, entity) {
+            egui::Window::new(format!("Timeline: {}", *file_name))
+                .id(egui::Id::new((T::TYPE_UUID, "timeline")))
+                .default_size(egui::vec2(600.0, 200.0))
+                .open(&mut timeline_open)
+                .show(context, |ui| {
+                    if let BehaviorInspectorState::Running = inspector_item_state {
+                        show_frame |= history.ui(ui);
+                    }
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        graph_state.timeline.ui(ui, &editor_state.graph);
+                    });
+                });
+        }
+        if !timeline_open {
+            if let Some(item) = world
+                .resource_mut::<BehaviorInspector<T>>()
+                .behaviors
+                .get_mut(&selected_behavior)
+            {
+                item.timeline = false;
+            }
+        }
+    }
+
+    // show the frame stepped to, or the latest one when back to live
+    if show_frame {
+        if let Ok((_, _, mut graph_state, mut editor_state)) =
+            behavior_graphs.get_mut(world, entity)
+        {
+            if let Some((time, telemetry)) = history.frame() {
+                utils::show_telemetry(&mut editor_state, &mut graph_state, telemetry);
+                graph_state.timeline.marker = history.is_past().then_some(*time);
+            }
+        }
+    }
+
+    if let Some(item) = world
+        .resource_mut::<BehaviorInspector<T>>()
+        .behaviors
+        .get_mut(&selected_behavior)
+    {
+        item.history = history;
+    }
 
     if reset_graph_layout {
-        if let Ok((_, _, _graph_state, mut editor_state)) = behavior_graphs.get_mut(world, entity) {
-            let mut child = 0;
-            utils::layout_graph(&mut editor_state, None, 0, &mut child);
+        let layout = world.resource::<BehaviorLayout>().clone();
+        if let Ok((_, _, graph_state, mut editor_state)) = behavior_graphs.get_mut(world, entity) {
+            let root = graph_state.root_node.or_else(|| {
+                editor_state
+                    .graph
+                    .nodes
+                    .iter()
+                    .find(|(_, node)| matches!(node.user_data.data, BehaviorData::Root))
+                    .map(|(node_id, _)| node_id)
+            });
+            if let Some(root) = root {
+                for (node_id, position) in layout.layout_tree(&editor_state.graph, root) {
+                    editor_state.node_positions.insert(node_id, position);
+                }
+            }
         }
     }
 
@@ -0,0 +1,169 @@
+use crate::{
+    inspector::{graph::BehaviorEditorState, BehaviorInspector, BehaviorInspectorState},
+    protocol::BehaviorFileName,
+    BehaviorFactory,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_inspector::egui;
+use std::time::Duration;
+
+/// How long to wait for the behaviors of a restored workspace to be listed
+const RESTORE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Directory where inspector workspaces are stored, one file per behavior type
+#[derive(Resource, Clone)]
+pub struct BehaviorWorkspaceDir(pub String);
+
+impl Default for BehaviorWorkspaceDir {
+    fn default() -> Self {
+        Self("assets/bht".into())
+    }
+}
+
+/// Inspector editing session, the open behaviors and how they are shown. Behaviors are
+/// referenced by file name, their trees are saved to their own files.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BehaviorWorkspace {
+    #[serde(default)]
+    pub selected: Option<BehaviorFileName>,
+    /// Behavior window position and size
+    #[serde(default)]
+    pub window: Option<[f32; 4]>,
+    #[serde(default)]
+    pub behaviors: Vec<BehaviorWorkspaceItem>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BehaviorWorkspaceItem {
+    pub name: BehaviorFileName,
+    #[serde(default)]
+    pub collapsed: bool,
+    #[serde(default)]
+    pub isolate: bool,
+    #[serde(default)]
+    pub minimap: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub pan: [f32; 2],
+}
+
+fn workspace_path<T: BehaviorFactory>(dir: &BehaviorWorkspaceDir) -> String {
+    format!("{}/{}.workspace.ron", dir.0, T::TYPE_UUID)
+}
+
+/// Capture the open behaviors, those loaded in an editor
+pub(super) fn capture<T: BehaviorFactory>(world: &mut World) -> BehaviorWorkspace {
+    let mut editor_states = world.query::<&BehaviorEditorState<T>>();
+    let behavior_inspector = world.resource::<BehaviorInspector<T>>();
+    let mut behaviors: Vec<BehaviorWorkspaceItem> = behavior_inspector
+        .behaviors
+        .values()
+        .filter_map(|item| {
+            let editor_state = editor_states.get(world, item.entity?).ok()?;
+            let pan = editor_state.pan_zoom.pan;
+            Some(BehaviorWorkspaceItem {
+                name: item.name.clone(),
+                collapsed: item.collapsed,
+                isolate: item.isolate,
+                minimap: item.minimap,
+                read_only: item.read_only,
+                pan: [pan.x, pan.y],
+            })
+        })
+        .collect();
+    behaviors.sort_by(|a, b| a.name.cmp(&b.name));
+    BehaviorWorkspace {
+        selected: behavior_inspector
+            .selected
+            .as_ref()
+            .and_then(|file_id| behavior_inspector.behaviors.get(file_id))
+            .map(|item| item.name.clone()),
+        window: behavior_inspector
+            .window_rect
+            .map(|rect| [rect.min.x, rect.min.y, rect.width(), rect.height()]),
+        behaviors,
+    }
+}
+
+pub(super) fn save<T: BehaviorFactory>(
+    dir: &BehaviorWorkspaceDir,
+    workspace: &BehaviorWorkspace,
+) -> Result<(), String> {
+    let ron = ron::ser::to_string_pretty(workspace, ron::ser::PrettyConfig::default())
+        .map_err(|err| err.to_string())?;
+    let path = workspace_path::<T>(dir);
+    std::fs::write(&path, ron).map_err(|err| err.to_string())?;
+    info!("Saved workspace: {}", path);
+    Ok(())
+}
+
+/// Load a saved workspace, None if there is none
+pub(super) fn load<T: BehaviorFactory>(
+    dir: &BehaviorWorkspaceDir,
+) -> Result<Option<BehaviorWorkspace>, String> {
+    let path = workspace_path::<T>(dir);
+    match std::fs::read_to_string(&path) {
+        Ok(ron) => ron::de::from_str(&ron)
+            .map(Some)
+            .map_err(|err| err.to_string()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Start restoring a workspace, behaviors are restored as they get listed
+pub(super) fn restore<T: BehaviorFactory>(
+    behavior_inspector: &mut BehaviorInspector<T>,
+    workspace: BehaviorWorkspace,
+    elapsed: Duration,
+) {
+    behavior_inspector.restore_window = workspace
+        .window
+        .map(|[x, y, w, h]| egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(w, h)));
+    behavior_inspector.workspace = Some((workspace, elapsed));
+}
+
+/// Restore listed behaviors of a pending workspace, giving up on missing ones after a while
+pub(super) fn update<T: BehaviorFactory>(
+    behavior_inspector: &mut BehaviorInspector<T>,
+    elapsed: Duration,
+) {
+    let Some((mut workspace, started)) = behavior_inspector.workspace.take() else {
+        return;
+    };
+
+    workspace.behaviors.retain(|workspace_item| {
+        let Some((file_id, item)) = behavior_inspector
+            .behaviors
+            .iter_mut()
+            .find(|(_, item)| item.name == workspace_item.name)
+        else {
+            return true;
+        };
+        item.collapsed = workspace_item.collapsed;
+        item.isolate = workspace_item.isolate;
+        item.minimap = workspace_item.minimap;
+        item.read_only = workspace_item.read_only;
+        item.pan = Some(egui::vec2(workspace_item.pan[0], workspace_item.pan[1]));
+        if let BehaviorInspectorState::Listing = item.state {
+            item.state = BehaviorInspectorState::Load;
+        }
+        if workspace.selected.as_ref() == Some(&workspace_item.name) {
+            behavior_inspector.selected = Some(file_id.clone());
+        }
+        false
+    });
+
+    if workspace.behaviors.is_empty() {
+        return;
+    }
+    if elapsed - started > RESTORE_TIMEOUT {
+        for workspace_item in &workspace.behaviors {
+            warn!("Workspace behavior not found: {}", *workspace_item.name);
+        }
+        return;
+    }
+    behavior_inspector.workspace = Some((workspace, started));
+}
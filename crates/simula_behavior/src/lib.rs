@@ -1,7 +1,8 @@
 use actions::*;
 use asset::{
-    behavior_document_to_asset, behavior_tree_reset, Behavior, BehaviorAsset, BehaviorAssetLoader,
-    BehaviorDocument,
+    behavior_document_reload, behavior_document_to_asset, behavior_tree_cleanup,
+    behavior_tree_hot_reload, behavior_tree_pause, behavior_tree_reset, Behavior, BehaviorAsset,
+    BehaviorAssetLoader, BehaviorDocument, BehaviorTreePaused,
 };
 use bevy::{
     ecs::{
@@ -15,7 +16,10 @@ use bevy::{
 };
 use composites::*;
 use decorators::*;
+use profiler::{profiled, BehaviorCost, BehaviorDuration, BehaviorProfiler};
+use rand::{rngs::StdRng, SeedableRng};
 use serde::{Deserialize, Serialize};
+use simula_action::ActionStage;
 use simula_script::{ScriptContext, ScriptPlugin};
 use strum::AsRefStr;
 
@@ -23,21 +27,37 @@ pub mod actions;
 pub mod asset;
 pub mod composites;
 pub mod decorators;
+pub mod dot;
+pub mod harness;
 pub mod inspector;
+pub mod lod;
+pub mod panic_safe;
+pub mod profiler;
 pub mod property;
 pub mod protocol;
 pub mod server;
+pub mod storage;
 pub mod test;
 
 pub mod prelude {
     pub use crate::actions::*;
     pub use crate::asset::{
-        Behavior, BehaviorAsset, BehaviorAssetLoader, BehaviorDocument, BehaviorTreeReset,
+        convert_document, Behavior, BehaviorAsset, BehaviorAssetLoader, BehaviorDocument,
+        BehaviorTreePaused, BehaviorTreeReset,
     };
     pub use crate::composites::*;
     pub use crate::decorators::*;
+    pub use crate::dot::behavior_to_dot;
+    pub use crate::harness::{BehaviorTestPlugin, BehaviorTestReport, BehaviorVerdict};
     pub use crate::inspector::{
-        BehaviorInspectable, BehaviorInspectorPlugin, BehaviorNodeInspectable, BehaviorUI,
+        reflect_ui, BehaviorAutoSave, BehaviorDefaults, BehaviorInspectable,
+        BehaviorInspectorPlugin, BehaviorNodeInspectable, BehaviorStyle, BehaviorStyles,
+        BehaviorUI, BehaviorWorkspace, BehaviorWorkspaceDir,
+    };
+    pub use crate::lod::BehaviorLod;
+    pub use crate::panic_safe::panic_safe;
+    pub use crate::profiler::{
+        profiled, time_running, BehaviorCost, BehaviorDuration, BehaviorProfiler,
     };
     pub use crate::property::{
         BehaviorEval, BehaviorProp, BehaviorPropEPath, BehaviorPropGeneric, BehaviorPropOption,
@@ -45,15 +65,18 @@ pub mod prelude {
     };
     pub use crate::protocol::{self};
     pub use crate::server::{
-        AssetTracker, BehaviorServerPlugin, BehaviorTracker, BehaviorTrackers, EntityTracker,
+        AssetTracker, BehaviorServerPlugin, BehaviorSource, BehaviorTelemetryRequests,
+        BehaviorTracker, BehaviorTrackers, EntityTracker,
     };
+    pub use crate::storage::{BehaviorStorage, BehaviorStorageBackend, FileStorage};
     pub use crate::{behavior_ui, behavior_ui_readonly};
     pub use crate::{
-        BehaviorChildQuery, BehaviorChildQueryFilter, BehaviorChildQueryItem, BehaviorChildren,
-        BehaviorCursor, BehaviorFactory, BehaviorFailure, BehaviorIdleQuery, BehaviorMissing,
-        BehaviorNode, BehaviorParent, BehaviorPlugin, BehaviorRunQuery, BehaviorRunning,
-        BehaviorSet, BehaviorSpec, BehaviorStarted, BehaviorSuccess, BehaviorTree,
-        BehaviorTreePlugin, BehaviorType,
+        BehaviorBlackboard, BehaviorChildQuery, BehaviorChildQueryFilter, BehaviorChildQueryItem,
+        BehaviorChildren, BehaviorCursor, BehaviorFactory, BehaviorFailure, BehaviorIdleQuery,
+        BehaviorMissing, BehaviorNode, BehaviorOutcome, BehaviorParent, BehaviorPaused,
+        BehaviorPlugin, BehaviorRng, BehaviorRunQuery, BehaviorRunning, BehaviorSet, BehaviorSpec,
+        BehaviorStarted, BehaviorStatus, BehaviorSuccess, BehaviorSuspended, BehaviorTransition,
+        BehaviorTree, BehaviorTreePlugin, BehaviorType,
     };
 }
 
@@ -62,6 +85,13 @@ pub struct BehaviorPlugin;
 impl Plugin for BehaviorPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(ScriptPlugin)
+            .init_resource::<BehaviorSpawners>()
+            .init_resource::<BehaviorComponents>()
+            .init_resource::<BehaviorProfiler>()
+            .init_resource::<BehaviorRng>()
+            .init_resource::<BehaviorInputs>()
+            .add_event::<BehaviorSignal>()
+            .add_event::<BehaviorTransition>()
             .init_asset_loader::<BehaviorAssetLoader>()
             .add_asset::<BehaviorDocument>()
             .configure_set(BehaviorSet::PostUpdate.in_base_set(CoreSet::PostUpdate))
@@ -70,10 +100,25 @@ impl Plugin for BehaviorPlugin {
                     .chain()
                     .in_set(BehaviorSet::PostUpdate),
             )
+            .add_system(profiler::time_running.in_set(BehaviorSet::PostUpdate))
+            .add_system(lod::update.in_base_set(CoreSet::PreUpdate))
+            .add_systems(
+                (
+                    input_pressed::collect::<KeyCode>,
+                    input_pressed::collect::<MouseButton>,
+                )
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(ActionStage::PreUpdate),
+            )
             .register_type::<BehaviorNode>()
+            .register_type::<BehaviorLod>()
+            .register_type::<BehaviorCost>()
+            .register_type::<BehaviorDuration>()
             .register_type::<BehaviorSuccess>()
             .register_type::<BehaviorRunning>()
+            .register_type::<BehaviorSuspended>()
             .register_type::<BehaviorFailure>()
+            .register_type::<BehaviorOutcome>()
             .register_type::<BehaviorCursor>()
             .register_type::<BehaviorParent>()
             .register_type::<BehaviorChildren>()
@@ -83,6 +128,9 @@ impl Plugin for BehaviorPlugin {
             .register_type::<Sequencer>()
             .register_type::<All>()
             .register_type::<Any>()
+            .register_type::<RandomSelector>()
+            .register_type::<Parallel>()
+            .register_type::<Switch>()
             .register_type::<Repeater>()
             .register_type::<Inverter>()
             .register_type::<Succeeder>()
@@ -91,19 +139,67 @@ impl Plugin for BehaviorPlugin {
             .register_type::<Identity>()
             .register_type::<Guard>()
             .register_type::<Timeout>()
-            .add_system(debug::run)
-            .add_system(selector::run)
-            .add_system(sequencer::run)
-            .add_system(all::run)
-            .add_system(any::run)
-            .add_system(repeater::run)
-            .add_system(inverter::run)
-            .add_system(succeeder::run)
-            .add_system(wait::run)
-            .add_system(delay::run)
-            .add_system(identity::run)
-            .add_system(guard::run)
-            .add_system(timeout::run);
+            .register_type::<Spawn>()
+            .register_type::<Despawn>()
+            .register_type::<StampNow>()
+            .register_type::<CooldownReady>()
+            .register_type::<Retry>()
+            .register_type::<RetryUntilSuccess>()
+            .register_type::<Chance>()
+            .register_type::<DistanceCheck>()
+            .register_type::<Compute>()
+            .register_type::<Overlap>()
+            .register_type::<Yield>()
+            .register_type::<AddComponent>()
+            .register_type::<RemoveComponent>()
+            .register_type::<Wander>()
+            .register_type::<Tween>()
+            .register_type::<InputPressed>()
+            .register_type::<FindNearest>()
+            .register_type::<ForEach>()
+            .register_type::<Cooldown>()
+            .register_type::<BlackboardGate>()
+            .register_type::<WaitForSignal>()
+            .register_type::<ScriptCondition>()
+            .add_systems(profiled::<Debug, _>(debug::run))
+            .add_systems(profiled::<Selector, _>(selector::run))
+            .add_systems(profiled::<Sequencer, _>(sequencer::run))
+            .add_systems(profiled::<All, _>(all::run))
+            .add_systems(profiled::<Any, _>(any::run))
+            .add_systems(profiled::<RandomSelector, _>(random_selector::run))
+            .add_systems(profiled::<Parallel, _>(parallel::run))
+            .add_systems(profiled::<Switch, _>(switch::run))
+            .add_systems(profiled::<Repeater, _>(repeater::run))
+            .add_systems(profiled::<Inverter, _>(inverter::run))
+            .add_systems(profiled::<Succeeder, _>(succeeder::run))
+            .add_systems(profiled::<Wait, _>(wait::run))
+            .add_systems(profiled::<Delay, _>(delay::run))
+            .add_systems(profiled::<Identity, _>(identity::run))
+            .add_systems(profiled::<Guard, _>(guard::run))
+            .add_systems(profiled::<Timeout, _>(timeout::run))
+            .add_systems(profiled::<Spawn, _>(spawn::run))
+            .add_system(spawn::cleanup)
+            .add_systems(profiled::<Despawn, _>(despawn::run))
+            .add_systems(profiled::<StampNow, _>(stamp_now::run))
+            .add_systems(profiled::<CooldownReady, _>(cooldown_ready::run))
+            .add_systems(profiled::<Retry, _>(retry::run))
+            .add_systems(profiled::<RetryUntilSuccess, _>(retry_until_success::run))
+            .add_systems(profiled::<Chance, _>(chance::run))
+            .add_systems(profiled::<DistanceCheck, _>(distance_check::run))
+            .add_systems(profiled::<Compute, _>(compute::run))
+            .add_systems(profiled::<Overlap, _>(overlap::run))
+            .add_systems(profiled::<Yield, _>(yield_tick::run))
+            .add_systems(profiled::<AddComponent, _>(add_component::run))
+            .add_systems(profiled::<RemoveComponent, _>(remove_component::run))
+            .add_systems(profiled::<Wander, _>(wander::run))
+            .add_systems(profiled::<Tween, _>(tween::run))
+            .add_systems(profiled::<InputPressed, _>(input_pressed::run))
+            .add_systems(profiled::<FindNearest, _>(find_nearest::run))
+            .add_systems(profiled::<ForEach, _>(for_each::run))
+            .add_systems(profiled::<Cooldown, _>(cooldown::run))
+            .add_systems(profiled::<BlackboardGate, _>(blackboard_gate::run))
+            .add_systems(profiled::<WaitForSignal, _>(wait_for_signal::run))
+            .add_systems(profiled::<ScriptCondition, _>(script_condition::run));
     }
 }
 
@@ -122,7 +218,18 @@ where
     fn build(&self, app: &mut App) {
         app.register_type::<BehaviorTree<T>>()
             .add_asset::<BehaviorAsset<T>>()
-            .add_systems((behavior_document_to_asset::<T>, behavior_tree_reset::<T>).chain());
+            .add_systems(
+                (
+                    behavior_document_to_asset::<T>,
+                    behavior_document_reload::<T>,
+                    behavior_tree_hot_reload::<T>,
+                    behavior_tree_reset::<T>,
+                )
+                    .chain(),
+            )
+            .add_system(behavior_tree_cleanup::<T>)
+            .init_resource::<BehaviorTreePaused<T>>()
+            .add_system(behavior_tree_pause::<T>.in_base_set(CoreSet::PreUpdate));
     }
 }
 
@@ -219,6 +326,13 @@ pub struct BehaviorRunning;
 #[component(storage = "SparseSet")]
 pub struct BehaviorPaused;
 
+/// A marker added to nodes of trees paused by BehaviorTreePaused, kept apart from BehaviorPaused
+/// so a global pause and level of detail don't resume each other's nodes
+#[derive(Debug, Default, Reflect, Clone, Copy, Component, PartialEq)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+pub struct BehaviorSuspended;
+
 /// A marker added to entities that want to run a behavior
 #[derive(Debug, Default, Reflect, Clone, Copy, Component, PartialEq)]
 #[reflect(Component)]
@@ -243,6 +357,45 @@ pub struct BehaviorFailure;
 #[component(storage = "SparseSet")]
 pub struct BehaviorStopped;
 
+/// Optional detail inserted along BehaviorSuccess or BehaviorFailure, telling why a behavior
+/// completed the way it did. Parents completing with the same result inherit the outcome of
+/// their last child, so the tree root outcome tells why the whole tree completed.
+#[derive(Debug, Default, Reflect, Clone, Component, PartialEq)]
+#[reflect(Component)]
+pub struct BehaviorOutcome {
+    pub code: i64,
+    pub reason: String,
+}
+
+impl BehaviorOutcome {
+    pub fn new(code: i64, reason: impl Into<String>) -> Self {
+        Self {
+            code,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn reason(reason: impl Into<String>) -> Self {
+        Self::new(0, reason)
+    }
+}
+
+/// Random number generator shared by behaviors, seed it for reproducible runs
+#[derive(Resource)]
+pub struct BehaviorRng(pub StdRng);
+
+impl Default for BehaviorRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+impl BehaviorRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
 /// A marker added to behavior node entities
 #[derive(Component, Debug, Eq, PartialEq, Reflect)]
 #[reflect(Component, MapEntities, PartialEq)]
@@ -293,7 +446,7 @@ impl MapEntities for BehaviorParent {
 pub struct BehaviorChildren(Vec<Entity>);
 
 /// A component added to identify the type of a behavior node
-#[derive(Debug, Default, PartialEq, Reflect, Clone, Component, Copy, AsRefStr)]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Reflect, Clone, Component, Copy, AsRefStr)]
 #[reflect(Component)]
 pub enum BehaviorType {
     #[default]
@@ -318,20 +471,58 @@ where
     }
 }
 
+/// Initial blackboard values of an agent, seeded into the script context created for its tree,
+/// so one behavior asset can drive many differently configured agents.
+///
+/// Seeded values are in place before the first tick, and take precedence over the default
+/// blackboard values of the tree for the same keys. Nodes writing the same keys overwrite them.
+#[derive(Debug, Default, Clone, Component)]
+pub struct BehaviorBlackboard(pub simula_script::script::Map);
+
+impl BehaviorBlackboard {
+    pub fn with(
+        mut self,
+        key: impl Into<simula_script::script::ImmutableString>,
+        value: impl Into<simula_script::script::Dynamic>,
+    ) -> Self {
+        self.0.insert(key.into(), value.into());
+        self
+    }
+}
+
 /// A component added to identify the root of a behavior tree
 #[derive(Default, Reflect, Clone, Component)]
 #[reflect(Component)]
-pub struct BehaviorTree<T: BehaviorFactory>(#[reflect(ignore)] std::marker::PhantomData<T>);
+pub struct BehaviorTree<T: BehaviorFactory> {
+    /// Rebuild the tree when its behavior asset is modified, e.g. edited on disk
+    pub hot_reload: bool,
+    #[reflect(ignore)]
+    pub phantom: std::marker::PhantomData<T>,
+}
 
 impl<T> BehaviorTree<T>
 where
     T: BehaviorFactory,
 {
+    /// A behavior tree rebuilt whenever its behavior asset is modified
+    pub fn with_hot_reload() -> Self {
+        Self {
+            hot_reload: true,
+            ..default()
+        }
+    }
+
     /// Create a script context to be used by the behavior tree
     pub fn create_script_context() -> ScriptContext {
+        Self::create_script_context_with(&BehaviorBlackboard::default())
+    }
+
+    /// Create a script context to be used by the behavior tree, seeding its blackboard
+    pub fn create_script_context_with(seed: &BehaviorBlackboard) -> ScriptContext {
         let mut scope = ScriptContext::new();
         let mut blackboard = simula_script::script::Map::new();
         blackboard.insert("state".into(), 0.into());
+        blackboard.extend(seed.0.clone());
         scope.scope.push("blackboard", blackboard);
         scope
     }
@@ -384,6 +575,7 @@ pub struct BehaviorRunQuery {
     _cursor: With<BehaviorCursor>,
     _running: With<BehaviorRunning>,
     _paused: Without<BehaviorPaused>,
+    _suspended: Without<BehaviorSuspended>,
     _failure: Without<BehaviorFailure>,
     _success: Without<BehaviorSuccess>,
 }
@@ -395,6 +587,7 @@ pub struct BehaviorIdleQuery {
     _node: With<BehaviorNode>,
     _running: With<BehaviorRunning>,
     _paused: Without<BehaviorPaused>,
+    _suspended: Without<BehaviorSuspended>,
     _failure: Without<BehaviorFailure>,
     _success: Without<BehaviorSuccess>,
 }
@@ -443,6 +636,25 @@ impl BehaviorTrace {
     }
 }
 
+/// Execution status of a behavior node, as reported by `BehaviorTransition`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BehaviorStatus {
+    Running,
+    Success,
+    Failure,
+}
+
+/// Event sent whenever a behavior node starts running, succeeds or fails, in the order the
+/// transitions happen. Subscribe to it to record or analyze trees without polling components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BehaviorTransition {
+    pub entity: Entity,
+    pub tree: Entity,
+    pub from: Option<BehaviorStatus>,
+    pub to: BehaviorStatus,
+    pub name: String,
+}
+
 /// Clear BehaviorStarted every frame
 fn clear_behavior_started(mut commands: Commands, started: Query<Entity, With<BehaviorStarted>>) {
     for entity in &mut started.iter() {
@@ -458,20 +670,24 @@ fn complete_behavior(
             Entity,
             Option<&BehaviorSuccess>,
             Option<&BehaviorFailure>,
+            Option<&BehaviorOutcome>,
             Option<&BehaviorParent>,
             Option<&BehaviorChildren>,
             &Name,
+            &BehaviorNode,
         ),
         BehaviorDoneQuery,
     >,
+    outcomes: Query<(Option<&BehaviorSuccess>, &BehaviorOutcome)>,
     parents: Query<Entity, (With<BehaviorChildren>, With<BehaviorRunning>)>,
     nodes: Query<
         (Entity, Option<&BehaviorChildren>),
         Or<(With<BehaviorCursor>, With<BehaviorRunning>)>,
     >,
     mut trace: Option<ResMut<BehaviorTrace>>,
+    mut transitions: EventWriter<BehaviorTransition>,
 ) {
-    for (entity, success, failure, parent, children, name) in &dones {
+    for (entity, success, failure, outcome, parent, children, name, node) in &dones {
         let state = if success.is_some() {
             "SUCCESS"
         } else if failure.is_some() {
@@ -489,9 +705,31 @@ fn complete_behavior(
         if let Some(trace) = trace.as_mut() {
             trace.push(format!("[{}] {} {}", entity.index(), state, name,));
         }
+        transitions.send(BehaviorTransition {
+            entity,
+            tree: node.tree,
+            from: Some(BehaviorStatus::Running),
+            to: if success.is_some() {
+                BehaviorStatus::Success
+            } else {
+                BehaviorStatus::Failure
+            },
+            name: name.to_string(),
+        });
         commands.entity(entity).remove::<BehaviorRunning>();
         commands.entity(entity).remove::<BehaviorCursor>();
 
+        // Inherit the outcome of the last child completed with the same result
+        if let (None, Some(children)) = (outcome, children) {
+            let inherited = outcomes
+                .iter_many(children.iter())
+                .filter(|(child_success, _)| child_success.is_some() == success.is_some())
+                .last();
+            if let Some((_, child_outcome)) = inherited {
+                commands.entity(entity).insert(child_outcome.clone());
+            }
+        }
+
         // Stop all children recursively
         if let Some(children) = children {
             stop_children(&mut commands, children, &nodes);
@@ -509,7 +747,16 @@ fn complete_behavior(
 /// Process ready behaviors, start them
 fn start_behavior(
     mut commands: Commands,
-    ready: Query<(Entity, Option<&BehaviorChildren>, &Name, &BehaviorCursor), BehaviorReadyQuery>,
+    ready: Query<
+        (
+            Entity,
+            Option<&BehaviorChildren>,
+            &Name,
+            &BehaviorCursor,
+            &BehaviorNode,
+        ),
+        BehaviorReadyQuery,
+    >,
     nodes: Query<
         (Entity, Option<&BehaviorChildren>),
         Or<(
@@ -520,8 +767,9 @@ fn start_behavior(
         )>,
     >,
     mut trace: Option<ResMut<BehaviorTrace>>,
+    mut transitions: EventWriter<BehaviorTransition>,
 ) {
-    for (entity, children, name, cursor) in &ready {
+    for (entity, children, name, cursor, node) in &ready {
         // Reset all children recursively
         if let Some(children) = children {
             reset_children(&mut commands, children, &nodes);
@@ -535,8 +783,18 @@ fn start_behavior(
         if let Some(trace) = trace.as_mut() {
             trace.push(format!("[{}] STARTED {}", entity.index(), name));
         }
+        transitions.send(BehaviorTransition {
+            entity,
+            tree: node.tree,
+            from: None,
+            to: BehaviorStatus::Running,
+            name: name.to_string(),
+        });
 
         commands.entity(entity).insert(BehaviorRunning);
+        if let BehaviorCursor::Delegate = cursor {
+            commands.entity(entity).remove::<BehaviorOutcome>();
+        }
 
         let starting = match cursor {
             BehaviorCursor::Delegate => true,
@@ -568,7 +826,7 @@ fn stop_children(
 }
 
 /// Reset all children nodes recursively and remove their execution states
-fn reset_children(
+pub(crate) fn reset_children(
     commands: &mut Commands,
     children: &BehaviorChildren,
     nodes: &Query<
@@ -586,6 +844,7 @@ fn reset_children(
         commands.entity(entity).remove::<BehaviorRunning>();
         commands.entity(entity).remove::<BehaviorSuccess>();
         commands.entity(entity).remove::<BehaviorFailure>();
+        commands.entity(entity).remove::<BehaviorOutcome>();
         if let Some(children) = children {
             reset_children(commands, children, nodes);
         }
@@ -0,0 +1,107 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Tick rate policy for a behavior tree, based on its agent visibility and camera distance.
+/// Visible agents tick at `near_hz`, agents out of view at `far_hz`, and agents beyond
+/// `cull_distance` from every camera are paused. A rate of zero ticks every frame.
+///
+/// Throttled nodes are paused, not stopped. Children results are kept until the parent ticks
+/// again, so no completion is lost, but time based nodes only notice time passed on their next
+/// tick, so they may complete late by up to one tick period.
+#[derive(Debug, Component, Reflect, FromReflect, Clone)]
+#[reflect(Component)]
+pub struct BehaviorLod {
+    pub near_hz: f64,
+    pub far_hz: f64,
+    pub cull_distance: f32,
+    #[reflect(ignore)]
+    pub last_tick: f64,
+}
+
+impl Default for BehaviorLod {
+    fn default() -> Self {
+        Self {
+            near_hz: 0.0,
+            far_hz: 2.0,
+            cull_distance: 100.0,
+            last_tick: 0.0,
+        }
+    }
+}
+
+impl BehaviorLod {
+    /// Tick rate for an agent, None if the agent is culled
+    pub fn rate(&self, visible: bool, distance: f32) -> Option<f64> {
+        if distance > self.cull_distance {
+            None
+        } else if visible {
+            Some(self.near_hz)
+        } else {
+            Some(self.far_hz)
+        }
+    }
+}
+
+/// Pause and resume behavior tree nodes according to their tree LOD policy
+pub fn update(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut trees: Query<(
+        &mut BehaviorLod,
+        Option<&GlobalTransform>,
+        Option<&ComputedVisibility>,
+        Option<&Children>,
+    )>,
+    cameras: Query<&GlobalTransform, With<Camera>>,
+    nodes: Query<(Option<&BehaviorChildren>, Option<&BehaviorPaused>), With<BehaviorNode>>,
+) {
+    let elapsed = time.elapsed_seconds_f64();
+    for (mut lod, transform, visibility, children) in &mut trees {
+        // Agents without a transform or camera are always near
+        let distance = transform
+            .and_then(|transform| {
+                cameras
+                    .iter()
+                    .map(|camera| camera.translation().distance(transform.translation()))
+                    .min_by(f32::total_cmp)
+            })
+            .unwrap_or_default();
+        let visible = visibility.map_or(true, |visibility| visibility.is_visible_in_view());
+
+        let tick = match lod.rate(visible, distance) {
+            None => false,
+            Some(hz) if hz <= 0.0 => true,
+            Some(hz) => elapsed - lod.last_tick >= 1.0 / hz,
+        };
+        if tick {
+            lod.last_tick = elapsed;
+        }
+
+        for child in children.iter().flat_map(|children| children.iter()) {
+            set_paused(&mut commands, &nodes, *child, !tick);
+        }
+    }
+}
+
+fn set_paused(
+    commands: &mut Commands,
+    nodes: &Query<(Option<&BehaviorChildren>, Option<&BehaviorPaused>), With<BehaviorNode>>,
+    entity: Entity,
+    paused: bool,
+) {
+    let Ok((children, is_paused)) = nodes.get(entity) else {
+        return;
+    };
+    match (paused, is_paused.is_some()) {
+        (true, false) => {
+            commands.entity(entity).insert(BehaviorPaused);
+        }
+        (false, true) => {
+            commands.entity(entity).remove::<BehaviorPaused>();
+        }
+        _ => {}
+    }
+    for child in children.iter().flat_map(|children| children.iter()) {
+        set_paused(commands, nodes, *child, paused);
+    }
+}
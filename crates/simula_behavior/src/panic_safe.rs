@@ -0,0 +1,48 @@
+use crate::prelude::*;
+use bevy::{ecs::system::BoxedSystem, prelude::*};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Run a node system of node type N so that a panic fails its running nodes, with a logged
+/// error, instead of crashing the app. Opt-in, for untrusted or in-development actions:
+///
+/// ```ignore
+/// app.add_system(panic_safe::<MyAction, _>(my_action::run));
+/// ```
+///
+/// Limitations:
+/// - The node system becomes exclusive, it no longer runs in parallel with other systems.
+/// - Only unwinding panics are caught, builds with `panic = "abort"`, like wasm, still abort.
+/// - The panic hook still runs, so the panic message is printed as usual.
+/// - Commands queued before the panic are applied, nodes completed by then keep their result.
+/// - Resources and components changed before the panic are left as they were.
+pub fn panic_safe<N: Component, M>(
+    system: impl IntoSystem<(), (), M>,
+) -> impl FnMut(&mut World) + Send + Sync + 'static {
+    let mut system: BoxedSystem = Box::new(IntoSystem::into_system(system));
+    let mut initialized = false;
+    move |world: &mut World| {
+        if !initialized {
+            system.initialize(world);
+            initialized = true;
+        }
+        let result = catch_unwind(AssertUnwindSafe(|| system.run((), world)));
+        system.apply_buffers(world);
+        let Err(panic) = result else {
+            return;
+        };
+
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        error!("Behavior system {} panicked: {}", system.name(), message);
+        let running: Vec<Entity> = world
+            .query_filtered::<Entity, (With<N>, BehaviorRunQuery)>()
+            .iter(world)
+            .collect();
+        for entity in running {
+            world.entity_mut(entity).insert(BehaviorFailure);
+        }
+    }
+}
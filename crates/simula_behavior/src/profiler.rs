@@ -0,0 +1,105 @@
+use crate::prelude::*;
+use bevy::{
+    ecs::schedule::SystemConfigs,
+    prelude::*,
+    utils::{HashMap, Instant},
+};
+use std::any::TypeId;
+
+/// Weight of the newest sample in the rolling average cost
+const COST_SMOOTHING: f32 = 0.1;
+
+/// Measures the cost of behavior node systems, when enabled.
+///
+/// Each node system is timed as a whole, and its cost is shared evenly by the nodes it ran.
+/// Node systems still run in parallel with other systems, so costs are approximate and best
+/// read relative to each other.
+#[derive(Resource, Default)]
+pub struct BehaviorProfiler {
+    pub enabled: bool,
+    /// Measure how long nodes have been running, for telemetry
+    pub timing: bool,
+    starts: HashMap<TypeId, Instant>,
+}
+
+/// Rolling average, in seconds, of a node tick cost
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct BehaviorCost(pub f32);
+
+/// Seconds a node has spent running since it was last entered
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct BehaviorDuration(pub f32);
+
+/// Chain a node system between profiler measurements of node type N
+pub fn profiled<N: Component, M>(system: impl IntoSystemConfig<M>) -> SystemConfigs {
+    (begin::<N>, system, end::<N>).chain()
+}
+
+fn begin<N: Component>(mut profiler: ResMut<BehaviorProfiler>) {
+    if profiler.enabled {
+        profiler.starts.insert(TypeId::of::<N>(), Instant::now());
+    }
+}
+
+fn end<N: Component>(
+    mut commands: Commands,
+    mut profiler: ResMut<BehaviorProfiler>,
+    mut nodes: Query<(Entity, Option<&mut BehaviorCost>), (With<N>, BehaviorRunQuery)>,
+) {
+    if !profiler.enabled {
+        return;
+    }
+    let Some(start) = profiler.starts.remove(&TypeId::of::<N>()) else {
+        return;
+    };
+    let count = nodes.iter().count();
+    if count == 0 {
+        return;
+    }
+    let sample = start.elapsed().as_secs_f32() / count as f32;
+    for (entity, cost) in &mut nodes {
+        match cost {
+            Some(mut cost) => {
+                cost.0 += (sample - cost.0) * COST_SMOOTHING;
+            }
+            None => {
+                commands.entity(entity).insert(BehaviorCost(sample));
+            }
+        }
+    }
+}
+
+/// Accumulate running time of nodes, restarting when a node is entered
+pub fn time_running(
+    mut commands: Commands,
+    time: Res<Time>,
+    profiler: Res<BehaviorProfiler>,
+    mut nodes: Query<
+        (
+            Entity,
+            Option<&mut BehaviorDuration>,
+            Option<&BehaviorStarted>,
+        ),
+        With<BehaviorRunning>,
+    >,
+) {
+    if !profiler.timing {
+        return;
+    }
+    let delta = time.delta_seconds();
+    for (entity, duration, started) in &mut nodes {
+        match duration {
+            Some(mut duration) if started.is_none() => {
+                duration.0 += delta;
+            }
+            Some(mut duration) => {
+                duration.0 = delta;
+            }
+            None => {
+                commands.entity(entity).insert(BehaviorDuration(delta));
+            }
+        }
+    }
+}
@@ -226,35 +226,106 @@ pub struct ScriptQueries<'w, 's> {
     ctxs: ResMut<'w, Assets<ScriptContext>>,
 }
 
+impl<'w, 's> ScriptQueries<'w, 's> {
+    /// Get a blackboard value from the script context of the node's tree
+    pub fn get_blackboard(
+        &mut self,
+        node: &BehaviorNode,
+        key: &str,
+    ) -> Result<Option<simula_script::script::Dynamic>, String> {
+        let script_ctx = script_ctx(&self.ctx_handles, &mut self.ctxs, node)?;
+        Ok(blackboard(script_ctx)?.get(key).cloned())
+    }
+
+    /// Set a blackboard value in the script context of the node's tree
+    pub fn set_blackboard(
+        &mut self,
+        node: &BehaviorNode,
+        key: &str,
+        value: simula_script::script::Dynamic,
+    ) -> Result<(), String> {
+        let script_ctx = script_ctx(&self.ctx_handles, &mut self.ctxs, node)?;
+        let mut blackboard = blackboard(script_ctx)?;
+        blackboard.insert(key.into(), value);
+        script_ctx.scope.set_value("blackboard", blackboard);
+        Ok(())
+    }
+
+    /// Compile a script in the script context of the node's tree, for later evaluation
+    pub fn compile(
+        &mut self,
+        node: &BehaviorNode,
+        source: impl Into<Cow<'static, str>>,
+    ) -> Result<Handle<Script>, String> {
+        make_handle(source, node, self)
+    }
+
+    /// Eval a compiled script with the blackboard values of the node's tree as scope variables
+    pub fn eval_with_blackboard(
+        &mut self,
+        node: &BehaviorNode,
+        handle: &Handle<Script>,
+    ) -> Result<simula_script::script::Dynamic, String> {
+        let script = self
+            .assets
+            .get(handle)
+            .ok_or_else(|| "Invalid script handle".to_string())?;
+        let script_ctx = script_ctx(&self.ctx_handles, &mut self.ctxs, node)?;
+        let blackboard = blackboard(script_ctx)?;
+        let stack = script_ctx.scope.len();
+        for (key, value) in blackboard {
+            script_ctx.scope.push_dynamic(key.to_string(), value);
+        }
+        let result = script.eval::<simula_script::script::Dynamic>(script_ctx);
+        script_ctx.scope.rewind(stack);
+        result.map_err(|err| err.to_string())
+    }
+}
+
+/// Script context of the node's tree, stored in the tree entity
+fn script_ctx<'a>(
+    ctx_handles: &Query<&Handle<ScriptContext>>,
+    ctxs: &'a mut Assets<ScriptContext>,
+    node: &BehaviorNode,
+) -> Result<&'a mut ScriptContext, String> {
+    let script_ctx_handle = ctx_handles
+        .get(node.tree)
+        .map_err(|_| "Cannot find script context handle in tree entity".to_string())?;
+    ctxs.get_mut(script_ctx_handle)
+        .ok_or_else(|| "Invalid script context handle".to_string())
+}
+
+fn blackboard(script_ctx: &ScriptContext) -> Result<simula_script::script::Map, String> {
+    script_ctx
+        .scope
+        .get_value::<simula_script::script::Map>("blackboard")
+        .ok_or_else(|| "Cannot find blackboard in script context".to_string())
+}
+
 fn make_handle(
     eval: impl Into<Cow<'static, str>>,
     node: &BehaviorNode,
     scripts: &mut ScriptQueries,
 ) -> Result<Handle<Script>, String> {
-    // if we have a script context handle, compile the script
-    // script context are stored in the tree entity
-    if let Some(script_ctx_handle) = scripts.ctx_handles.get(node.tree).ok() {
-        // if we have a script context, compile the script
-        if let Some(script_ctx) = scripts.ctxs.get_mut(&script_ctx_handle) {
-            let mut script = Script::default();
-            script.script = eval.into();
-            match script.compile(script_ctx) {
-                Ok(_) => {
-                    let script_handle = scripts.assets.add(script);
-                    return Ok(script_handle.clone());
-                }
-                Err(err) => {
-                    error!("{:#?}", err);
-                    return Err(err.to_string());
-                }
-            }
-        } else {
-            error!("Invalid script context handle");
-            return Err("Invalid script context handle".into());
+    // if we have a script context, compile the script
+    let script_ctx = match script_ctx(&scripts.ctx_handles, &mut scripts.ctxs, node) {
+        Ok(script_ctx) => script_ctx,
+        Err(err) => {
+            error!("{}", err);
+            return Err(err);
+        }
+    };
+    let mut script = Script::default();
+    script.script = eval.into();
+    match script.compile(script_ctx) {
+        Ok(_) => {
+            let script_handle = scripts.assets.add(script);
+            Ok(script_handle)
+        }
+        Err(err) => {
+            error!("{:#?}", err);
+            Err(err.to_string())
         }
-    } else {
-        error!("Cannot find script context handle in tree entity");
-        return Err("Cannot find script context handle in tree entity".into());
     }
 }
 
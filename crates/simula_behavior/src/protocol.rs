@@ -1,4 +1,4 @@
-use crate::{Behavior, BehaviorFactory};
+use crate::{Behavior, BehaviorFactory, BehaviorOutcome};
 use bevy::{prelude::*, utils::Uuid};
 use crossbeam_channel::{Receiver, Sender};
 use serde::{Deserialize, Serialize};
@@ -92,6 +92,16 @@ pub enum BehaviorProtocolClient<T: BehaviorFactory> {
     ),
     /// Request behavior to be stopped
     Stop(BehaviorFileId, StopOption),
+    /// Request a single telemetry message with the current behavior state
+    RequestSnapshot(BehaviorFileId),
+    /// Request telemetry to be streamed every frame
+    Subscribe(BehaviorFileId),
+    /// Request telemetry streaming to stop
+    Unsubscribe(BehaviorFileId),
+    /// Request node profiling to be enabled or disabled
+    Profile(bool),
+    /// Request node running time telemetry to be enabled or disabled
+    Timing(bool),
 }
 
 pub enum BehaviorProtocolServer<T: BehaviorFactory> {
@@ -105,23 +115,29 @@ pub enum BehaviorProtocolServer<T: BehaviorFactory> {
     FileLoaded(BehaviorFileId, Behavior<T>),
     /// Behavior file saved
     FileSaved(BehaviorFileId),
-    /// Behavior started
-    Started(BehaviorFileId),
+    /// Behavior started on agent
+    Started(BehaviorFileId, RemoteEntity),
     /// Behavior stopped
     Stopped(BehaviorFileId),
-    /// Behavior telemetry
-    Telemetry(BehaviorFileId, BehaviorTelemetry<T>),
+    /// Behavior telemetry from agent
+    Telemetry(BehaviorFileId, RemoteEntity, BehaviorTelemetry<T>),
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct BehaviorTelemetry<T: BehaviorFactory>(
     pub Option<RemoteEntity>,
     pub BehaviorState,
     pub Option<T>,
     pub Vec<BehaviorTelemetry<T>>,
+    /// Rolling average tick cost in seconds, when profiling
+    pub Option<f32>,
+    /// Why the behavior completed, if known
+    pub Option<BehaviorOutcome>,
+    /// Seconds running since the node was last entered, when timing
+    pub f32,
 );
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum BehaviorState {
     #[default]
     None,
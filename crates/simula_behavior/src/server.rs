@@ -1,14 +1,20 @@
 use crate::{
+    asset::{trim_extension, INLINE_PREFIX},
     prelude::*,
     protocol::{
         BehaviorFileId, BehaviorFileName, BehaviorProtocolClient, BehaviorProtocolServer,
         BehaviorServer, BehaviorState, BehaviorTelemetry, RemoteEntity, StartOption, StopOption,
     },
+    storage::BehaviorStorageBackend,
+};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
 };
-use bevy::{prelude::*, utils::HashMap};
 use serde::{Deserialize, Serialize};
 use simula_script::ScriptContext;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::{cmp::Ordering, collections::BinaryHeap, time::Duration};
 
 #[derive(Default)]
@@ -20,11 +26,15 @@ where
 {
     fn build(&self, app: &mut App) {
         app.insert_resource(BehaviorTrackers::<T>::default())
+            .init_resource::<BehaviorTelemetryRequests>()
+            .init_resource::<BehaviorStorageBackend>()
+            .init_resource::<BehaviorProfiler>()
             .add_startup_system(setup::<T>)
             .add_system(track_loaded_behaviors::<T>)
             .add_system(tracker_documents::<T>)
             .add_system(update::<T>)
-            .add_system(update_telemetry::<T>);
+            .add_system(update_telemetry::<T>)
+            .add_system(untrack_removed_trees::<T>);
     }
 }
 
@@ -50,50 +60,87 @@ pub struct BehaviorTracker<T: BehaviorFactory> {
     pub asset: AssetTracker<T>,
 }
 
+/// Behavior file a tree was started from, used to route telemetry to the right agent
+#[derive(Component, Debug, Clone, Deref)]
+pub struct BehaviorSource(pub BehaviorFileId);
+
+/// Which behaviors clients want telemetry for
+#[derive(Resource)]
+pub struct BehaviorTelemetryRequests {
+    /// Only stream telemetry for subscribed behaviors, otherwise stream all running behaviors
+    pub subscribed_only: bool,
+    pub subscribed: HashSet<BehaviorFileId>,
+    /// Behaviors to send a single telemetry message for, on next update
+    pub snapshots: HashSet<BehaviorFileId>,
+}
+
+impl Default for BehaviorTelemetryRequests {
+    fn default() -> Self {
+        Self {
+            subscribed_only: true,
+            subscribed: default(),
+            snapshots: default(),
+        }
+    }
+}
+
+impl BehaviorTelemetryRequests {
+    pub fn wants(&self, file_id: &BehaviorFileId) -> bool {
+        !self.subscribed_only
+            || self.subscribed.contains(file_id)
+            || self.snapshots.contains(file_id)
+    }
+}
+
 #[derive(Default, Resource, Deref, DerefMut)]
 pub struct BehaviorTrackers<T: BehaviorFactory>(HashMap<BehaviorFileId, BehaviorTracker<T>>);
 
 fn setup<T: BehaviorFactory>(
     mut behavior_trackers: ResMut<BehaviorTrackers<T>>,
     behavior_server: Res<BehaviorServer<T>>,
+    behavior_storage: Res<BehaviorStorageBackend>,
 ) {
-    let dir_path = "assets/bht/u";
-
-    // Read the directory and handle any errors
-    let paths = match std::fs::read_dir(dir_path) {
-        Ok(paths) => paths,
-        Err(err) => {
-            eprintln!("Error reading directory: {}", err);
-            return;
-        }
-    };
-
-    // Iterate over the directory entries
-    for path in paths {
-        if let Ok(entry) = path {
-            // Check if the entry is a file with the desired extension
-            if entry.file_type().unwrap().is_file() {
-                let osfile_name = entry.file_name();
-                let file_name = osfile_name.to_string_lossy().to_owned();
-                if file_name.ends_with(".bht.ron") {
-                    let file_id = BehaviorFileId::new();
-                    let file_name = format!("bht/u/{}", file_name.trim_end_matches(".bht.ron"));
-                    let file_name = BehaviorFileName(file_name.to_string().into());
-
-                    behavior_trackers.insert(
-                        file_id.clone(),
-                        BehaviorTracker {
-                            file_name: file_name.clone(),
-                            entity: EntityTracker::None,
-                            asset: AssetTracker::None,
-                        },
-                    );
+    // List behavior files available in storage
+    for file_name in behavior_storage.list() {
+        let file_id = BehaviorFileId::new();
+
+        behavior_trackers.insert(
+            file_id.clone(),
+            BehaviorTracker {
+                file_name: file_name.clone(),
+                entity: EntityTracker::None,
+                asset: AssetTracker::None,
+            },
+        );
+
+        behavior_server
+            .sender
+            .send(BehaviorProtocolServer::FileName(file_id, file_name))
+            .unwrap();
+    }
+}
 
-                    behavior_server
-                        .sender
-                        .send(BehaviorProtocolServer::FileName(file_id, file_name))
-                        .unwrap();
-                }
+// Stop tracking entities whose behavior tree was removed or despawned
+fn untrack_removed_trees<T: BehaviorFactory>(
+    mut removed: RemovedComponents<BehaviorTree<T>>,
+    mut behavior_trackers: ResMut<BehaviorTrackers<T>>,
+    behavior_server: Res<BehaviorServer<T>>,
+) {
+    for entity in removed.iter() {
+        for (file_id, tracker) in behavior_trackers.iter_mut() {
+            let tracked = match tracker.entity {
+                EntityTracker::Spawned(tracked)
+                | EntityTracker::Attached(tracked)
+                | EntityTracker::Inserted(tracked) => tracked,
+                EntityTracker::None => continue,
+            };
+            if tracked == entity {
+                info!("Behavior tree removed from: {:?}", entity);
+                tracker.entity = EntityTracker::None;
+                behavior_server
+                    .sender
+                    .send(BehaviorProtocolServer::Stopped(file_id.clone()))
+                    .unwrap();
             }
         }
     }
@@ -109,14 +156,14 @@ fn tracker_documents<T: BehaviorFactory + for<'de> Deserialize<'de>>(
     for (_file_id, tracker) in behavior_trackers.iter_mut() {
         if let AssetTracker::Document(document_handle) = &tracker.asset {
             if let Some(document) = behavior_documents.get(document_handle) {
-                let res = ron::de::from_str::<Behavior<T>>(&document);
+                let res = document.to_behavior::<T>();
                 if let Ok(behavior) = res {
                     // Get file name
                     let path = asset_server.get_handle_path(document_handle);
                     let file_name = path.and_then(|path| {
                         let file_path = path.path().to_string_lossy();
                         let file_name: Cow<'static, str> =
-                            file_path.trim_end_matches(".bht.ron").to_owned().into();
+                            trim_extension(&file_path).to_owned().into();
                         Some(file_name)
                     });
 
@@ -131,7 +178,7 @@ fn tracker_documents<T: BehaviorFactory + for<'de> Deserialize<'de>>(
                 } else if let Err(err) = res {
                     // Remove asset from tracker
                     tracker.asset = AssetTracker::None;
-                    error!("Failed to deserialize behavior {:?}", err);
+                    error!("Failed to deserialize behavior {}", err);
                 }
             }
         }
@@ -139,8 +186,6 @@ fn tracker_documents<T: BehaviorFactory + for<'de> Deserialize<'de>>(
 }
 
 fn track_loaded_behaviors<T: BehaviorFactory>(
-    mut commands: Commands,
-    mut behavior_trees: Query<(Entity, &Name, &Handle<BehaviorAsset<T>>), With<BehaviorTree<T>>>,
     mut asset_events: EventReader<AssetEvent<BehaviorAsset<T>>>,
     asset_server: Res<AssetServer>,
     mut behavior_trackers: ResMut<BehaviorTrackers<T>>,
@@ -193,20 +238,11 @@ fn track_loaded_behaviors<T: BehaviorFactory>(
                         .unwrap();
                 }
             }
+            // hot reloading trees are rebuilt by behavior_tree_hot_reload
             AssetEvent::Modified { handle } => {
                 if let Some(path) = asset_server.get_handle_path(handle) {
                     info!("Modified: {:?}", path);
                 }
-                for (entity, name, behavior_asset) in &mut behavior_trees {
-                    if behavior_asset == handle {
-                        if let Some(_asset) = behavior_assets.get(&handle) {
-                            info!("Rebuilding behavior for: [{}] {}", entity.index(), name);
-                            commands
-                                .entity(entity)
-                                .insert(BehaviorTreeReset::<T>::default());
-                        }
-                    }
-                }
             }
             _ => {
                 error!("{:?}", event);
@@ -221,11 +257,18 @@ fn build_telemetry<T: BehaviorFactory>(
     entity: Entity,
     telemetry: &mut BehaviorTelemetry<T>,
     behavior: &Behavior<T>,
+    inline: &BTreeMap<Cow<'static, str>, Behavior<T>>,
 ) -> Result<(), BehaviorMissing> {
     let behavior_running = world.get::<BehaviorRunning>(entity);
     let behavior_failure = world.get::<BehaviorFailure>(entity);
     let behavior_success = world.get::<BehaviorSuccess>(entity);
     let behavior_cursor = world.get::<BehaviorCursor>(entity);
+    let behavior_cost = world.get::<BehaviorCost>(entity).map(|cost| cost.0);
+    let behavior_outcome = world.get::<BehaviorOutcome>(entity).cloned();
+    let behavior_duration = world
+        .get::<BehaviorDuration>(entity)
+        .filter(|_| world.resource::<BehaviorProfiler>().timing)
+        .map_or(0.0, |duration| duration.0);
     let behavior_state = if behavior_cursor.is_some() {
         BehaviorState::Cursor
     } else if behavior_running.is_some() {
@@ -250,16 +293,46 @@ fn build_telemetry<T: BehaviorFactory>(
         let instance_children = instance_children.iter();
         for (instance_child, source_child) in instance_children.zip(source_children) {
             let mut telemetry = BehaviorTelemetry::<T>::default();
-            build_telemetry(world, *instance_child, &mut telemetry, source_child)?;
+            build_telemetry(world, *instance_child, &mut telemetry, source_child, inline)?;
+            telemetry_children.push(telemetry);
+        }
+    }
+
+    // transparent subtrees report their nodes as children
+    let subtree = data.inner_reflect().downcast_ref::<Subtree<T>>();
+    if let Some(subtree) = subtree.filter(|subtree| subtree.transparent) {
+        let root = world
+            .get::<BehaviorChildren>(entity)
+            .and_then(|children| children.first().copied());
+        let asset = world
+            .get::<Handle<BehaviorAsset<T>>>(entity)
+            .and_then(|handle| world.resource::<Assets<BehaviorAsset<T>>>().get(handle))
+            .map(|asset| asset.behavior.clone());
+        let source = match subtree.asset.strip_prefix(INLINE_PREFIX) {
+            Some(name) => inline
+                .get(name)
+                .cloned()
+                .map(|source| (source, inline.clone())),
+            None => asset.map(|source| {
+                let inline = source.inline().clone();
+                (source, inline)
+            }),
+        };
+        if let (Some(root), Some((source, inline))) = (root, source) {
+            let mut telemetry = BehaviorTelemetry::<T>::default();
+            build_telemetry(world, root, &mut telemetry, &source, &inline)?;
             telemetry_children.push(telemetry);
         }
     }
 
     *telemetry = BehaviorTelemetry(
-        Some(RemoteEntity::new(entity, "")),
+        Some(RemoteEntity::new(entity, behavior.name().to_owned())),
         behavior_state,
         Some(data),
         telemetry_children,
+        behavior_cost,
+        behavior_outcome,
+        behavior_duration,
     );
 
     Ok(())
@@ -267,8 +340,12 @@ fn build_telemetry<T: BehaviorFactory>(
 
 fn update_telemetry<T: BehaviorFactory>(world: &mut World) {
     let mut tracks = vec![];
+    let telemetry_requests = world.resource::<BehaviorTelemetryRequests>();
     if let Some(behavior_trackers) = world.get_resource::<BehaviorTrackers<T>>() {
         for (file_id, behavior_tracker) in behavior_trackers.iter() {
+            if !telemetry_requests.wants(file_id) {
+                continue;
+            }
             let entity = match behavior_tracker.entity {
                 EntityTracker::Spawned(entity) => Some(entity),
                 EntityTracker::Attached(entity) => Some(entity),
@@ -276,6 +353,10 @@ fn update_telemetry<T: BehaviorFactory>(world: &mut World) {
                 EntityTracker::None => continue,
             };
             if let Some(entity) = entity {
+                // Only report agents running this file, another file may have taken over
+                if world.get::<BehaviorSource>(entity).map(|source| &source.0) != Some(file_id) {
+                    continue;
+                }
                 if let Some(behavior_asset) = world.get::<Handle<BehaviorAsset<T>>>(entity) {
                     if let Some(behavior_assets) = world.get_resource::<Assets<BehaviorAsset<T>>>()
                     {
@@ -296,20 +377,35 @@ fn update_telemetry<T: BehaviorFactory>(world: &mut World) {
         error!("Failed to get behavior trackers");
     }
 
+    // Snapshots are sent once
+    world
+        .resource_mut::<BehaviorTelemetryRequests>()
+        .snapshots
+        .clear();
+
     let mut behaviors_children = world.query_filtered::<&Children, With<BehaviorTree<T>>>();
 
+    let mut names = world.query::<Option<&Name>>();
+
     for (file_id, entity, behavior) in tracks {
+        let name = names
+            .get(world, entity)
+            .ok()
+            .flatten()
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        let agent = RemoteEntity::new(entity, name);
         let mut root = None;
         if let Ok(children) = behaviors_children.get(world, entity) {
             root = children.first();
         }
         if let Some(root) = root {
             let mut telemetry = BehaviorTelemetry::<T>::default();
-            if build_telemetry(world, *root, &mut telemetry, &behavior).is_ok() {
+            if build_telemetry(world, *root, &mut telemetry, &behavior, behavior.inline()).is_ok() {
                 let behavior_server = world.get_resource::<BehaviorServer<T>>().unwrap();
                 behavior_server
                     .sender
-                    .send(BehaviorProtocolServer::Telemetry(file_id, telemetry))
+                    .send(BehaviorProtocolServer::Telemetry(file_id, agent, telemetry))
                     .unwrap();
             } else {
                 error!("Failed to build telemetry");
@@ -360,14 +456,18 @@ fn update<T>(
         (Entity, &Name, Option<&Handle<BehaviorAsset<T>>>),
         With<BehaviorTree<T>>,
     >,
+    children: Query<&Children>,
     mut behavior_assets: ResMut<Assets<BehaviorAsset<T>>>,
     mut behavior_trackers: ResMut<BehaviorTrackers<T>>,
+    mut telemetry_requests: ResMut<BehaviorTelemetryRequests>,
+    mut profiler: ResMut<BehaviorProfiler>,
     mut script_ctxs: ResMut<Assets<ScriptContext>>,
     behavior_server: Res<BehaviorServer<T>>,
     asset_server: Res<AssetServer>,
+    behavior_storage: Res<BehaviorStorageBackend>,
     mut queued_msgs: Local<PriorityMessageQueue<T>>,
 ) where
-    T: BehaviorFactory + Serialize,
+    T: BehaviorFactory + Serialize + for<'de> Deserialize<'de>,
 {
     let priority = time.elapsed();
 
@@ -480,9 +580,29 @@ fn update<T>(
                         // if no asset, load and get a handle to asset
                         AssetTracker::None if msg.count == 0 => {
                             info!("Behavior not loaded for: {:?}", behavior_tracker.file_name);
-                            let behavior_handle: Handle<BehaviorDocument> = asset_server
-                                .load(format!("{}.bht.ron", *behavior_tracker.file_name).as_str());
-                            behavior_tracker.asset = AssetTracker::Document(behavior_handle);
+                            // storage may provide the document, otherwise use the asset server
+                            if let Some(document) =
+                                behavior_storage.load(&behavior_tracker.file_name)
+                            {
+                                match ron::de::from_str::<Behavior<T>>(&document) {
+                                    Ok(behavior) => {
+                                        let behavior_handle = behavior_assets.add(BehaviorAsset {
+                                            behavior,
+                                            file_name: Some(behavior_tracker.file_name.0.clone()),
+                                        });
+                                        behavior_tracker.asset =
+                                            AssetTracker::Asset(behavior_handle);
+                                    }
+                                    Err(err) => {
+                                        error!("Failed to deserialize behavior {:?}", err);
+                                    }
+                                }
+                            } else {
+                                let behavior_handle: Handle<BehaviorDocument> = asset_server.load(
+                                    format!("{}.bht.ron", *behavior_tracker.file_name).as_str(),
+                                );
+                                behavior_tracker.asset = AssetTracker::Document(behavior_handle);
+                            }
                             // check again later
                             queued_msgs.push(PriorityMessage {
                                 priority: priority + Duration::from_millis(100),
@@ -510,15 +630,17 @@ fn update<T>(
                         if let Some(behavior_tracker) = behavior_trackers.get_mut(&file_id) {
                             behavior_tracker.file_name = file_name.clone();
                         }
-                        let dir_path = "assets";
-                        let file_ext = "bht.ron";
-                        let file_path = format!("{}/{}.{}", dir_path, file_name.as_ref(), file_ext);
-                        std::fs::write(&file_path, file_data).unwrap();
-                        info!("Saved file: {}", &file_path);
-                        behavior_server
-                            .sender
-                            .send(BehaviorProtocolServer::FileSaved(file_id.clone()))
-                            .unwrap();
+                        match behavior_storage.save(file_name, &file_data) {
+                            Ok(_) => {
+                                behavior_server
+                                    .sender
+                                    .send(BehaviorProtocolServer::FileSaved(file_id.clone()))
+                                    .unwrap();
+                            }
+                            Err(err) => {
+                                error!("Failed to save file: {:?}", err);
+                            }
+                        }
                     }
                     Err(err) => {
                         error!("Failed to serialize file_data: {:?}", err);
@@ -579,7 +701,7 @@ fn update<T>(
                 {
                     behavior_tracker.entity = EntityTracker::None;
 
-                    match start_option {
+                    let agent = match start_option {
                         // spawn behavior tree
                         StartOption::Spawn => {
                             let script_ctx = BehaviorTree::<T>::create_script_context();
@@ -590,11 +712,13 @@ fn update<T>(
                                     behavior_asset.clone(),
                                     BehaviorTree::<T>::default(),
                                     BehaviorTreeReset::<T>::default(),
+                                    BehaviorSource(file_id.clone()),
                                     script_ctx_handle,
                                 ))
                                 .id();
                             info!("Spawned entity: {:?} for: {}", entity, file_name.as_ref());
                             behavior_tracker.entity = EntityTracker::Spawned(entity);
+                            RemoteEntity::new(entity, format!("BHT: {}", file_name.as_ref()))
                         }
                         // attach to behavior tree
                         StartOption::Attach(remote_entity) => {
@@ -605,7 +729,11 @@ fn update<T>(
                                     .insert(behavior_asset.clone())
                                     .insert(BehaviorTreeReset::<T>::default());
                             }
+                            commands
+                                .entity(entity)
+                                .insert(BehaviorSource(file_id.clone()));
                             behavior_tracker.entity = EntityTracker::Attached(entity);
+                            remote_entity.clone()
                         }
                         // insert behavior asset
                         StartOption::Insert(remote_entity) => {
@@ -616,14 +744,16 @@ fn update<T>(
                                 .entity(entity)
                                 .insert(behavior_asset.clone())
                                 .insert(BehaviorTreeReset::<T>::default())
+                                .insert(BehaviorSource(file_id.clone()))
                                 .insert(script_ctx_handle);
                             behavior_tracker.entity = EntityTracker::Inserted(entity);
+                            remote_entity.clone()
                         }
                     };
 
                     behavior_server
                         .sender
-                        .send(BehaviorProtocolServer::Started(file_id.clone()))
+                        .send(BehaviorProtocolServer::Started(file_id.clone(), agent))
                         .unwrap();
                 } else {
                     error!("Failed to build behavior tree for file_id: {:?}", file_id);
@@ -653,14 +783,29 @@ fn update<T>(
                     };
 
                     if let Some(entity) = entity {
+                        // clear node state, so no node is left running and the tree
+                        // starts clean when run again
+                        for node in std::iter::once(entity).chain(children.iter_descendants(entity))
+                        {
+                            commands.entity(node).remove::<(
+                                BehaviorCursor,
+                                BehaviorRunning,
+                                BehaviorStarted,
+                                BehaviorSuccess,
+                                BehaviorFailure,
+                            )>();
+                        }
                         match stop_option {
                             StopOption::Despawn => {
                                 commands.entity(entity).despawn_recursive();
                             }
-                            StopOption::Detach => {}
+                            StopOption::Detach => {
+                                commands.entity(entity).remove::<BehaviorSource>();
+                            }
                             StopOption::Remove => {
                                 commands
                                     .entity(entity)
+                                    .remove::<BehaviorSource>()
                                     .remove::<Handle<BehaviorAsset<T>>>()
                                     .remove::<Handle<ScriptContext>>()
                                     .despawn_descendants();
@@ -678,6 +823,21 @@ fn update<T>(
                     error!("Invalid file_id: {:?}", file_id);
                 }
             }
+            BehaviorProtocolClient::RequestSnapshot(file_id) => {
+                telemetry_requests.snapshots.insert(file_id.clone());
+            }
+            BehaviorProtocolClient::Subscribe(file_id) => {
+                telemetry_requests.subscribed.insert(file_id.clone());
+            }
+            BehaviorProtocolClient::Unsubscribe(file_id) => {
+                telemetry_requests.subscribed.remove(file_id);
+            }
+            BehaviorProtocolClient::Profile(enabled) => {
+                profiler.enabled = *enabled;
+            }
+            BehaviorProtocolClient::Timing(enabled) => {
+                profiler.timing = *enabled;
+            }
         }
 
         if let Some(msg) = queued_msgs.peek() {
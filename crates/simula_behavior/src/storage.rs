@@ -0,0 +1,162 @@
+use crate::protocol::BehaviorFileName;
+use bevy::prelude::*;
+
+/// Where behavior files created or edited by users are stored
+pub const USER_BEHAVIOR_DIR: &str = "bht/u";
+const BEHAVIOR_FILE_EXT: &str = "bht.ron";
+
+/// Storage backend used by the behavior server to list and save behavior files
+pub trait BehaviorStorage: Send + Sync + 'static {
+    /// list behavior files available in the user directory
+    fn list(&self) -> Vec<BehaviorFileName>;
+
+    /// save a serialized behavior document
+    fn save(&self, file_name: &BehaviorFileName, document: &str) -> Result<(), String>;
+
+    /// load a serialized behavior document, None defers loading to the asset server
+    fn load(&self, file_name: &BehaviorFileName) -> Option<String>;
+}
+
+/// The storage backend used by the behavior server
+#[derive(Resource, Deref, DerefMut)]
+pub struct BehaviorStorageBackend(pub Box<dyn BehaviorStorage>);
+
+impl Default for BehaviorStorageBackend {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default() -> Self {
+        Self(Box::new(FileStorage::default()))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn default() -> Self {
+        Self(Box::new(LocalStorage::default()))
+    }
+}
+
+/// Native storage, behavior files live in the assets directory
+pub struct FileStorage {
+    pub assets_dir: String,
+}
+
+impl Default for FileStorage {
+    fn default() -> Self {
+        Self {
+            assets_dir: "assets".into(),
+        }
+    }
+}
+
+impl BehaviorStorage for FileStorage {
+    fn list(&self) -> Vec<BehaviorFileName> {
+        let dir_path = format!("{}/{}", self.assets_dir, USER_BEHAVIOR_DIR);
+
+        // Read the directory and handle any errors
+        let paths = match std::fs::read_dir(&dir_path) {
+            Ok(paths) => paths,
+            Err(err) => {
+                error!("Error reading directory {}: {}", dir_path, err);
+                return vec![];
+            }
+        };
+
+        // Iterate over the directory entries
+        let mut file_names = vec![];
+        for entry in paths.flatten() {
+            // Check if the entry is a file with the desired extension
+            if entry.file_type().map_or(false, |typ| typ.is_file()) {
+                let osfile_name = entry.file_name();
+                let file_name = osfile_name.to_string_lossy().to_owned();
+                if let Some(file_name) = file_name.strip_suffix(&format!(".{}", BEHAVIOR_FILE_EXT))
+                {
+                    let file_name = format!("{}/{}", USER_BEHAVIOR_DIR, file_name);
+                    file_names.push(BehaviorFileName(file_name.into()));
+                }
+            }
+        }
+        file_names
+    }
+
+    fn save(&self, file_name: &BehaviorFileName, document: &str) -> Result<(), String> {
+        let file_path = format!(
+            "{}/{}.{}",
+            self.assets_dir,
+            file_name.as_ref(),
+            BEHAVIOR_FILE_EXT
+        );
+        std::fs::write(&file_path, document).map_err(|err| err.to_string())?;
+        info!("Saved file: {}", &file_path);
+        Ok(())
+    }
+
+    fn load(&self, _file_name: &BehaviorFileName) -> Option<String> {
+        // Files on disk are loaded through the asset server
+        None
+    }
+}
+
+/// Browser storage, behavior files are kept in `window.localStorage`
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage {
+    pub prefix: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Default for LocalStorage {
+    fn default() -> Self {
+        Self {
+            prefix: "simula:".into(),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+    }
+
+    fn key(&self, file_name: &BehaviorFileName) -> String {
+        format!(
+            "{}{}.{}",
+            self.prefix,
+            file_name.as_ref(),
+            BEHAVIOR_FILE_EXT
+        )
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl BehaviorStorage for LocalStorage {
+    fn list(&self) -> Vec<BehaviorFileName> {
+        let Some(storage) = Self::storage() else {
+            error!("Local storage is not available");
+            return vec![];
+        };
+        let dir_prefix = format!("{}{}/", self.prefix, USER_BEHAVIOR_DIR);
+        let file_suffix = format!(".{}", BEHAVIOR_FILE_EXT);
+        let len = storage.length().unwrap_or(0);
+        (0..len)
+            .filter_map(|idx| storage.key(idx).ok().flatten())
+            .filter(|key| key.starts_with(&dir_prefix))
+            .filter_map(|key| {
+                key.strip_prefix(&self.prefix)
+                    .and_then(|key| key.strip_suffix(&file_suffix))
+                    .map(|file_name| BehaviorFileName(file_name.to_owned().into()))
+            })
+            .collect()
+    }
+
+    fn save(&self, file_name: &BehaviorFileName, document: &str) -> Result<(), String> {
+        let storage = Self::storage().ok_or("Local storage is not available")?;
+        let key = self.key(file_name);
+        storage
+            .set_item(&key, document)
+            .map_err(|err| format!("{:?}", err))?;
+        info!("Saved file: {}", &key);
+        Ok(())
+    }
+
+    fn load(&self, file_name: &BehaviorFileName) -> Option<String> {
+        Self::storage().and_then(|storage| storage.get_item(&self.key(file_name)).ok().flatten())
+    }
+}
@@ -1,6 +1,12 @@
-use crate::{clear_behavior_started, complete_behavior, prelude::*, start_behavior, BehaviorTrace};
+use crate::{
+    asset::behavior_tree_reset, clear_behavior_started, complete_behavior, prelude::*,
+    start_behavior, BehaviorTrace,
+};
 use bevy::{
-    ecs::system::{CommandQueue, EntityCommands},
+    ecs::{
+        system::{CommandQueue, EntityCommands},
+        world::EntityMut,
+    },
     prelude::*,
     reflect::TypeUuid,
 };
@@ -14,6 +20,7 @@ pub fn test_app(app: &mut App) -> &mut App {
     app.add_plugin(AssetPlugin::default());
     app.add_asset::<Script>();
     app.add_asset::<ScriptContext>();
+    app.add_asset::<BehaviorAsset<TestBehavior>>();
     // Add the behaviors system to the app
     app.add_systems((clear_behavior_started, complete_behavior, start_behavior).chain());
     app.add_system(debug::run);
@@ -28,12 +35,68 @@ pub fn test_app(app: &mut App) -> &mut App {
     app.add_system(delay::run);
     app.add_system(identity::run);
     app.add_system(guard::run);
+    app.add_system(timeout::run);
+    app.add_system(spawn::run);
+    app.add_system(spawn::cleanup);
+    app.add_system(despawn::run);
+    app.add_system(stamp_now::run);
+    app.add_system(cooldown_ready::run);
+    app.add_system(subtree::run::<TestBehavior>);
+    app.add_system(retry::run);
+    app.add_system(retry_until_success::run);
+    app.add_system(chance::run);
+    app.add_system(distance_check::run);
+    app.add_system(compute::run);
+    app.add_system(overlap::run);
+    app.add_system(yield_tick::run);
+    app.add_system(add_component::run);
+    app.add_system(remove_component::run);
+    app.add_system(wander::run);
+    app.add_system(tween::run);
+    app.add_system(input_pressed::run);
+    app.add_system(find_nearest::run);
+    app.add_system(for_each::run);
+    app.add_system(cooldown::run);
+    app.add_system(random_selector::run);
+    app.add_system(blackboard_gate::run);
+    app.add_system(parallel::run);
+    app.add_system(switch::run);
+    app.add_system(wait_for_signal::run);
+    app.add_system(script_condition::run);
+    app.add_systems(
+        (
+            input_pressed::collect::<KeyCode>,
+            input_pressed::collect::<MouseButton>,
+        )
+            .in_base_set(CoreSet::PreUpdate),
+    );
+    app.add_system(crate::lod::update);
+    app.init_resource::<BehaviorSpawners>();
+    app.init_resource::<BehaviorComponents>();
+    app.init_resource::<BehaviorRng>();
+    app.init_resource::<BehaviorInputs>();
+    app.add_event::<BehaviorSignal>();
+    app.add_event::<BehaviorTransition>();
     app.init_resource::<BehaviorTrace>();
+    app.init_resource::<AppTypeRegistry>();
     app
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
-pub struct TestBehaviorAttributes;
+pub struct TestBehaviorAttributes {
+    #[serde(default)]
+    pub pos: Vec2,
+}
+
+impl BehaviorNodeInspectable<TestBehavior> for TestBehaviorAttributes {
+    fn set_pos(&mut self, pos: Vec2) {
+        self.pos = pos;
+    }
+
+    fn get_pos(&self) -> Vec2 {
+        self.pos
+    }
+}
 
 #[derive(Serialize, Deserialize, TypeUuid, Debug, Clone, Reflect, FromReflect, BehaviorFactory)]
 #[uuid = "3d6cc56a-542e-11ed-9abb-02a179e5df2b"]
@@ -52,6 +115,32 @@ pub enum TestBehavior {
     Identity(Identity),
     Guard(Guard),
     Timeout(Timeout),
+    Spawn(Spawn),
+    Despawn(Despawn),
+    StampNow(StampNow),
+    CooldownReady(CooldownReady),
+    Subtree(Subtree<TestBehavior>),
+    Retry(Retry),
+    RetryUntilSuccess(RetryUntilSuccess),
+    Chance(Chance),
+    DistanceCheck(DistanceCheck),
+    Compute(Compute),
+    Overlap(Overlap),
+    Yield(Yield),
+    AddComponent(AddComponent),
+    RemoveComponent(RemoveComponent),
+    Wander(Wander),
+    Tween(Tween),
+    InputPressed(InputPressed),
+    FindNearest(FindNearest),
+    ForEach(ForEach),
+    Cooldown(Cooldown),
+    RandomSelector(RandomSelector),
+    BlackboardGate(BlackboardGate),
+    Parallel(Parallel),
+    Switch(Switch),
+    WaitForSignal(WaitForSignal),
+    ScriptCondition(ScriptCondition),
 }
 
 impl Default for TestBehavior {
@@ -60,6 +149,17 @@ impl Default for TestBehavior {
     }
 }
 
+impl BehaviorInspectable for TestBehavior {
+    fn categories(&self) -> Vec<&'static str> {
+        match self.typ() {
+            BehaviorType::Action => vec!["Action"],
+            BehaviorType::Composite => vec!["Composite"],
+            BehaviorType::Decorator => vec!["Decorator"],
+            BehaviorType::Subtree => vec!["Subtree"],
+        }
+    }
+}
+
 pub fn trace_behavior(behavior: &str) -> BehaviorTrace {
     // Load behavior tree from RON string
     let document = ron::from_str::<Behavior<TestBehavior>>(behavior);
@@ -71,11 +171,23 @@ pub fn trace_behavior(behavior: &str) -> BehaviorTrace {
     let mut app = App::new();
     app.add_plugin(bevy::time::TimePlugin::default());
     test_app(&mut app);
+    let script_ctx = BehaviorTree::<TestBehavior>::create_script_context();
+    let script_ctx_handle = app
+        .world
+        .resource_mut::<Assets<ScriptContext>>()
+        .add(script_ctx);
+    let behavior_handle = app
+        .world
+        .resource_mut::<Assets<BehaviorAsset<TestBehavior>>>()
+        .add(BehaviorAsset {
+            behavior: document.clone(),
+            file_name: None,
+        });
     let mut command_queue = CommandQueue::default();
     let mut commands = Commands::new(&mut command_queue, &app.world);
 
-    // Spawn tree
-    let entity = commands.spawn_empty().id();
+    // Spawn tree, with a script context for the blackboard
+    let entity = commands.spawn((script_ctx_handle, behavior_handle)).id();
     let root = BehaviorTree::insert_tree(entity, None, &mut commands, &document);
     commands.entity(entity).add_child(root);
     commands.entity(root).insert(BehaviorCursor::Delegate);
@@ -93,3 +205,51 @@ pub fn trace_behavior(behavior: &str) -> BehaviorTrace {
     // Get app trace
     app.world.get_resource::<BehaviorTrace>().unwrap().clone()
 }
+
+/// Create an app running the test behaviors, building trees of agents with BehaviorTreeReset.
+/// Time follows the wall clock.
+pub fn behavior_app() -> App {
+    let mut app = App::new();
+    app.add_plugin(bevy::time::TimePlugin::default());
+    test_app(&mut app);
+    app.add_system(behavior_tree_reset::<TestBehavior>);
+    app
+}
+
+/// Same as behavior_app, but time only advances when the test updates the Time resource.
+pub fn behavior_app_manual_time() -> App {
+    let mut app = App::new();
+    app.init_resource::<Time>();
+    test_app(&mut app);
+    app.add_system(behavior_tree_reset::<TestBehavior>);
+    app
+}
+
+/// Add a behavior asset loaded from a RON string
+pub fn add_behavior(app: &mut App, behavior: &str) -> Handle<BehaviorAsset<TestBehavior>> {
+    let document = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+    app.world
+        .resource_mut::<Assets<BehaviorAsset<TestBehavior>>>()
+        .add(BehaviorAsset {
+            behavior: document,
+            file_name: None,
+        })
+}
+
+/// Spawn an agent running a behavior, with its own script context and a blackboard seeded
+/// with the given values. Insert more components on the returned entity as needed.
+pub fn spawn_agent<'w>(
+    app: &'w mut App,
+    behavior: &Handle<BehaviorAsset<TestBehavior>>,
+    seed: &BehaviorBlackboard,
+) -> EntityMut<'w> {
+    let script_ctx_handle = app.world.resource_mut::<Assets<ScriptContext>>().add(
+        BehaviorTree::<TestBehavior>::create_script_context_with(seed),
+    );
+    app.world.spawn((
+        script_ctx_handle,
+        behavior.clone(),
+        BehaviorTree::<TestBehavior>::default(),
+        BehaviorTreeReset::<TestBehavior>::default(),
+    ))
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use simula_behavior::{
+    asset::{trim_extension, BINARY_MAGIC},
+    prelude::*,
+    test::*,
+};
+
+const BEHAVIOR: &str = r##"
+(
+    "Greet and count",
+    Sequencer(()),
+    [
+        ("Say hi", Subtree((asset: "#greet")), [], (pos: (120.0, 40.0))),
+        (
+            "Count",
+            Compute((key: (prop: Value("count")), expression: "1 + 1")),
+            [],
+            (pos: (240.0, 80.0)),
+        ),
+    ],
+    (pos: (10.0, 20.0)),
+    {
+        "greet": ("Greet", Debug((message: (prop: Value("Hello"))))),
+    },
+)
+"##;
+
+fn to_ron(behavior: &Behavior<TestBehavior>) -> String {
+    ron::ser::to_string(behavior).unwrap()
+}
+
+fn clear_pos(behavior: &mut Behavior<TestBehavior>) {
+    behavior.attrs_mut().pos = Vec2::ZERO;
+    for node in behavior.nodes_mut() {
+        clear_pos(node);
+    }
+    for inline in behavior.inline_mut().values_mut() {
+        clear_pos(inline);
+    }
+}
+
+#[test]
+fn binary_round_trip() {
+    let mut behavior = ron::de::from_str::<Behavior<TestBehavior>>(BEHAVIOR).unwrap();
+    let bytes = convert_document::<TestBehavior>(BEHAVIOR).unwrap();
+    assert!(bytes.starts_with(BINARY_MAGIC));
+    assert!(bytes.len() < BEHAVIOR.len() / 2);
+
+    let decoded = Behavior::<TestBehavior>::from_binary(&bytes).unwrap();
+    // positions are editor only and left out
+    assert_eq!(decoded.nodes()[0].attrs().pos, Vec2::ZERO);
+    clear_pos(&mut behavior);
+    assert_eq!(to_ron(&decoded), to_ron(&behavior));
+    assert_eq!(decoded.to_binary().unwrap(), bytes);
+}
+
+#[test]
+fn binary_document_loads_either_form() {
+    let bytes = convert_document::<TestBehavior>(BEHAVIOR).unwrap();
+    let binary = BehaviorDocument::from_bytes(&bytes).unwrap();
+    assert!(matches!(binary, BehaviorDocument::Binary(_)));
+    let ron = BehaviorDocument::from_bytes(BEHAVIOR.as_bytes()).unwrap();
+    assert!(matches!(ron, BehaviorDocument::Ron(_)));
+
+    let from_binary = binary.to_behavior::<TestBehavior>().unwrap();
+    let mut from_ron = ron.to_behavior::<TestBehavior>().unwrap();
+    clear_pos(&mut from_ron);
+    assert_eq!(to_ron(&from_binary), to_ron(&from_ron));
+
+    // both run the same
+    let trace = trace_behavior(&to_ron(&from_binary));
+    let expected_trace = trace_behavior(BEHAVIOR);
+    assert_eq!(&trace, &expected_trace);
+    assert_eq!(
+        trace.last().map(String::as_str),
+        Some("[1] SUCCESS Greet and count")
+    );
+}
+
+#[test]
+fn binary_rejects_bad_input() {
+    let mut bytes = convert_document::<TestBehavior>(BEHAVIOR).unwrap();
+    assert!(Behavior::<TestBehavior>::from_binary(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Behavior::<TestBehavior>::from_binary(BEHAVIOR.as_bytes()).is_err());
+    bytes[BINARY_MAGIC.len()] = 99;
+    assert!(Behavior::<TestBehavior>::from_binary(&bytes).is_err());
+    assert!(convert_document::<TestBehavior>("(\"Broken\", Debug(()),").is_err());
+}
+
+#[test]
+fn binary_extension_trimmed() {
+    assert_eq!(trim_extension("behaviors/guard.bht.bin"), "behaviors/guard");
+    assert_eq!(trim_extension("behaviors/guard.bht.ron"), "behaviors/guard");
+    assert_eq!(trim_extension("behaviors/guard"), "behaviors/guard");
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use simula_script::{script::Dynamic, ScriptContext};
+
+// Spawn an agent with a seeded blackboard, returning its script context
+fn spawn_seeded(
+    app: &mut App,
+    behavior_handle: &Handle<BehaviorAsset<TestBehavior>>,
+    seed: BehaviorBlackboard,
+) -> Handle<ScriptContext> {
+    spawn_agent(app, behavior_handle, &seed)
+        .get::<Handle<ScriptContext>>()
+        .unwrap()
+        .clone()
+}
+
+fn blackboard(app: &App, handle: &Handle<ScriptContext>, key: &str) -> Option<Dynamic> {
+    app.world
+        .resource::<Assets<ScriptContext>>()
+        .get(handle)
+        .and_then(|ctx| {
+            ctx.scope
+                .get_value::<simula_script::script::Map>("blackboard")
+        })
+        .and_then(|blackboard| blackboard.get(key).cloned())
+}
+
+#[test]
+fn blackboard_seeds_each_agent() {
+    let behavior = r#"
+    ("Wait", Compute((key: (prop: Value("wait")), expression: "patience * 2 + state")))
+    "#;
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, behavior);
+    let patient = spawn_seeded(
+        &mut app,
+        &behavior_handle,
+        BehaviorBlackboard::default().with("patience", 10_i64),
+    );
+    let impatient = spawn_seeded(
+        &mut app,
+        &behavior_handle,
+        BehaviorBlackboard::default()
+            .with("patience", 1_i64)
+            .with("state", 5_i64),
+    );
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let wait = |handle| {
+        blackboard(&app, handle, "wait")
+            .and_then(|value| value.as_int().ok())
+            .unwrap()
+    };
+    assert_eq!(wait(&patient), 20);
+    // seeded values take precedence over the tree defaults
+    assert_eq!(wait(&impatient), 7);
+}
+
+// Run a behavior for an agent with a seeded blackboard, returning the trace
+fn trace_seeded(behavior: &str, seed: BehaviorBlackboard) -> BehaviorTrace {
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, behavior);
+    spawn_agent(&mut app, &behavior_handle, &seed);
+    for _ in 0..30 {
+        app.update();
+    }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+#[test]
+fn blackboard_gate_flips_with_key() {
+    let behavior = r#"
+    (
+        "Open the door",
+        Sequencer(()),
+        [
+            ("Closed", BlackboardGate((key: (prop: Value("door_open")), value: Bool(false)))),
+            ("Open", Compute((key: (prop: Value("door_open")), expression: "true"))),
+            ("Opened", BlackboardGate((key: (prop: Value("door_open")), value: Bool(true)))),
+            ("Name", Compute((key: (prop: Value("who")), expression: "\"guard\""))),
+            ("Guard", BlackboardGate((key: (prop: Value("who")), value: Str("guard")))),
+            ("Count", BlackboardGate((key: (prop: Value("state")), value: Float(0.0)))),
+        ],
+    )
+    "#;
+    let seed = BehaviorBlackboard::default().with("door_open", false);
+    let trace = trace_seeded(behavior, seed);
+    println!("{:#?}", trace);
+    assert!(trace.iter().any(|line| line.ends_with("SUCCESS Closed")));
+    assert!(trace.iter().any(|line| line.ends_with("SUCCESS Opened")));
+    assert!(trace.iter().any(|line| line.ends_with("SUCCESS Guard")));
+    assert!(trace.last().unwrap().ends_with("SUCCESS Open the door"));
+}
+
+#[test]
+fn blackboard_gate_fails_on_mismatch() {
+    let gate = |value: &str| {
+        let behavior = format!(
+            r#"("Gate", BlackboardGate((key: (prop: Value("door_open")), value: {})))"#,
+            value
+        );
+        let seed = BehaviorBlackboard::default().with("door_open", false);
+        let trace = trace_seeded(&behavior, seed);
+        trace.last().unwrap().ends_with("SUCCESS Gate")
+    };
+    assert!(gate("Bool(false)"));
+    assert!(!gate("Bool(true)"));
+    assert!(!gate("Int(0)"));
+    assert!(!gate(r#"Str("false")"#));
+
+    let missing = r#"("Gate", BlackboardGate((key: (prop: Value("missing")), value: Bool(true))))"#;
+    let trace = trace_seeded(missing, BehaviorBlackboard::default());
+    assert!(trace.last().unwrap().ends_with("FAILURE Gate"));
+}
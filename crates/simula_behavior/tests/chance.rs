@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+#[test]
+fn chance_never_forces() {
+    let behavior = r#"
+    (
+        "Chance",
+        Chance((p: (prop: Value(0.0)))),
+        [
+            ("Action", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Chance",
+        "[2] STARTED Action",
+        "[2] SUCCESS Action",
+        "[1] SUCCESS Chance",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn chance_always_forces_failure() {
+    let behavior = r#"
+    (
+        "Chance",
+        Chance((p: (prop: Value(1.0)), mode: ForceFailure)),
+        [
+            ("Action", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Chance",
+        "[2] STARTED Action",
+        "[2] SUCCESS Action",
+        "[1] FAILURE Chance",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn chance_always_forces_success() {
+    let behavior = r#"
+    (
+        "Chance",
+        Chance((p: (prop: Value(1.0)), mode: ForceSuccess)),
+        [
+            ("Action", Debug((fail: (prop: Value(true))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Chance",
+        "[2] STARTED Action",
+        "[2] FAILURE Action",
+        "[1] SUCCESS Chance",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+// Results of a coin flip chance repeated many times, with a seeded rng
+fn flips(seed: u64) -> Vec<String> {
+    let behavior = r#"
+    (
+        "Flip coins",
+        Repeater((repeat: Times(20))),
+        [
+            (
+                "Chance",
+                Chance((p: (prop: Value(0.5)))),
+                [
+                    ("Action", Debug(())),
+                ]
+            ),
+        ]
+    )
+    "#;
+    let mut app = behavior_app();
+    app.insert_resource(BehaviorRng::seeded(seed));
+    let behavior_handle = add_behavior(&mut app, behavior);
+    spawn_agent(&mut app, &behavior_handle, &default());
+    for _ in 0..200 {
+        app.update();
+    }
+    app.world
+        .resource::<BehaviorTrace>()
+        .iter()
+        .filter(|line| line.ends_with("SUCCESS Chance") || line.ends_with("FAILURE Chance"))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn chance_seeded_rolls_repeat() {
+    let first = flips(7);
+    let second = flips(7);
+    println!("{:#?}", first);
+    assert!(first.len() > 1);
+    assert_eq!(first, second);
+    // A fair coin over many flips lands both ways
+    assert!(first.iter().any(|line| line.ends_with("SUCCESS Chance")));
+    assert!(first.iter().any(|line| line.ends_with("FAILURE Chance")));
+}
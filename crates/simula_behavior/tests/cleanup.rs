@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use simula_behavior::{asset::behavior_tree_cleanup, prelude::*, test::*};
+use simula_script::ScriptContext;
+
+const AGENTS: usize = 50;
+
+#[test]
+fn despawned_agents_leave_no_nodes() {
+    let behavior = r#"
+    (
+        "Patrol",
+        Sequencer(()),
+        [
+            ("Wait", Wait((duration: (prop: Value(100.0))))),
+            ("Action", Debug(())),
+        ]
+    )
+    "#;
+    let mut app = behavior_app();
+    app.add_system(behavior_tree_cleanup::<TestBehavior>);
+    let behavior_handle = add_behavior(&mut app, behavior);
+
+    // Spawn agents, half of them despawned without recursion
+    let agents: Vec<Entity> = (0..AGENTS)
+        .map(|_| spawn_agent(&mut app, &behavior_handle, &default()).id())
+        .collect();
+    let script_ctxs: Vec<Handle<ScriptContext>> = agents
+        .iter()
+        .map(|agent| {
+            app.world
+                .get::<Handle<ScriptContext>>(*agent)
+                .unwrap()
+                .clone_weak()
+        })
+        .collect();
+    for _ in 0..5 {
+        app.update();
+    }
+    let mut nodes = app.world.query::<&BehaviorNode>();
+    assert_eq!(nodes.iter(&app.world).count(), AGENTS * 3);
+
+    for (i, agent) in agents.iter().enumerate() {
+        if i % 2 == 0 {
+            app.world.despawn(*agent);
+        } else {
+            app.world
+                .entity_mut(*agent)
+                .remove::<BehaviorTree<TestBehavior>>();
+        }
+    }
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(nodes.iter(&app.world).count(), 0);
+
+    // Agents that kept living have no behavior state left
+    let mut running = app
+        .world
+        .query_filtered::<Entity, Or<(With<BehaviorCursor>, With<BehaviorRunning>)>>();
+    assert_eq!(running.iter(&app.world).count(), 0);
+
+    // Nor a script context holding their blackboard
+    let mut script_ctx_handles = app.world.query::<&Handle<ScriptContext>>();
+    assert_eq!(script_ctx_handles.iter(&app.world).count(), 0);
+    assert!(script_ctxs.iter().all(|handle| app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(handle)
+        .is_none()));
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+#[derive(Component, Default)]
+struct Fleeing;
+
+#[derive(Component, Default)]
+struct Idle;
+
+#[test]
+fn component_added_and_removed() {
+    let behavior = r#"
+    (
+        "Flee",
+        Sequencer(()),
+        [
+            ("Start fleeing", AddComponent((component: (prop: Value("Fleeing"))))),
+            ("Stop idling", RemoveComponent((component: (prop: Value("Idle"))))),
+        ]
+    )
+    "#;
+    let mut app = behavior_app();
+    let mut components = app.world.resource_mut::<BehaviorComponents>();
+    components.register::<Fleeing>("Fleeing");
+    components.register::<Idle>("Idle");
+    let behavior_handle = add_behavior(&mut app, behavior);
+    let agent = spawn_agent(&mut app, &behavior_handle, &default())
+        .insert(Idle)
+        .id();
+
+    for _ in 0..10 {
+        app.update();
+    }
+    assert!(app
+        .world
+        .resource::<BehaviorTrace>()
+        .iter()
+        .any(|line| line == "[1] SUCCESS Flee"));
+    assert!(app.world.get::<Fleeing>(agent).is_some());
+    assert!(app.world.get::<Idle>(agent).is_none());
+}
+
+#[test]
+fn component_unregistered_fails() {
+    let behavior = r#"
+    (
+        "Add unknown",
+        AddComponent((component: (prop: Value("unknown")))),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Add unknown", "[1] FAILURE Add unknown"]);
+    assert_eq!(&trace, &expected_trace);
+}
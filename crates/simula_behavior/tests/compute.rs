@@ -0,0 +1,46 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn compute_round_trips_blackboard() {
+    let behavior = r#"
+    (
+        "Assess",
+        Sequencer(()),
+        [
+            ("Measure", Compute((key: (prop: Value("dist")), expression: "1.5 * 2.0"))),
+            ("Threat", Compute((key: (prop: Value("threat")), expression: "dist < 5.0"))),
+            (
+                "Check",
+                Compute((
+                    key: (prop: Value("checked")),
+                    expression: "if threat && dist == 3.0 { true } else { missing }",
+                )),
+            ),
+        ],
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Assess",
+        "[2] STARTED Measure",
+        "[2] SUCCESS Measure",
+        "[3] STARTED Threat",
+        "[3] SUCCESS Threat",
+        "[4] STARTED Check",
+        "[4] SUCCESS Check",
+        "[1] SUCCESS Assess",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn compute_error_fails() {
+    let behavior = r#"
+    ("Unknown", Compute((key: (prop: Value("result")), expression: "missing + 1")))
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&["[1] STARTED Unknown", "[1] FAILURE Unknown"]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,56 @@
+use bevy::{prelude::*, utils::Instant};
+use simula_behavior::{prelude::*, test::*};
+use std::time::Duration;
+
+const BEHAVIOR: &str = r#"
+(
+    "Keep attacking",
+    Repeater((repeat: Forever)),
+    [
+        (
+            "Cool off",
+            Cooldown((duration: (prop: Value(1.0)))),
+            [
+                ("Attack", Debug(())),
+            ],
+        ),
+    ],
+)
+"#;
+
+// How many times the child ran after each frame, advancing time by the given frame durations
+fn attacks(frames: &[u64]) -> Vec<usize> {
+    let mut app = behavior_app_manual_time();
+    let behavior_handle = add_behavior(&mut app, BEHAVIOR);
+    spawn_agent(&mut app, &behavior_handle, &default());
+
+    let mut now = Instant::now();
+    let mut counts = vec![];
+    for millis in frames {
+        now += Duration::from_millis(*millis);
+        app.world.resource_mut::<Time>().update_with_instant(now);
+        app.update();
+        let trace = app.world.resource::<BehaviorTrace>();
+        counts.push(
+            trace
+                .iter()
+                .filter(|line| line.ends_with("SUCCESS Attack"))
+                .count(),
+        );
+    }
+    let trace = app.world.resource::<BehaviorTrace>();
+    println!("{:#?}", trace);
+    assert!(trace.iter().any(|line| line.ends_with("FAILURE Cool off")));
+    counts
+}
+
+#[test]
+fn cooldown_runs_cools_down_and_runs_again() {
+    let counts = attacks(&[0, 0, 0, 0, 0, 0, 0, 0, 500, 0, 0, 0, 600, 0, 0, 0, 0, 0]);
+    // runs once, then fails while cooling down
+    assert_eq!(counts[7], 1);
+    // still cooling down half way through
+    assert_eq!(counts[11], 1);
+    // runs again once cooled down, then cools down again
+    assert_eq!(counts[17], 2);
+}
@@ -0,0 +1,80 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn cooldown_ready_multiple_abilities() {
+    let behavior = r#"
+    (
+        "Use fireball",
+        Sequencer(()),
+        [
+            ("Cast fireball", StampNow((key: (prop: Value("fireball"))))),
+            (
+                "Fireball cooling down",
+                Inverter(()),
+                [
+                    (
+                        "Fireball ready",
+                        CooldownReady((
+                            key: (prop: Value("fireball")),
+                            duration: (prop: Value(100.0)),
+                        )),
+                    ),
+                ],
+            ),
+            (
+                "Dash ready",
+                CooldownReady((
+                    key: (prop: Value("dash")),
+                    duration: (prop: Value(100.0)),
+                )),
+            ),
+        ],
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Use fireball",
+        "[2] STARTED Cast fireball",
+        "[2] SUCCESS Cast fireball",
+        "[3] STARTED Fireball cooling down",
+        "[4] STARTED Fireball ready",
+        "[4] FAILURE Fireball ready",
+        "[3] SUCCESS Fireball cooling down",
+        "[5] STARTED Dash ready",
+        "[5] SUCCESS Dash ready",
+        "[1] SUCCESS Use fireball",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn cooldown_ready_elapsed() {
+    let behavior = r#"
+    (
+        "Use dash",
+        Sequencer(()),
+        [
+            ("Dash", StampNow((key: (prop: Value("dash"))))),
+            (
+                "Dash ready",
+                CooldownReady((
+                    key: (prop: Value("dash")),
+                    duration: (prop: Value(0.0)),
+                )),
+            ),
+        ],
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Use dash",
+        "[2] STARTED Dash",
+        "[2] SUCCESS Dash",
+        "[3] STARTED Dash ready",
+        "[3] SUCCESS Dash ready",
+        "[1] SUCCESS Use dash",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*};
+
+fn wait_duration(behaviors: &[TestBehavior]) -> Option<f64> {
+    behaviors.iter().find_map(|behavior| match behavior {
+        TestBehavior::Wait(Wait {
+            duration:
+                BehaviorPropGeneric {
+                    prop: BehaviorEval::Value(duration),
+                    ..
+                },
+            ..
+        }) => Some(*duration),
+        _ => None,
+    })
+}
+
+#[test]
+fn defaults_replace_new_behaviors() {
+    let mut defaults = BehaviorDefaults::<TestBehavior>::default();
+    assert_eq!(wait_duration(&defaults.list()), Some(0.0));
+
+    defaults.set(TestBehavior::Wait(Wait {
+        duration: BehaviorPropGeneric {
+            prop: BehaviorEval::Value(0.5),
+            ..default()
+        },
+        ..default()
+    }));
+    let behaviors = defaults.list();
+    assert_eq!(behaviors.len(), TestBehavior::list().len());
+    assert_eq!(wait_duration(&behaviors), Some(0.5));
+
+    // other behaviors keep their defaults
+    assert!(defaults
+        .get(&TestBehavior::Delay(Delay::default()))
+        .is_none());
+
+    defaults.reset(&TestBehavior::Wait(Wait::default()));
+    assert_eq!(wait_duration(&defaults.list()), Some(0.0));
+}
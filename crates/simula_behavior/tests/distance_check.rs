@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+// Spawn an agent at the origin, with the target entity in its blackboard
+fn distance_app(target: Vec3) -> (App, Entity, Entity) {
+    let behavior = r#"
+    (
+        "Near",
+        DistanceCheck((
+            target: (prop: Value("target")),
+            threshold: (prop: Value(2.0)),
+            comparator: Less,
+        )),
+    )
+    "#;
+    let mut app = behavior_app();
+    let target = app
+        .world
+        .spawn(GlobalTransform::from_translation(target))
+        .id();
+    let behavior_handle = add_behavior(&mut app, behavior);
+    let seed = BehaviorBlackboard::default().with("target", target.to_bits() as i64);
+    let agent = spawn_agent(&mut app, &behavior_handle, &seed)
+        .insert(GlobalTransform::IDENTITY)
+        .id();
+    (app, agent, target)
+}
+
+fn run(app: &mut App) -> BehaviorTrace {
+    for _ in 0..10 {
+        app.update();
+    }
+    std::mem::take(&mut *app.world.resource_mut::<BehaviorTrace>())
+}
+
+#[test]
+fn distance_check_moving_target() {
+    let (mut app, agent, target) = distance_app(Vec3::new(1.0, 0.0, 0.0));
+    let trace = run(&mut app);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&["[2] STARTED Near", "[2] SUCCESS Near"]);
+    assert_eq!(&trace, &expected_trace);
+
+    // Target moves away, rebuild the tree to check again
+    *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+        GlobalTransform::from_translation(Vec3::new(5.0, 0.0, 0.0));
+    app.world
+        .entity_mut(agent)
+        .insert(BehaviorTreeReset::<TestBehavior>::default());
+    let trace = run(&mut app);
+    println!("{:#?}", trace);
+    assert_eq!(trace.len(), 2);
+    assert!(trace[1].ends_with("FAILURE Near"));
+}
+
+#[test]
+fn distance_check_missing_target() {
+    let (mut app, _agent, target) = distance_app(Vec3::ZERO);
+    app.world.despawn(target);
+    let trace = run(&mut app);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&["[2] STARTED Near", "[2] FAILURE Near"]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use simula_script::{script::Map, ScriptContext};
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+struct Enemy;
+
+// Find the nearest enemy among some, from an agent at the origin, returning the trace and
+// the entity found
+fn find_nearest(range: &str, enemies: &[Vec3]) -> (BehaviorTrace, Vec<Entity>, Option<Entity>) {
+    let behavior = format!(
+        r#"
+    (
+        "Target",
+        FindNearest((
+            tag: (prop: Value("Enemy")),
+            range: {},
+            key: (prop: Value("target")),
+        )),
+    )
+    "#,
+        range
+    );
+    let mut app = behavior_app();
+    app.register_type::<Enemy>();
+
+    let enemies = enemies
+        .iter()
+        .map(|enemy| {
+            app.world
+                .spawn((Enemy, GlobalTransform::from_translation(*enemy)))
+                .id()
+        })
+        .collect::<Vec<_>>();
+    // untagged entities are never picked
+    app.world.spawn(GlobalTransform::from_xyz(0.5, 0.0, 0.0));
+
+    let behavior_handle = add_behavior(&mut app, &behavior);
+    let script_ctx_handle = spawn_agent(&mut app, &behavior_handle, &default())
+        .insert(GlobalTransform::IDENTITY)
+        .get::<Handle<ScriptContext>>()
+        .unwrap()
+        .clone();
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let script_ctx = app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(&script_ctx_handle)
+        .unwrap();
+    let blackboard = script_ctx.scope.get_value::<Map>("blackboard").unwrap();
+    let target = blackboard
+        .get("target")
+        .and_then(|target| target.clone().try_cast::<i64>())
+        .map(|bits| Entity::from_bits(bits as u64));
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    (trace, enemies, target)
+}
+
+#[test]
+fn find_nearest_picks_closest() {
+    let enemies = [Vec3::new(0.0, 0.0, 6.0), Vec3::new(-2.0, 0.0, 0.0)];
+    let (trace, enemies, target) = find_nearest("None", &enemies);
+    println!("{:#?}", trace);
+    assert!(trace[1].ends_with("SUCCESS Target"));
+    assert_eq!(target, Some(enemies[1]));
+}
+
+#[test]
+fn find_nearest_breaks_ties_by_lowest_entity() {
+    let enemies = [
+        Vec3::new(0.0, 0.0, 8.0),
+        Vec3::new(3.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, -3.0),
+    ];
+    let (_, enemies, target) = find_nearest("None", &enemies);
+    assert_eq!(target, Some(enemies[1].min(enemies[2])));
+}
+
+#[test]
+fn find_nearest_fails_out_of_range() {
+    let enemies = [Vec3::new(0.0, 0.0, 6.0)];
+    let (trace, _, target) = find_nearest("Some(5.0)", &enemies);
+    println!("{:#?}", trace);
+    assert!(trace[1].ends_with("FAILURE Target"));
+    assert_eq!(target, None);
+
+    let (trace, enemies, target) = find_nearest("Some(6.0)", &enemies);
+    assert!(trace[1].ends_with("SUCCESS Target"));
+    assert_eq!(target, Some(enemies[0]));
+}
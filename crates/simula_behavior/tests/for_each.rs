@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use simula_script::{
+    script::{Dynamic, Map},
+    ScriptContext,
+};
+
+// Run a ForEach over enemies 1, 2 and 3, returning the trace and the blackboard sum
+fn for_each(for_each: &str, child: &str) -> (BehaviorTrace, i64) {
+    let behavior = format!(r#"("Attack all", ForEach({}), [{}])"#, for_each, child);
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, &behavior);
+    let enemies = (1..=3).map(Dynamic::from).collect::<Vec<Dynamic>>();
+    let seed = BehaviorBlackboard::default()
+        .with("enemies", Dynamic::from(enemies))
+        .with("nobody", Dynamic::from(Vec::<Dynamic>::new()))
+        .with("sum", 0_i64);
+    let script_ctx_handle = spawn_agent(&mut app, &behavior_handle, &seed)
+        .get::<Handle<ScriptContext>>()
+        .unwrap()
+        .clone();
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let script_ctx = app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(&script_ctx_handle)
+        .unwrap();
+    let blackboard = script_ctx.scope.get_value::<Map>("blackboard").unwrap();
+    let sum = blackboard.get("sum").unwrap().as_int().unwrap();
+    (app.world.resource::<BehaviorTrace>().clone(), sum)
+}
+
+const FOR_EACH: &str = r#"(list: (prop: Value("enemies")), item: (prop: Value("enemy")))"#;
+
+#[test]
+fn for_each_runs_child_per_element() {
+    let child = r#"("Attack", Compute((key: (prop: Value("sum")), expression: "sum + enemy")))"#;
+    let (trace, sum) = for_each(FOR_EACH, child);
+    println!("{:#?}", trace);
+    assert_eq!(sum, 6);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Attack all",
+        "[2] STARTED Attack",
+        "[2] SUCCESS Attack",
+        "[1] STARTED Attack all",
+        "[2] STARTED Attack",
+        "[2] SUCCESS Attack",
+        "[1] STARTED Attack all",
+        "[2] STARTED Attack",
+        "[2] SUCCESS Attack",
+        "[1] SUCCESS Attack all",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn for_each_snapshots_list() {
+    let child = r#"
+    (
+        "Attack",
+        Sequencer(()),
+        [
+            ("Hit", Compute((key: (prop: Value("sum")), expression: "sum + enemy"))),
+            ("Forget", Compute((key: (prop: Value("enemies")), expression: "[]"))),
+        ],
+    )
+    "#;
+    let (trace, sum) = for_each(FOR_EACH, child);
+    println!("{:#?}", trace);
+    assert_eq!(sum, 6);
+    assert!(trace.last().unwrap().ends_with("SUCCESS Attack all"));
+}
+
+#[test]
+fn for_each_stops_on_failure() {
+    let child = r#"
+    (
+        "Attack",
+        Compute((
+            key: (prop: Value("sum")),
+            expression: "if enemy == 2 { missing } else { sum + enemy }",
+        )),
+    )
+    "#;
+    let (trace, sum) = for_each(FOR_EACH, child);
+    println!("{:#?}", trace);
+    assert_eq!(sum, 1);
+    assert!(trace.last().unwrap().ends_with("FAILURE Attack all"));
+
+    let continue_on_failure = r#"(
+        list: (prop: Value("enemies")),
+        item: (prop: Value("enemy")),
+        continue_on_failure: true,
+    )"#;
+    let (trace, sum) = for_each(continue_on_failure, child);
+    println!("{:#?}", trace);
+    assert_eq!(sum, 4);
+    assert!(trace.last().unwrap().ends_with("SUCCESS Attack all"));
+}
+
+#[test]
+fn for_each_empty_list_succeeds() {
+    let child = r#"("Attack", Compute((key: (prop: Value("sum")), expression: "sum + enemy")))"#;
+    let nobody = r#"(list: (prop: Value("nobody")), item: (prop: Value("enemy")))"#;
+    let (trace, sum) = for_each(nobody, child);
+    assert_eq!(sum, 0);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Attack all", "[1] SUCCESS Attack all"]);
+    assert_eq!(&trace, &expected_trace);
+
+    // not a list at all
+    let missing = r#"(list: (prop: Value("sum")), item: (prop: Value("enemy")))"#;
+    let (trace, _) = for_each(missing, child);
+    assert!(trace.last().unwrap().ends_with("FAILURE Attack all"));
+}
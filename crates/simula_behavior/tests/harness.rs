@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use simula_behavior::{harness::BehaviorTestResult, prelude::*, test::*};
+
+// Write test trees to a fresh directory and run them all
+fn run_tests(name: &str, files: &[(&str, &[u8])], max_ticks: u32) -> BehaviorTestReport {
+    let dir = std::env::temp_dir().join(format!("simula_behavior_{}", name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    for (file_name, contents) in files {
+        std::fs::write(dir.join(file_name), contents).unwrap();
+    }
+
+    let mut app = behavior_app();
+    app.add_plugin(BehaviorTestPlugin::<TestBehavior> {
+        max_ticks,
+        exit: false,
+        ..BehaviorTestPlugin::new(&dir)
+    });
+    for _ in 0..max_ticks + 10 {
+        app.update();
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+    app.world.remove_resource::<BehaviorTestReport>().unwrap()
+}
+
+fn verdict<'a>(report: &'a BehaviorTestReport, name: &str) -> &'a BehaviorTestResult {
+    report
+        .results
+        .iter()
+        .find(|result| result.name == name)
+        .unwrap()
+}
+
+const PASS: &str = r#"
+(
+    "Count",
+    Sequencer(()),
+    [
+        ("Set", Compute((key: (prop: Value("count")), expression: "1"))),
+        ("Add", Compute((key: (prop: Value("count")), expression: "count + 1"))),
+    ],
+)
+"#;
+
+const FAIL: &str = r#"
+(
+    "Count",
+    Sequencer(()),
+    [
+        ("Set", Compute((key: (prop: Value("count")), expression: "1"))),
+        ("Missing", Compute((key: (prop: Value("count")), expression: "missing + 1"))),
+    ],
+)
+"#;
+
+#[test]
+fn harness_reports_verdicts() {
+    let binary = convert_document::<TestBehavior>(PASS).unwrap();
+    let report = run_tests(
+        "verdicts",
+        &[
+            ("pass.bht.ron", PASS.as_bytes()),
+            ("pass_binary.bht.bin", &binary),
+            ("fail.bht.ron", FAIL.as_bytes()),
+            ("broken.bht.ron", b"(\"Broken\", Debug(()),"),
+            ("notes.txt", b"not a behavior"),
+        ],
+        50,
+    );
+    println!("{}", report.summary());
+    assert!(report.done);
+    assert_eq!(report.results.len(), 4);
+    assert_eq!(report.passed(), 2);
+    assert_eq!(report.failed(), 2);
+    assert_eq!(report.exit_code(), 1);
+
+    assert_eq!(verdict(&report, "pass").verdict, BehaviorVerdict::Passed);
+    assert_eq!(
+        verdict(&report, "pass_binary").verdict,
+        BehaviorVerdict::Passed
+    );
+    let BehaviorVerdict::Failed(reason) = &verdict(&report, "fail").verdict else {
+        panic!("Failing tree passed");
+    };
+    assert!(reason.contains("missing"));
+    let BehaviorVerdict::Failed(reason) = &verdict(&report, "broken").verdict else {
+        panic!("Broken tree passed");
+    };
+    assert!(reason.starts_with("Cannot load"));
+    assert!(report.summary().ends_with("2 passed, 2 failed"));
+}
+
+#[test]
+fn harness_times_out() {
+    let hang = r#"("Hang", Wait((duration: (prop: Value(1000.0)))))"#;
+    let report = run_tests(
+        "timeout",
+        &[
+            ("hang.bht.ron", hang.as_bytes()),
+            ("pass.bht.ron", PASS.as_bytes()),
+        ],
+        20,
+    );
+    println!("{}", report.summary());
+    assert_eq!(verdict(&report, "pass").verdict, BehaviorVerdict::Passed);
+    assert!(verdict(&report, "pass").ticks < 20);
+    assert_eq!(
+        verdict(&report, "hang").verdict,
+        BehaviorVerdict::Failed("Timed out after 20 ticks".to_string())
+    );
+    assert_eq!(report.exit_code(), 1);
+}
+
+#[test]
+fn harness_passes_all() {
+    let report = run_tests("passes", &[("pass.bht.ron", PASS.as_bytes())], 20);
+    assert_eq!(report.exit_code(), 0);
+    assert!(report.summary().starts_with("PASS pass"));
+}
@@ -0,0 +1,39 @@
+use simula_behavior::{
+    inspector::history::BehaviorHistory,
+    protocol::{BehaviorState, BehaviorTelemetry},
+    test::*,
+};
+use std::time::Duration;
+
+fn frame(state: BehaviorState) -> BehaviorTelemetry<TestBehavior> {
+    BehaviorTelemetry(None, state, None, vec![], None, None, 0.0)
+}
+
+#[test]
+fn history_steps_back_to_past_frames() {
+    let mut history = BehaviorHistory::<TestBehavior>::default();
+    history.record(Duration::from_secs(1), frame(BehaviorState::Running));
+    history.record(Duration::from_secs(2), frame(BehaviorState::Success));
+    assert!(!history.is_past());
+    assert_eq!(history.frame().unwrap().1 .1, BehaviorState::Success);
+
+    // between frames, the one before is shown
+    history.viewing = Some(Duration::from_millis(1500));
+    assert!(history.is_past());
+    assert_eq!(history.frame().unwrap().0, Duration::from_secs(1));
+
+    // recording keeps showing the past
+    history.record(Duration::from_secs(3), frame(BehaviorState::Failure));
+    assert_eq!(history.frame().unwrap().1 .1, BehaviorState::Running);
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut history = BehaviorHistory::<TestBehavior>::default();
+    history.record(Duration::from_secs(1), frame(BehaviorState::Running));
+    history.viewing = Some(Duration::from_secs(1));
+    history.record(Duration::from_secs(60), frame(BehaviorState::Success));
+    assert_eq!(history.frames.len(), 1);
+    // the viewed frame went away, the oldest left is shown
+    assert_eq!(history.viewing, Some(Duration::from_secs(60)));
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use simula_behavior::{
+    asset::{behavior_tree_hot_reload, behavior_tree_reset},
+    prelude::*,
+    test::*,
+};
+use simula_script::ScriptContext;
+
+fn node_names(app: &mut App, tree: Entity) -> Vec<String> {
+    let mut names = app
+        .world
+        .query::<(&Name, &BehaviorNode)>()
+        .iter(&app.world)
+        .filter(|(_, node)| node.tree == tree)
+        .map(|(name, _)| name.to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn hot_reload_rebuilds_modified_trees() {
+    let behavior = r#"
+    (
+        "Patrol",
+        Sequencer(()),
+        [
+            ("Wait", Wait((duration: (prop: Value(100.0))))),
+            ("Action", Debug(())),
+        ]
+    )
+    "#;
+    let mut app = behavior_app();
+    app.add_system(
+        behavior_tree_hot_reload::<TestBehavior>.before(behavior_tree_reset::<TestBehavior>),
+    );
+    let behavior_handle = add_behavior(&mut app, behavior);
+
+    // Two agents share the behavior, only one of them hot reloads
+    let mut spawn = |tree: BehaviorTree<TestBehavior>| {
+        let seed = BehaviorBlackboard::default().with("kept", 7_i64);
+        let mut agent = spawn_agent(&mut app, &behavior_handle, &seed);
+        agent.insert(tree);
+        let script_ctx_handle = agent.get::<Handle<ScriptContext>>().unwrap().clone();
+        (agent.id(), script_ctx_handle)
+    };
+    let (hot, hot_script_ctx) = spawn(BehaviorTree::with_hot_reload());
+    let (cold, _) = spawn(BehaviorTree::default());
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(node_names(&mut app, hot), vec!["Action", "Patrol", "Wait"]);
+
+    // Modify the asset, as the asset server does when the file changes
+    let reloaded = r#"("Guard post", Wait((duration: (prop: Value(100.0)))))"#;
+    app.world
+        .resource_mut::<Assets<BehaviorAsset<TestBehavior>>>()
+        .get_mut(&behavior_handle)
+        .unwrap()
+        .behavior = ron::de::from_str::<Behavior<TestBehavior>>(reloaded).unwrap();
+    for _ in 0..5 {
+        app.update();
+    }
+
+    // The tree is rebuilt and running again, old nodes are gone
+    assert_eq!(node_names(&mut app, hot), vec!["Guard post"]);
+    let running = app
+        .world
+        .query_filtered::<&Name, With<BehaviorRunning>>()
+        .iter(&app.world)
+        .any(|name| name.as_str() == "Guard post");
+    assert!(running);
+    assert_eq!(node_names(&mut app, cold), vec!["Action", "Patrol", "Wait"]);
+    assert_eq!(
+        app.world.query::<&BehaviorNode>().iter(&app.world).count(),
+        4
+    );
+
+    // The tree entity keeps its script context and blackboard
+    assert_eq!(
+        app.world.get::<Handle<ScriptContext>>(hot),
+        Some(&hot_script_ctx)
+    );
+    let kept = app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(&hot_script_ctx)
+        .and_then(|ctx| {
+            ctx.scope
+                .get_value::<simula_script::script::Map>("blackboard")
+        })
+        .and_then(|blackboard| blackboard.get("kept").cloned())
+        .and_then(|value| value.as_int().ok());
+    assert_eq!(kept, Some(7));
+}
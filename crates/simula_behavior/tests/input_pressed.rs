@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use simula_action::Action;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+// Run an InputPressed for an action name, with the space key held down
+fn input_trace(action: &str) -> BehaviorTrace {
+    let behavior = format!(
+        r#"("Jump?", InputPressed((action: (prop: Value("{}")))))"#,
+        action
+    );
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, &behavior);
+
+    let mut keys = Action::<KeyCode>::default();
+    keys.enter(KeyCode::Space);
+    app.world.spawn(keys);
+
+    spawn_agent(&mut app, &behavior_handle, &default());
+    for _ in 0..10 {
+        app.update();
+    }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+#[test]
+fn input_pressed_succeeds_while_on() {
+    let trace = input_trace("Space");
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&["[2] STARTED Jump?", "[2] SUCCESS Jump?"]);
+    assert_eq!(&trace, &expected_trace);
+
+    let trace = input_trace("KeyCode::Space");
+    assert!(trace[1].ends_with("SUCCESS Jump?"));
+}
+
+#[test]
+fn input_pressed_fails_while_off() {
+    let trace = input_trace("Return");
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&["[2] STARTED Jump?", "[2] FAILURE Jump?"]);
+    assert_eq!(&trace, &expected_trace);
+
+    // qualified by the wrong action type
+    let trace = input_trace("MouseButton::Space");
+    assert!(trace[1].ends_with("FAILURE Jump?"));
+}
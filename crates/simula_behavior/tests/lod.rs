@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+fn lod_app(camera: Vec3) -> App {
+    let behavior = r#"("Action", Debug(()))"#;
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, behavior);
+    app.world
+        .spawn((Camera::default(), GlobalTransform::from_translation(camera)));
+    spawn_agent(&mut app, &behavior_handle, &default()).insert((
+        GlobalTransform::IDENTITY,
+        BehaviorLod {
+            cull_distance: 10.0,
+            ..default()
+        },
+    ));
+    app
+}
+
+#[test]
+fn lod_near_agent_runs() {
+    let mut app = lod_app(Vec3::new(0.0, 0.0, 5.0));
+    for _ in 0..10 {
+        app.update();
+    }
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    let expected_trace = BehaviorTrace::from_list(&["[2] STARTED Action", "[2] SUCCESS Action"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn lod_culled_agent_pauses() {
+    let mut app = lod_app(Vec3::new(0.0, 0.0, 50.0));
+    for _ in 0..10 {
+        app.update();
+    }
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    let expected_trace = BehaviorTrace::from_list(&["[2] STARTED Action"]);
+    assert_eq!(&trace, &expected_trace);
+
+    // Camera moves close, the agent resumes where it was
+    let mut cameras = app
+        .world
+        .query_filtered::<&mut GlobalTransform, With<Camera>>();
+    *cameras.single_mut(&mut app.world) = GlobalTransform::IDENTITY;
+    for _ in 0..10 {
+        app.update();
+    }
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    let expected_trace = BehaviorTrace::from_list(&["[2] STARTED Action", "[2] SUCCESS Action"]);
+    assert_eq!(&trace, &expected_trace);
+}
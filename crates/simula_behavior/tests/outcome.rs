@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*};
+
+fn outcome_app(behavior: &str) -> App {
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, behavior);
+    spawn_agent(&mut app, &behavior_handle, &default());
+    for _ in 0..20 {
+        app.update();
+    }
+    app
+}
+
+fn outcome(app: &mut App, name: &str) -> Option<BehaviorOutcome> {
+    app.world
+        .query::<(&Name, Option<&BehaviorOutcome>)>()
+        .iter(&app.world)
+        .find(|(node_name, _)| node_name.as_str() == name)
+        .and_then(|(_, outcome)| outcome.cloned())
+}
+
+#[test]
+fn outcome_propagates_to_root() {
+    let behavior = r#"
+    (
+        "Assess",
+        Sequencer(()),
+        [
+            ("Greet", Debug(())),
+            ("Measure", Compute((key: (prop: Value("dist")), expression: "missing + 1"))),
+        ]
+    )
+    "#;
+    let mut app = outcome_app(behavior);
+    let measure = outcome(&mut app, "Measure").unwrap();
+    assert!(!measure.reason.is_empty());
+    assert_eq!(outcome(&mut app, "Assess"), Some(measure));
+    assert_eq!(outcome(&mut app, "Greet"), None);
+}
+
+#[test]
+fn outcome_not_inherited_across_results() {
+    let behavior = r#"
+    (
+        "Fallback",
+        Selector(()),
+        [
+            ("Measure", Compute((key: (prop: Value("dist")), expression: "missing + 1"))),
+            ("Greet", Debug(())),
+        ]
+    )
+    "#;
+    let mut app = outcome_app(behavior);
+    assert!(outcome(&mut app, "Measure").is_some());
+    assert_eq!(outcome(&mut app, "Fallback"), None);
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use simula_script::{script::Map, ScriptContext};
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+struct Enemy;
+
+// Spawn an agent at the origin, facing -Z, and enemies and neutral entities around it
+fn overlap_app(shape: &str) -> (App, Handle<ScriptContext>, Entity) {
+    let behavior = format!(
+        r#"
+    (
+        "Perceive",
+        Overlap((
+            tag: (prop: Value("Enemy")),
+            shape: {},
+            offset: (0.0, 0.0, -2.0),
+            key: (prop: Value("hit")),
+        )),
+    )
+    "#,
+        shape
+    );
+    let mut app = behavior_app();
+    app.register_type::<Enemy>();
+
+    let near = app
+        .world
+        .spawn((Enemy, GlobalTransform::from_xyz(0.0, 0.0, -2.5)))
+        .id();
+    app.world
+        .spawn((Enemy, GlobalTransform::from_xyz(0.0, 0.0, -6.0)));
+    app.world.spawn(GlobalTransform::from_xyz(0.0, 0.0, -2.0));
+
+    let behavior_handle = add_behavior(&mut app, &behavior);
+    let script_ctx_handle = spawn_agent(&mut app, &behavior_handle, &default())
+        .insert(GlobalTransform::IDENTITY)
+        .get::<Handle<ScriptContext>>()
+        .unwrap()
+        .clone();
+    (app, script_ctx_handle, near)
+}
+
+fn run(app: &mut App) -> BehaviorTrace {
+    for _ in 0..10 {
+        app.update();
+    }
+    std::mem::take(&mut *app.world.resource_mut::<BehaviorTrace>())
+}
+
+fn hit(app: &App, script_ctx_handle: &Handle<ScriptContext>) -> Option<Entity> {
+    let script_ctx = app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(script_ctx_handle)
+        .unwrap();
+    let blackboard = script_ctx.scope.get_value::<Map>("blackboard").unwrap();
+    blackboard
+        .get("hit")
+        .and_then(|hit| hit.clone().try_cast::<i64>())
+        .map(|bits| Entity::from_bits(bits as u64))
+}
+
+#[test]
+fn overlap_sphere_hits_nearest_tagged() {
+    let (mut app, script_ctx_handle, near) = overlap_app("Sphere(radius: 5.0)");
+    let trace = run(&mut app);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[4] STARTED Perceive", "[4] SUCCESS Perceive"]);
+    assert_eq!(&trace, &expected_trace);
+    assert_eq!(hit(&app, &script_ctx_handle), Some(near));
+}
+
+#[test]
+fn overlap_box_misses() {
+    let (mut app, script_ctx_handle, _) = overlap_app("Box(half_extents: (1.0, 1.0, 0.25))");
+    let trace = run(&mut app);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[4] STARTED Perceive", "[4] FAILURE Perceive"]);
+    assert_eq!(&trace, &expected_trace);
+    assert_eq!(hit(&app, &script_ctx_handle), None);
+}
+
+#[test]
+fn overlap_box_hits() {
+    let (mut app, script_ctx_handle, near) = overlap_app("Box(half_extents: (1.0, 1.0, 1.0))");
+    let trace = run(&mut app);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[4] STARTED Perceive", "[4] SUCCESS Perceive"]);
+    assert_eq!(&trace, &expected_trace);
+    assert_eq!(hit(&app, &script_ctx_handle), Some(near));
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+// A wait that panics while running, and its trace
+fn panicking_wait(behavior: &str) -> BehaviorTrace {
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, behavior);
+    app.add_system(panic_safe::<Wait, _>(
+        |waits: Query<&Wait, BehaviorRunQuery>| {
+            if !waits.is_empty() {
+                panic!("Wait is broken");
+            }
+        },
+    ));
+    spawn_agent(&mut app, &behavior_handle, &default());
+    for _ in 0..10 {
+        app.update();
+    }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+#[test]
+fn panic_safe_fails_panicking_nodes() {
+    let behavior = r#"
+    (
+        "Try",
+        Selector(()),
+        [
+            ("Broken", Wait((duration: (prop: Value(100.0))))),
+            ("Fallback", Debug(())),
+        ]
+    )
+    "#;
+    let trace = panicking_wait(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Try",
+        "[2] STARTED Broken",
+        "[2] FAILURE Broken",
+        "[3] STARTED Fallback",
+        "[3] SUCCESS Fallback",
+        "[1] SUCCESS Try",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,71 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn parallel_two_of_three() {
+    let behavior = r#"
+    (
+        "Run until two good",
+        Parallel((success_threshold: 2, failure_threshold: 2)),
+        [
+            ("Do a thing", Debug(())),
+            ("Do another", Debug((fail: true))),
+            ("Do more", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    assert_eq!(
+        &trace[..4],
+        &BehaviorTrace::from_list(&[
+            "[1] STARTED Run until two good",
+            "[2] STARTED Do a thing",
+            "[3] STARTED Do another",
+            "[4] STARTED Do more",
+        ])[..]
+    );
+    assert_eq!(trace.last().unwrap(), "[1] SUCCESS Run until two good");
+}
+
+#[test]
+fn parallel_stops_running_children() {
+    let behavior = r#"
+    (
+        "Run until two good",
+        Parallel((success_threshold: 2)),
+        [
+            ("Do a thing", Debug(())),
+            ("Wait", Wait((duration: (prop: Value(100.0))))),
+            ("Do more", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    assert_eq!(trace.last().unwrap(), "[1] SUCCESS Run until two good");
+    assert!(trace.iter().any(|line| line == "[3] STARTED Wait"));
+    assert!(!trace
+        .iter()
+        .any(|line| line.ends_with("SUCCESS Wait") || line.ends_with("FAILURE Wait")));
+}
+
+#[test]
+fn parallel_fails_early() {
+    let behavior = r#"
+    (
+        "Run until two bad",
+        Parallel((success_threshold: 2, failure_threshold: 2)),
+        [
+            ("Do a thing", Debug((fail: true))),
+            ("Do another", Debug((fail: true))),
+            ("Wait", Wait((duration: (prop: Value(100.0))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    assert_eq!(trace.last().unwrap(), "[1] FAILURE Run until two bad");
+    assert!(!trace
+        .iter()
+        .any(|line| line.ends_with("SUCCESS Wait") || line.ends_with("FAILURE Wait")));
+}
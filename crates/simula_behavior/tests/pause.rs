@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use simula_behavior::{asset::behavior_tree_pause, prelude::*, test::*, BehaviorTrace};
+
+fn pause_app(agents: usize) -> App {
+    let behavior = r#"("Action", Debug(()))"#;
+    let mut app = behavior_app();
+    app.init_resource::<BehaviorTreePaused<TestBehavior>>();
+    app.add_system(behavior_tree_pause::<TestBehavior>.in_base_set(CoreSet::PreUpdate));
+    let behavior_handle = add_behavior(&mut app, behavior);
+    for _ in 0..agents {
+        spawn_agent(&mut app, &behavior_handle, &default());
+    }
+    app
+}
+
+fn count(app: &App, state: &str) -> usize {
+    app.world
+        .resource::<BehaviorTrace>()
+        .iter()
+        .filter(|line| line.contains(state))
+        .count()
+}
+
+#[test]
+fn pause_holds_all_agents() {
+    let mut app = pause_app(3);
+    set_paused(&mut app, true);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(count(&app, "SUCCESS"), 0);
+
+    // Resumed agents complete where they were, without restarting
+    set_paused(&mut app, false);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(count(&app, "SUCCESS"), 3);
+    assert!(count(&app, "STARTED") <= 3);
+}
+
+#[test]
+fn unpaused_agents_run() {
+    let mut app = pause_app(3);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(count(&app, "SUCCESS"), 3);
+}
+
+// One agent with level of detail, culled when the camera is over 10 units away
+fn pause_lod_app(camera: Vec3) -> App {
+    let mut app = pause_app(0);
+    let behavior_handle = add_behavior(&mut app, r#"("Action", Debug(()))"#);
+    app.world
+        .spawn((Camera::default(), GlobalTransform::from_translation(camera)));
+    spawn_agent(&mut app, &behavior_handle, &default()).insert((
+        GlobalTransform::IDENTITY,
+        BehaviorLod {
+            cull_distance: 10.0,
+            ..default()
+        },
+    ));
+    app
+}
+
+fn set_paused(app: &mut App, paused: bool) {
+    app.world
+        .resource_mut::<BehaviorTreePaused<TestBehavior>>()
+        .paused = paused;
+}
+
+#[test]
+fn pause_holds_lod_agents() {
+    let mut app = pause_lod_app(Vec3::ZERO);
+    set_paused(&mut app, true);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(count(&app, "SUCCESS"), 0);
+
+    set_paused(&mut app, false);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(count(&app, "SUCCESS"), 1);
+}
+
+#[test]
+fn resume_keeps_lod_culled_agents() {
+    let mut app = pause_lod_app(Vec3::new(0.0, 0.0, 50.0));
+    set_paused(&mut app, true);
+    for _ in 0..10 {
+        app.update();
+    }
+    set_paused(&mut app, false);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(count(&app, "SUCCESS"), 0);
+
+    // Camera moves close, the agent runs again
+    let mut cameras = app
+        .world
+        .query_filtered::<&mut GlobalTransform, With<Camera>>();
+    *cameras.single_mut(&mut app.world) = GlobalTransform::IDENTITY;
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(count(&app, "SUCCESS"), 1);
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*};
+
+fn profiler_app(enabled: bool) -> App {
+    let behavior = r#"("Wait", Wait((duration: (prop: Value(100.0)))))"#;
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, behavior);
+    app.insert_resource(BehaviorProfiler {
+        enabled,
+        ..default()
+    });
+    app.add_systems(profiled::<Wait, _>(|| {}));
+    spawn_agent(&mut app, &behavior_handle, &default());
+    app
+}
+
+fn costs(app: &mut App) -> Vec<f32> {
+    app.world
+        .query_filtered::<&BehaviorCost, With<Wait>>()
+        .iter(&app.world)
+        .map(|cost| cost.0)
+        .collect()
+}
+
+#[test]
+fn profiler_measures_running_nodes() {
+    let mut app = profiler_app(true);
+    for _ in 0..10 {
+        app.update();
+    }
+    let costs = costs(&mut app);
+    assert_eq!(costs.len(), 1);
+    assert!(costs[0] >= 0.0);
+}
+
+#[test]
+fn profiler_disabled_measures_nothing() {
+    let mut app = profiler_app(false);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert!(costs(&mut app).is_empty());
+}
@@ -0,0 +1,59 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use simula_behavior::{test::*, BehaviorTrace};
+
+fn random_selector(seed: u64, fail: [bool; 4]) -> String {
+    format!(
+        r#"
+        (
+            "Pick one",
+            RandomSelector((seed: Some({}))),
+            [
+                ("A", Debug((fail: (prop: Value({}))))),
+                ("B", Debug((fail: (prop: Value({}))))),
+                ("C", Debug((fail: (prop: Value({}))))),
+                ("D", Debug((fail: (prop: Value({}))))),
+            ]
+        )
+        "#,
+        seed, fail[0], fail[1], fail[2], fail[3]
+    )
+}
+
+// Children in the order they were started
+fn visited(trace: &BehaviorTrace) -> Vec<String> {
+    trace
+        .iter()
+        .filter_map(|line| line.split_once(" STARTED ").map(|(_, name)| name))
+        .filter(|name| *name != "Pick one")
+        .map(|name| name.to_string())
+        .collect()
+}
+
+#[test]
+fn random_selector_order_is_seeded() {
+    let trace = trace_behavior(&random_selector(7, [true; 4]));
+    println!("{:#?}", trace);
+    assert!(trace.last().unwrap().ends_with("FAILURE Pick one"));
+
+    // every child tried once, in the order shuffled by the seeded rng
+    let mut expected = vec!["A", "B", "C", "D"];
+    expected.shuffle(&mut StdRng::seed_from_u64(7));
+    assert_eq!(visited(&trace), expected);
+
+    let again = trace_behavior(&random_selector(7, [true; 4]));
+    assert_eq!(visited(&again), visited(&trace));
+}
+
+#[test]
+fn random_selector_stops_on_success() {
+    let mut order = vec![0, 1, 2, 3];
+    order.shuffle(&mut StdRng::seed_from_u64(3));
+    // only the third child in the shuffled order succeeds
+    let mut fail = [true; 4];
+    fail[order[2]] = false;
+
+    let trace = trace_behavior(&random_selector(3, fail));
+    println!("{:#?}", trace);
+    assert!(trace.last().unwrap().ends_with("SUCCESS Pick one"));
+    assert_eq!(visited(&trace).len(), 3);
+}
@@ -0,0 +1,111 @@
+use simula_behavior::test::*;
+
+fn guarded_sequence(sequencer: &str) -> String {
+    format!(
+        r#"
+    (
+        "Root",
+        Sequencer(()),
+        [
+            ("Arm", Compute((key: (prop: Value("armed")), expression: "true"))),
+            (
+                "Guarded",
+                {},
+                [
+                    ("Armed", BlackboardGate((key: (prop: Value("armed")), value: Bool(true)))),
+                    (
+                        "Work",
+                        Sequencer(()),
+                        [
+                            ("Disarm", Compute((key: (prop: Value("armed")), expression: "false"))),
+                            ("Step 1", Yield(())),
+                            ("Step 2", Yield(())),
+                            ("Step 3", Yield(())),
+                            ("Step 4", Yield(())),
+                        ]
+                    ),
+                ]
+            ),
+        ]
+    )
+    "#,
+        sequencer
+    )
+}
+
+fn alarmed_selector(selector: &str) -> String {
+    format!(
+        r#"
+    (
+        "Root",
+        Sequencer(()),
+        [
+            ("Calm", Compute((key: (prop: Value("alarm")), expression: "false"))),
+            (
+                "React",
+                {},
+                [
+                    ("Alarm", BlackboardGate((key: (prop: Value("alarm")), value: Bool(true)))),
+                    (
+                        "Patrol",
+                        Sequencer(()),
+                        [
+                            ("Raise", Compute((key: (prop: Value("alarm")), expression: "true"))),
+                            ("Walk 1", Yield(())),
+                            ("Walk 2", Yield(())),
+                            ("Walk 3", Yield(())),
+                            ("Walk 4", Yield(())),
+                        ]
+                    ),
+                ]
+            ),
+        ]
+    )
+    "#,
+        selector
+    )
+}
+
+fn count(trace: &[String], line: &str) -> usize {
+    trace.iter().filter(|l| *l == line).count()
+}
+
+#[test]
+fn sequence_with_memory_skips_condition() {
+    let trace = trace_behavior(&guarded_sequence("Sequencer(())"));
+    println!("{:#?}", trace);
+    assert_eq!(count(&trace, "[4] STARTED Armed"), 1);
+    assert_eq!(count(&trace, "[5] SUCCESS Work"), 1);
+    assert_eq!(trace.last().unwrap(), "[1] SUCCESS Root");
+}
+
+#[test]
+fn reactive_sequence_reevaluates_condition() {
+    let trace = trace_behavior(&guarded_sequence("Sequencer((memory: false))"));
+    println!("{:#?}", trace);
+    assert!(count(&trace, "[4] STARTED Armed") > 1);
+    assert_eq!(count(&trace, "[4] FAILURE Armed"), 1);
+    assert_eq!(count(&trace, "[5] SUCCESS Work"), 0);
+    assert_eq!(count(&trace, "[3] FAILURE Guarded"), 1);
+    assert_eq!(trace.last().unwrap(), "[1] FAILURE Root");
+}
+
+#[test]
+fn selector_with_memory_skips_condition() {
+    let trace = trace_behavior(&alarmed_selector("Selector(())"));
+    println!("{:#?}", trace);
+    assert_eq!(count(&trace, "[4] STARTED Alarm"), 1);
+    assert_eq!(count(&trace, "[5] SUCCESS Patrol"), 1);
+    assert_eq!(trace.last().unwrap(), "[1] SUCCESS Root");
+}
+
+#[test]
+fn reactive_selector_reevaluates_condition() {
+    let trace = trace_behavior(&alarmed_selector("Selector((memory: false))"));
+    println!("{:#?}", trace);
+    assert!(count(&trace, "[4] STARTED Alarm") > 1);
+    assert_eq!(count(&trace, "[4] SUCCESS Alarm"), 1);
+    assert_eq!(count(&trace, "[5] SUCCESS Patrol"), 0);
+    assert_eq!(count(&trace, "[3] SUCCESS React"), 1);
+    assert_eq!(trace.last().unwrap(), "[1] SUCCESS Root");
+}
@@ -0,0 +1,72 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn retry_stops_after_cap() {
+    let behavior = r#"
+    (
+        "Retry flaky",
+        Retry((max_retries: (prop: Value(2)), backoff: (prop: Value(0.0)))),
+        [
+            ("Flaky", Debug((fail: (prop: Value(true))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Retry flaky",
+        "[2] STARTED Flaky",
+        "[2] FAILURE Flaky",
+        "[1] STARTED Retry flaky",
+        "[2] STARTED Flaky",
+        "[2] FAILURE Flaky",
+        "[1] STARTED Retry flaky",
+        "[2] STARTED Flaky",
+        "[2] FAILURE Flaky",
+        "[1] FAILURE Retry flaky",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn retry_succeeds_first_time() {
+    let behavior = r#"
+    (
+        "Retry flaky",
+        Retry((max_retries: (prop: Value(2)), backoff: (prop: Value(0.0)))),
+        [
+            ("Flaky", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Retry flaky",
+        "[2] STARTED Flaky",
+        "[2] SUCCESS Flaky",
+        "[1] SUCCESS Retry flaky",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn retry_waits_for_backoff() {
+    let behavior = r#"
+    (
+        "Retry flaky",
+        Retry((max_retries: (prop: Value(2)), backoff: (prop: Value(100.0)))),
+        [
+            ("Flaky", Debug((fail: (prop: Value(true))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Retry flaky",
+        "[2] STARTED Flaky",
+        "[2] FAILURE Flaky",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,74 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn retry_until_success_stops_after_attempts() {
+    let behavior = r#"
+    (
+        "Keep trying",
+        RetryUntilSuccess((max_attempts: Some(3))),
+        [
+            ("Flaky", Debug((fail: (prop: Value(true))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Keep trying",
+        "[2] STARTED Flaky",
+        "[2] FAILURE Flaky",
+        "[1] STARTED Keep trying",
+        "[2] STARTED Flaky",
+        "[2] FAILURE Flaky",
+        "[1] STARTED Keep trying",
+        "[2] STARTED Flaky",
+        "[2] FAILURE Flaky",
+        "[1] FAILURE Keep trying",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn retry_until_success_succeeds_with_child() {
+    let behavior = r#"
+    (
+        "Keep trying",
+        RetryUntilSuccess((max_attempts: Some(3))),
+        [
+            ("Flaky", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Keep trying",
+        "[2] STARTED Flaky",
+        "[2] SUCCESS Flaky",
+        "[1] SUCCESS Keep trying",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn retry_until_success_without_cap_keeps_retrying() {
+    let behavior = r#"
+    (
+        "Keep trying",
+        RetryUntilSuccess(()),
+        [
+            ("Flaky", Debug((fail: (prop: Value(true))))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    let failures = trace
+        .iter()
+        .filter(|line| line.ends_with("FAILURE Flaky"))
+        .count();
+    assert!(failures > 10);
+    // never completes
+    assert!(!trace
+        .iter()
+        .any(|line| line.ends_with("Keep trying") && !line.contains("STARTED")));
+}
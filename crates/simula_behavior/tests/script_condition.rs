@@ -0,0 +1,54 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+fn condition_trace(x: i64, expression: &str) -> BehaviorTrace {
+    let behavior = format!(
+        r#"
+    (
+        "Check x",
+        Sequencer(()),
+        [
+            ("Set x", Compute((key: (prop: Value("x")), expression: "{}"))),
+            ("Is x big", ScriptCondition((expression: "{}"))),
+        ],
+    )
+    "#,
+        x, expression
+    );
+    let trace = trace_behavior(&behavior);
+    println!("{:#?}", trace);
+    trace
+}
+
+#[test]
+fn script_condition_true_succeeds() {
+    let trace = condition_trace(5, "x > 3");
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Check x",
+        "[2] STARTED Set x",
+        "[2] SUCCESS Set x",
+        "[3] STARTED Is x big",
+        "[3] SUCCESS Is x big",
+        "[1] SUCCESS Check x",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn script_condition_false_fails() {
+    let trace = condition_trace(1, "x > 3");
+    assert_eq!(trace.last().unwrap(), "[1] FAILURE Check x");
+    assert!(trace.iter().any(|line| line == "[3] FAILURE Is x big"));
+}
+
+#[test]
+fn script_condition_error_fails() {
+    // Runtime error
+    let trace = condition_trace(5, "missing > 3");
+    assert!(trace.iter().any(|line| line == "[3] FAILURE Is x big"));
+    // Parse error
+    let trace = condition_trace(5, "x >");
+    assert!(trace.iter().any(|line| line == "[3] FAILURE Is x big"));
+    // Not a boolean
+    let trace = condition_trace(5, "x + 3");
+    assert!(trace.iter().any(|line| line == "[3] FAILURE Is x big"));
+}
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use simula_script::{script::Map, ScriptContext};
+
+#[derive(Component)]
+struct Crate;
+
+// App with a crate prefab registered, and an agent at `origin` running the behavior
+fn spawn_app(behavior: &str, origin: Vec3) -> (App, Entity) {
+    let mut app = behavior_app();
+    app.world
+        .resource_mut::<BehaviorSpawners>()
+        .register_fn("crate", |commands, transform| {
+            commands
+                .spawn((Crate, TransformBundle::from_transform(transform)))
+                .id()
+        });
+    let behavior_handle = add_behavior(&mut app, behavior);
+    let agent = spawn_agent(&mut app, &behavior_handle, &default())
+        .insert(GlobalTransform::from_translation(origin))
+        .id();
+    (app, agent)
+}
+
+fn crates(app: &mut App) -> Vec<(Entity, Vec3)> {
+    app.world
+        .query_filtered::<(Entity, &Transform), With<Crate>>()
+        .iter(&app.world)
+        .map(|(entity, transform)| (entity, transform.translation))
+        .collect()
+}
+
+#[test]
+fn spawn_unregistered_fails() {
+    let behavior = r#"
+    (
+        "Spawn unknown",
+        Spawn((prefab: (prop: Value("unknown")))),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Spawn unknown", "[1] FAILURE Spawn unknown"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn despawn_missing_fails() {
+    let behavior = r#"
+    (
+        "Despawn missing",
+        Despawn((target: (prop: Value(999)))),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[1] STARTED Despawn missing", "[1] FAILURE Despawn missing"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn spawn_registered_prefab() {
+    let behavior = r#"("Spawn crate", Spawn((prefab: (prop: Value("crate")))))"#;
+    let origin = Vec3::new(1.0, 2.0, 3.0);
+    let (mut app, _) = spawn_app(behavior, origin);
+    for _ in 0..10 {
+        app.update();
+    }
+    let trace = app.world.resource::<BehaviorTrace>();
+    println!("{:#?}", trace);
+    assert!(trace
+        .iter()
+        .any(|line| line.ends_with("SUCCESS Spawn crate")));
+
+    // Spawned once, at the agent position
+    let crates = crates(&mut app);
+    assert_eq!(crates.len(), 1);
+    assert_eq!(crates[0].1, origin);
+}
+
+#[test]
+fn spawn_stores_entity_in_key() {
+    let behavior = r#"
+    (
+        "Spawn crate",
+        Spawn((prefab: (prop: Value("crate")), key: (prop: Value("crate")))),
+    )
+    "#;
+    let (mut app, agent) = spawn_app(behavior, Vec3::ZERO);
+    for _ in 0..10 {
+        app.update();
+    }
+    let crates = crates(&mut app);
+    assert_eq!(crates.len(), 1);
+
+    let script_ctx_handle = app.world.get::<Handle<ScriptContext>>(agent).unwrap();
+    let script_ctx = app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(script_ctx_handle)
+        .unwrap();
+    let blackboard = script_ctx.scope.get_value::<Map>("blackboard").unwrap();
+    let stored = blackboard.get("crate").unwrap().as_int().unwrap();
+    assert_eq!(stored, crates[0].0.to_bits() as i64);
+}
+
+#[test]
+fn spawn_aborted_despawns_entity() {
+    let behavior = r#"("Spawn crate", Spawn((prefab: (prop: Value("crate")))))"#;
+    let (mut app, _) = spawn_app(behavior, Vec3::ZERO);
+
+    // Run until the prefab is spawned, but the node has not completed yet
+    let mut spawns = app.world.query::<(Entity, &Spawn)>();
+    let mut node = None;
+    for _ in 0..10 {
+        app.update();
+        node = spawns
+            .iter(&app.world)
+            .find(|(_, spawn)| spawn.spawned.is_some())
+            .map(|(entity, _)| entity);
+        if node.is_some() {
+            break;
+        }
+    }
+    let node = node.expect("prefab was never spawned");
+    assert_eq!(crates(&mut app).len(), 1);
+
+    // Abort the node, as a parent stopping its children would
+    app.world
+        .entity_mut(node)
+        .remove::<(BehaviorCursor, BehaviorRunning)>();
+    for _ in 0..5 {
+        app.update();
+    }
+    let trace = app.world.resource::<BehaviorTrace>();
+    assert!(!trace
+        .iter()
+        .any(|line| line.ends_with("SUCCESS Spawn crate")));
+    assert!(crates(&mut app).is_empty());
+}
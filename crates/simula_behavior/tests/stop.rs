@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use crossbeam_channel::unbounded;
+use simula_behavior::{
+    prelude::*,
+    protocol::{
+        BehaviorClient, BehaviorFileId, BehaviorFileName, BehaviorProtocolClient,
+        BehaviorProtocolServer, BehaviorServer, StopOption,
+    },
+    test::*,
+};
+
+fn count<F: bevy::ecs::query::ReadOnlyWorldQuery>(app: &mut App) -> usize {
+    app.world
+        .query_filtered::<Entity, F>()
+        .iter(&app.world)
+        .count()
+}
+
+#[test]
+fn stop_clears_running_nodes() {
+    let behavior = r#"
+    (
+        "Patrol",
+        Sequencer(()),
+        [
+            ("Wait", Wait((duration: (prop: Value(100.0))))),
+            ("Action", Debug(())),
+        ]
+    )
+    "#;
+    let mut app = behavior_app();
+    app.add_asset::<BehaviorDocument>();
+
+    let (client_sender, server_receiver) = unbounded();
+    let (server_sender, client_receiver) = unbounded();
+    app.insert_resource(BehaviorServer::<TestBehavior> {
+        sender: server_sender,
+        receiver: server_receiver,
+    });
+    let client = BehaviorClient::<TestBehavior> {
+        sender: client_sender,
+        receiver: client_receiver,
+    };
+    app.add_plugin(BehaviorServerPlugin::<TestBehavior>::default());
+
+    let behavior_handle = add_behavior(&mut app, behavior);
+    let agent = spawn_agent(&mut app, &behavior_handle, &default())
+        .insert(Name::new("Agent"))
+        .id();
+
+    // track the agent as if the inspector had started it
+    let file_id = BehaviorFileId::new();
+    app.world
+        .resource_mut::<BehaviorTrackers<TestBehavior>>()
+        .insert(
+            file_id.clone(),
+            BehaviorTracker {
+                file_name: BehaviorFileName("patrol".into()),
+                entity: EntityTracker::Attached(agent),
+                asset: AssetTracker::Asset(behavior_handle),
+            },
+        );
+
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(count::<With<BehaviorRunning>>(&mut app) > 0);
+
+    client
+        .sender
+        .send(BehaviorProtocolClient::Stop(
+            file_id.clone(),
+            StopOption::Detach,
+        ))
+        .unwrap();
+    for _ in 0..5 {
+        app.update();
+    }
+
+    assert!(client
+        .receiver
+        .try_iter()
+        .any(|msg| matches!(msg, BehaviorProtocolServer::Stopped(id) if id == file_id)));
+    assert_eq!(count::<With<BehaviorRunning>>(&mut app), 0);
+    assert_eq!(count::<With<BehaviorCursor>>(&mut app), 0);
+    assert_eq!(
+        count::<Or<(With<BehaviorSuccess>, With<BehaviorFailure>)>>(&mut app),
+        0
+    );
+}
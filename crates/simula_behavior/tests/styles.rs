@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use simula_behavior::prelude::*;
+
+#[test]
+fn styles_default_by_type_and_name() {
+    let styles = BehaviorStyles::default();
+    let action = styles.color(BehaviorType::Action, "Wait");
+    assert_eq!(action, Color::hex("235").unwrap());
+    assert_eq!(styles.color(BehaviorType::Action, "Debug"), action);
+    assert_eq!(
+        styles.color(BehaviorType::Composite, "Sequencer"),
+        Color::hex("252").unwrap()
+    );
+    assert_eq!(
+        styles.color(BehaviorType::Composite, "Selector"),
+        Color::hex("522").unwrap()
+    );
+    assert_eq!(styles.icon(BehaviorType::Action, "Wait", "⌛"), "⌛");
+}
+
+#[test]
+fn styles_override() {
+    let mut styles = BehaviorStyles::default();
+    styles
+        .set_type(BehaviorType::Action, BehaviorStyle::new(Color::BLUE))
+        .set_name("Wait", BehaviorStyle::new(Color::ORANGE).with_icon("zz"));
+    assert_eq!(styles.color(BehaviorType::Action, "Debug"), Color::BLUE);
+    assert_eq!(styles.color(BehaviorType::Action, "Wait"), Color::ORANGE);
+    assert_eq!(styles.icon(BehaviorType::Action, "Wait", "⌛"), "zz");
+    assert_eq!(styles.icon(BehaviorType::Action, "Debug", "🐞"), "🐞");
+}
@@ -0,0 +1,90 @@
+use simula_behavior::{asset::validate_inline, prelude::*, test::*, BehaviorTrace};
+
+#[test]
+fn subtree_inline() {
+    let behavior = r##"
+    (
+        "Greet twice",
+        Sequencer(()),
+        [
+            ("Say hi", Subtree((asset: "#greet"))),
+            ("Say hi again", Subtree((asset: "#greet"))),
+        ],
+        (),
+        {
+            "greet": ("Greet", Debug(())),
+        },
+    )
+    "##;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Greet twice",
+        "[2] STARTED Say hi",
+        "[4] STARTED Greet",
+        "[4] SUCCESS Greet",
+        "[2] SUCCESS Say hi",
+        "[3] STARTED Say hi again",
+        "[5] STARTED Greet",
+        "[5] SUCCESS Greet",
+        "[3] SUCCESS Say hi again",
+        "[1] SUCCESS Greet twice",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn subtree_inline_recursive_rejected() {
+    let behavior = r##"
+    (
+        "Loop",
+        Subtree((asset: "#ping")),
+        [],
+        (),
+        {
+            "ping": ("Ping", Subtree((asset: "#pong"))),
+            "pong": ("Pong", Subtree((asset: "#ping"))),
+        },
+    )
+    "##;
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+    assert!(validate_inline(&behavior).is_err());
+}
+
+#[test]
+fn subtree_inline_unknown_rejected() {
+    let behavior = r##"
+    (
+        "Missing",
+        Subtree((asset: "#missing")),
+    )
+    "##;
+    let behavior = ron::from_str::<Behavior<TestBehavior>>(behavior).unwrap();
+    assert!(validate_inline(&behavior).is_err());
+}
+
+#[test]
+fn subtree_transparent_fails_as_a_whole() {
+    let behavior = r##"
+    (
+        "Try",
+        Subtree((asset: "#fail", transparent: true)),
+        [],
+        (),
+        {
+            "fail": ("Refuse", Inverter(()), [("Greet", Debug(()))]),
+        },
+    )
+    "##;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Try",
+        "[2] STARTED Refuse",
+        "[3] STARTED Greet",
+        "[3] SUCCESS Greet",
+        "[2] FAILURE Refuse",
+        "[1] FAILURE Try",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,109 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn switch_runs_indexed_child() {
+    let behavior = r#"
+    (
+        "Route",
+        Switch((index: (prop: Value(1)))),
+        [
+            ("A", Debug(())),
+            ("B", Debug(())),
+            ("C", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Route",
+        "[3] STARTED B",
+        "[3] SUCCESS B",
+        "[1] SUCCESS Route",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn switch_fails_with_child() {
+    let behavior = r#"
+    (
+        "Route",
+        Switch((index: (prop: Value(0)))),
+        [
+            ("A", Debug((fail: (prop: Value(true))))),
+            ("B", Debug(())),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Route",
+        "[2] STARTED A",
+        "[2] FAILURE A",
+        "[1] FAILURE Route",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn switch_out_of_range_fails() {
+    for index in ["2", "-1"] {
+        let behavior = format!(
+            r#"("Route", Switch((index: (prop: Value({})))), [("A", Debug(())), ("B", Debug(()))])"#,
+            index
+        );
+        let trace = trace_behavior(&behavior);
+        println!("{:#?}", trace);
+        let expected_trace = BehaviorTrace::from_list(&["[1] STARTED Route", "[1] FAILURE Route"]);
+        assert_eq!(&trace, &expected_trace);
+    }
+}
+
+#[test]
+fn switch_reroutes_when_index_changes() {
+    let behavior = r#"
+    (
+        "Routes",
+        Sequencer(()),
+        [
+            ("Start", Compute((key: (prop: Value("route")), expression: "0"))),
+            (
+                "Loop",
+                Repeater((repeat: Times(3))),
+                [
+                    (
+                        "Step",
+                        Sequencer(()),
+                        [
+                            (
+                                "Route",
+                                Switch((index: (prop: Eval(eval: "blackboard.route")))),
+                                [
+                                    ("A", Debug(())),
+                                    ("B", Debug(())),
+                                    ("C", Debug(())),
+                                ]
+                            ),
+                            ("Next", Compute((key: (prop: Value("route")), expression: "route + 1"))),
+                        ]
+                    ),
+                ]
+            ),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+
+    // each step runs the child at the index left by the previous step
+    let routed = trace
+        .iter()
+        .filter(|line| line.contains(" SUCCESS "))
+        .filter_map(|line| line.rsplit(' ').next())
+        .filter(|name| ["A", "B", "C"].contains(name))
+        .collect::<Vec<_>>();
+    assert_eq!(routed, vec!["A", "B", "C"]);
+    assert_eq!(trace.last().unwrap(), "[1] SUCCESS Routes");
+}
@@ -0,0 +1,58 @@
+use bevy::{prelude::*, utils::Instant};
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use std::time::Duration;
+
+const BEHAVIOR: &str = r#"
+(
+    "Hurry up",
+    Timeout((duration: (prop: Value(1.0)))),
+    [
+        ("Take long", Wait((duration: (prop: Value(10.0))))),
+    ],
+)
+"#;
+
+#[test]
+fn timeout_resets_running_child() {
+    let mut app = behavior_app_manual_time();
+    let behavior_handle = add_behavior(&mut app, BEHAVIOR);
+    spawn_agent(&mut app, &behavior_handle, &default());
+
+    // Tick every 100ms for 12s, noting when the timeout starts and fails
+    let mut now = Instant::now();
+    let mut started_at = None;
+    let mut failed_at = None;
+    for _ in 0..120 {
+        now += Duration::from_millis(100);
+        app.world.resource_mut::<Time>().update_with_instant(now);
+        app.update();
+        let trace = app.world.resource::<BehaviorTrace>();
+        if started_at.is_none() && trace.iter().any(|line| line.ends_with("STARTED Hurry up")) {
+            started_at = Some(now);
+        }
+        if failed_at.is_none() && trace.iter().any(|line| line.ends_with("FAILURE Hurry up")) {
+            failed_at = Some(now);
+        }
+    }
+
+    let trace = app.world.resource::<BehaviorTrace>();
+    println!("{:#?}", trace);
+    let timed_out = (failed_at.unwrap() - started_at.unwrap()).as_secs_f64();
+    assert!(
+        timed_out > 0.95 && timed_out < 1.35,
+        "failed after {}s",
+        timed_out
+    );
+    assert!(trace.iter().any(|line| line.ends_with("STARTED Take long")));
+    assert!(!trace.iter().any(|line| line.ends_with("SUCCESS Take long")));
+    assert!(!trace.iter().any(|line| line.ends_with("FAILURE Take long")));
+
+    // Child was reset, not left running
+    let mut waits = app.world.query_filtered::<Entity, With<Wait>>();
+    let child = waits.single(&app.world);
+    let child = app.world.entity(child);
+    assert!(!child.contains::<BehaviorRunning>());
+    assert!(!child.contains::<BehaviorCursor>());
+    assert!(!child.contains::<BehaviorSuccess>());
+    assert!(!child.contains::<BehaviorFailure>());
+}
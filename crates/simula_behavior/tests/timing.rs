@@ -0,0 +1,145 @@
+use bevy::{prelude::*, utils::Instant};
+use crossbeam_channel::unbounded;
+use simula_behavior::{
+    prelude::*,
+    protocol::{
+        BehaviorClient, BehaviorFileId, BehaviorFileName, BehaviorProtocolClient,
+        BehaviorProtocolServer, BehaviorServer, BehaviorTelemetry,
+    },
+    test::*,
+};
+use std::time::Duration;
+
+const DT: f32 = 0.1;
+
+struct TimingApp {
+    app: App,
+    client: BehaviorClient<TestBehavior>,
+    file_id: BehaviorFileId,
+    now: Instant,
+}
+
+impl TimingApp {
+    fn new() -> Self {
+        let behavior = r#"("Wait", Wait((duration: (prop: Value(100.0)))))"#;
+
+        let mut app = behavior_app_manual_time();
+        app.add_asset::<BehaviorDocument>();
+        app.add_system(time_running);
+
+        let (client_sender, server_receiver) = unbounded();
+        let (server_sender, client_receiver) = unbounded();
+        app.insert_resource(BehaviorServer::<TestBehavior> {
+            sender: server_sender,
+            receiver: server_receiver,
+        });
+        let client = BehaviorClient::<TestBehavior> {
+            sender: client_sender,
+            receiver: client_receiver,
+        };
+        app.add_plugin(BehaviorServerPlugin::<TestBehavior>::default());
+
+        let behavior_handle = add_behavior(&mut app, behavior);
+        let file_id = BehaviorFileId::new();
+        let agent = spawn_agent(&mut app, &behavior_handle, &default())
+            .insert((Name::new("Agent"), BehaviorSource(file_id.clone())))
+            .id();
+        app.world
+            .resource_mut::<BehaviorTrackers<TestBehavior>>()
+            .insert(
+                file_id.clone(),
+                BehaviorTracker {
+                    file_name: BehaviorFileName("wait".into()),
+                    entity: EntityTracker::Attached(agent),
+                    asset: AssetTracker::Asset(behavior_handle),
+                },
+            );
+        client
+            .sender
+            .send(BehaviorProtocolClient::Subscribe(file_id.clone()))
+            .unwrap();
+
+        let now = Instant::now();
+        Self {
+            app,
+            client,
+            file_id,
+            now,
+        }
+    }
+
+    // Advance time by a fixed step and run a frame
+    fn update(&mut self) {
+        self.now += Duration::from_secs_f32(DT);
+        let now = self.now;
+        self.app
+            .world
+            .resource_mut::<Time>()
+            .update_with_instant(now);
+        self.app.update();
+    }
+
+    fn running(&mut self) -> bool {
+        self.app
+            .world
+            .query_filtered::<Entity, (With<Wait>, With<BehaviorRunning>)>()
+            .iter(&self.app.world)
+            .count()
+            > 0
+    }
+
+    // Duration reported by the last telemetry message
+    fn duration(&self) -> f32 {
+        self.client
+            .receiver
+            .try_iter()
+            .filter_map(|msg| match msg {
+                BehaviorProtocolServer::Telemetry(id, _, telemetry) if id == self.file_id => {
+                    Some(telemetry)
+                }
+                _ => None,
+            })
+            .last()
+            .map(|BehaviorTelemetry(_, _, _, _, _, _, duration)| duration)
+            .unwrap()
+    }
+}
+
+#[test]
+fn timing_reports_running_duration() {
+    let mut timing = TimingApp::new();
+    timing
+        .client
+        .sender
+        .send(BehaviorProtocolClient::Timing(true))
+        .unwrap();
+    let mut frames = 0;
+    for _ in 0..25 {
+        timing.update();
+        if timing.running() {
+            frames += 1;
+        }
+    }
+    assert!(frames > 20);
+    let duration = timing.duration();
+    let expected = frames as f32 * DT;
+    assert!(
+        (duration - expected).abs() <= DT * 1.5,
+        "expected about {} s, got {} s",
+        expected,
+        duration
+    );
+}
+
+#[test]
+fn timing_disabled_reports_nothing() {
+    let mut timing = TimingApp::new();
+    for _ in 0..5 {
+        timing.update();
+    }
+    assert!(timing.running());
+    for _ in 0..20 {
+        timing.update();
+    }
+    assert_eq!(timing.duration(), 0.0);
+}
@@ -0,0 +1,49 @@
+use bevy::{ecs::event::Events, prelude::*};
+use simula_behavior::{prelude::*, test::*};
+
+const BEHAVIOR: &str = r#"
+(
+    "Greet",
+    Sequencer(()),
+    [
+        ("Say hi", Debug(())),
+        ("Say bye", Debug((fail: true))),
+    ],
+)
+"#;
+
+#[test]
+fn transitions_are_sent_in_order() {
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, BEHAVIOR);
+    let tree = spawn_agent(&mut app, &behavior_handle, &default()).id();
+
+    let mut transitions = vec![];
+    for _ in 0..20 {
+        app.update();
+        transitions.extend(
+            app.world
+                .resource_mut::<Events<BehaviorTransition>>()
+                .drain(),
+        );
+    }
+    println!("{:#?}", transitions);
+
+    assert!(transitions.iter().all(|transition| transition.tree == tree));
+    let transitions = transitions
+        .iter()
+        .map(|transition| (transition.name.as_str(), transition.from, transition.to))
+        .collect::<Vec<_>>();
+    use BehaviorStatus::*;
+    assert_eq!(
+        transitions,
+        vec![
+            ("Greet", None, Running),
+            ("Say hi", None, Running),
+            ("Say hi", Some(Running), Success),
+            ("Say bye", None, Running),
+            ("Say bye", Some(Running), Failure),
+            ("Greet", Some(Running), Failure),
+        ]
+    );
+}
@@ -0,0 +1,65 @@
+use bevy::{prelude::*, utils::Instant};
+use simula_behavior::{prelude::*, test::*};
+use simula_script::{script::Map, ScriptContext};
+use std::time::Duration;
+
+// Blackboard value after each frame, advancing time by the given frame durations, and
+// whether the tween succeeded by then
+fn tween(tween: &str, from: f64, frames: &[u64]) -> Vec<(f64, bool)> {
+    let behavior = format!(r#"("Fade", Tween({}))"#, tween);
+    let mut app = behavior_app_manual_time();
+    let behavior_handle = add_behavior(&mut app, &behavior);
+    let seed = BehaviorBlackboard::default().with("alpha", from);
+    let script_ctx_handle = spawn_agent(&mut app, &behavior_handle, &seed)
+        .get::<Handle<ScriptContext>>()
+        .unwrap()
+        .clone();
+
+    let mut now = Instant::now();
+    let mut values = vec![];
+    for millis in frames {
+        now += Duration::from_millis(*millis);
+        app.world.resource_mut::<Time>().update_with_instant(now);
+        app.update();
+        let script_ctx = app
+            .world
+            .resource::<Assets<ScriptContext>>()
+            .get(&script_ctx_handle)
+            .unwrap();
+        let blackboard = script_ctx.scope.get_value::<Map>("blackboard").unwrap();
+        let value = blackboard.get("alpha").unwrap().as_float().unwrap();
+        let trace = app.world.resource::<BehaviorTrace>();
+        values.push((value, trace.iter().any(|line| line.contains("SUCCESS"))));
+    }
+    values
+}
+
+#[test]
+fn tween_reaches_target_at_the_end() {
+    let linear = r#"(
+        key: (prop: Value("alpha")),
+        target: (prop: Value(10.0)),
+        duration: (prop: Value(1.0)),
+    )"#;
+    // the tree starts during the first frames, before time moves
+    let values = tween(linear, 0.0, &[0, 0, 0, 0, 250, 250, 250, 250, 100]);
+    assert_eq!(values[3], (0.0, false));
+    assert!((values[4].0 - 2.5).abs() < 1e-6 && !values[4].1);
+    assert!((values[6].0 - 7.5).abs() < 1e-6 && !values[6].1);
+    assert_eq!(values[7].0, 10.0);
+    assert_eq!(values[8], (10.0, true));
+}
+
+#[test]
+fn tween_eases() {
+    let quadratic = r#"(
+        key: (prop: Value("alpha")),
+        target: (prop: Value(0.0)),
+        duration: (prop: Value(1.0)),
+        ease: QuadraticIn,
+    )"#;
+    let values = tween(quadratic, 8.0, &[0, 0, 0, 0, 500, 500, 0]);
+    assert!((values[4].0 - 6.0).abs() < 1e-6 && !values[4].1);
+    assert_eq!(values[5].0, 0.0);
+    assert_eq!(values[6], (0.0, true));
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+fn signal_app(behavior: &str) -> App {
+    let mut app = behavior_app();
+    let behavior_handle = add_behavior(&mut app, behavior);
+
+    spawn_agent(&mut app, &behavior_handle, &default());
+    app
+}
+
+fn update(app: &mut App, times: usize) -> BehaviorTrace {
+    for _ in 0..times {
+        app.update();
+    }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+fn count(trace: &BehaviorTrace, suffix: &str) -> usize {
+    trace.iter().filter(|line| line.ends_with(suffix)).count()
+}
+
+#[test]
+fn wait_for_signal_runs_until_sent() {
+    let mut app = signal_app(r#"("Listen", WaitForSignal((name: (prop: Value("go")))))"#);
+
+    let trace = update(&mut app, 10);
+    println!("{:#?}", trace);
+    assert_eq!(count(&trace, "STARTED Listen"), 1);
+    assert_eq!(count(&trace, "SUCCESS Listen"), 0);
+
+    // Other signals are ignored
+    app.world.send_event(BehaviorSignal::new("stop"));
+    let trace = update(&mut app, 10);
+    assert_eq!(count(&trace, "SUCCESS Listen"), 0);
+
+    app.world.send_event(BehaviorSignal::new("go"));
+    let trace = update(&mut app, 10);
+    println!("{:#?}", trace);
+    assert_eq!(count(&trace, "SUCCESS Listen"), 1);
+    assert_eq!(count(&trace, "FAILURE Listen"), 0);
+}
+
+#[test]
+fn wait_for_signal_resets_on_reentry() {
+    let mut app = signal_app(
+        r#"
+    (
+        "Twice",
+        Repeater((repeat: Times(2))),
+        [
+            ("Listen", WaitForSignal((name: (prop: Value("go"))))),
+        ]
+    )
+    "#,
+    );
+    update(&mut app, 10);
+
+    // One signal completes one wait, the next one waits for another signal
+    app.world.send_event(BehaviorSignal::new("go"));
+    let trace = update(&mut app, 10);
+    println!("{:#?}", trace);
+    assert_eq!(count(&trace, "STARTED Listen"), 2);
+    assert_eq!(count(&trace, "SUCCESS Listen"), 1);
+    assert_eq!(count(&trace, "SUCCESS Twice"), 0);
+
+    app.world.send_event(BehaviorSignal::new("go"));
+    let trace = update(&mut app, 10);
+    println!("{:#?}", trace);
+    assert_eq!(count(&trace, "SUCCESS Listen"), 2);
+    assert_eq!(count(&trace, "SUCCESS Twice"), 1);
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*};
+use simula_script::{script::Map, ScriptContext};
+
+// Run a wander from an agent at `origin`, with a seeded rng, and return the picked point
+fn wander_point(wander: &str, origin: Vec3, seed: u64) -> Option<Vec3> {
+    let behavior = format!(r#"("Wander", Wander({}))"#, wander);
+    let mut app = behavior_app();
+    app.insert_resource(BehaviorRng::seeded(seed));
+    let behavior_handle = add_behavior(&mut app, &behavior);
+    let script_ctx_handle = spawn_agent(&mut app, &behavior_handle, &default())
+        .insert(GlobalTransform::from_translation(origin))
+        .get::<Handle<ScriptContext>>()
+        .unwrap()
+        .clone();
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let script_ctx = app
+        .world
+        .resource::<Assets<ScriptContext>>()
+        .get(&script_ctx_handle)
+        .unwrap();
+    let blackboard = script_ctx.scope.get_value::<Map>("blackboard").unwrap();
+    blackboard
+        .get("target")
+        .and_then(|point| point.clone().try_cast::<Vec3>())
+}
+
+const WANDER: &str = r#"(radius: (prop: Value(4.0)), key: (prop: Value("target")))"#;
+
+#[test]
+fn wander_is_reproducible() {
+    let origin = Vec3::new(1.0, 0.0, -2.0);
+    let a = wander_point(WANDER, origin, 7).unwrap();
+    let b = wander_point(WANDER, origin, 7).unwrap();
+    assert_eq!(a, b);
+    assert!(a.distance(origin) <= 4.0);
+    assert_eq!(a.y, origin.y);
+
+    let points = (0..8)
+        .map(|seed| wander_point(WANDER, origin, seed).unwrap())
+        .collect::<Vec<_>>();
+    assert!(points.iter().any(|point| *point != a));
+}
+
+#[test]
+fn wander_follows_bias() {
+    let wander = r#"(
+        radius: (prop: Value(4.0)),
+        bias: (1.0, 0.0, 0.0),
+        key: (prop: Value("target")),
+    )"#;
+    for seed in 0..8 {
+        let point = wander_point(wander, Vec3::ZERO, seed).unwrap();
+        assert!(point.x >= 0.0 && point.z.abs() < 1e-4);
+    }
+}
+
+#[test]
+fn wander_stays_in_grid() {
+    let wander = r#"(
+        radius: (prop: Value(4.0)),
+        grid_size: Some(2.0),
+        key: (prop: Value("target")),
+    )"#;
+    for seed in 0..8 {
+        let point = wander_point(wander, Vec3::new(1.0, 0.0, 1.0), seed).unwrap();
+        assert!(point.x.abs() <= 1.0 && point.z.abs() <= 1.0);
+    }
+}
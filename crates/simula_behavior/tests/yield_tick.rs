@@ -0,0 +1,65 @@
+use bevy::{prelude::*, utils::Instant};
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+use std::time::Duration;
+
+#[test]
+fn yield_sequence_success() {
+    let behavior = r#"
+    (
+        "Paced",
+        Sequencer(()),
+        [
+            ("Pause", Yield(())),
+            ("Act", Debug((message:"After one tick"))),
+        ]
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[1] STARTED Paced",
+        "[2] STARTED Pause",
+        "[2] SUCCESS Pause",
+        "[3] STARTED Act",
+        "[3] SUCCESS Act",
+        "[1] SUCCESS Paced",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+// Number of updates between the action starting and succeeding, advancing time by the
+// given frame durations
+fn ticks(behavior: &str, frames: &[u64]) -> usize {
+    let mut app = behavior_app_manual_time();
+    let behavior_handle = add_behavior(&mut app, behavior);
+    spawn_agent(&mut app, &behavior_handle, &default());
+
+    let mut now = Instant::now();
+    let mut started = None;
+    for (frame, millis) in frames.iter().enumerate() {
+        now += Duration::from_millis(*millis);
+        app.world.resource_mut::<Time>().update_with_instant(now);
+        app.update();
+        let trace = app.world.resource::<BehaviorTrace>();
+        if started.is_none() && trace.iter().any(|line| line.contains("STARTED")) {
+            started = Some(frame);
+        }
+        if trace.iter().any(|line| line.contains("SUCCESS")) {
+            return frame - started.unwrap();
+        }
+    }
+    panic!("Action did not complete");
+}
+
+#[test]
+fn yield_one_tick_at_any_frame_rate() {
+    let instant = r#"("Act", Debug(()))"#;
+    let pause = r#"("Pause", Yield(()))"#;
+    for frames in [
+        vec![16; 10],
+        vec![1, 100, 1, 100, 1, 100, 1, 100],
+        vec![250; 10],
+    ] {
+        assert_eq!(ticks(pause, &frames), ticks(instant, &frames) + 1);
+    }
+}
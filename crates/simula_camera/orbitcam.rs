@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use bevy::reflect::FromReflect;
-use bevy::render::camera::Camera;
+use bevy::render::camera::{Camera, Projection, ScalingMode};
 use simula_action::{
     action_axis_map, action_map, Action, ActionAxis, ActionAxisMap, ActionMap, ActionMapInput,
     AxisMapInput, AxisMapSource, MouseAxis,
@@ -15,6 +15,15 @@ pub enum CameraEvents {
 
 pub struct OrbitCameraAction;
 
+/// Event to ease all enabled orbit cameras toward a target, optionally at a new distance.
+pub struct OrbitCameraFocus {
+    pub target: Vec3,
+    pub distance: Option<f32>,
+}
+
+// Closest the pitch gets to the poles, where the camera would flip over
+const POLE_MARGIN: f32 = 0.001;
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct OrbitCamera {
@@ -23,13 +32,91 @@ pub struct OrbitCamera {
     #[reflect(ignore)]
     pub pitch_range: RangeInclusive<f32>,
     pub distance: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
     pub center: Vec3,
     pub rotate_sensitivity: f32,
     pub pan_sensitivity: f32,
     pub zoom_sensitivity: f32,
+    /// Time in seconds to ease most of the way toward the target pose, 0 snaps to it.
+    pub damping: f32,
+    /// Pose the camera is at, easing toward the target. None until the first update.
+    #[reflect(ignore)]
+    pub pose: Option<OrbitCameraPose>,
+    /// Render with an orthographic projection, framing the same height at the center as the
+    /// perspective projection does at the current distance.
+    pub orthographic: bool,
+    /// Perspective projection restored when switching back from orthographic.
+    #[reflect(ignore)]
+    pub perspective: PerspectiveProjection,
     pub enabled: bool,
 }
 
+/// Yaw, pitch, distance and center of an orbit camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCameraPose {
+    pub x: f32,
+    pub y: f32,
+    pub distance: f32,
+    pub center: Vec3,
+}
+
+impl OrbitCameraPose {
+    // Snap to the target when closer than this
+    const EPSILON: f32 = 1e-4;
+
+    /// Move a fraction of the way toward a target pose, snapping when close enough.
+    pub fn lerp(&self, target: &OrbitCameraPose, factor: f32) -> OrbitCameraPose {
+        let lerp = |a: f32, b: f32| {
+            let value = a + (b - a) * factor;
+            if (b - value).abs() < Self::EPSILON {
+                b
+            } else {
+                value
+            }
+        };
+        let center = self.center.lerp(target.center, factor);
+        OrbitCameraPose {
+            x: lerp(self.x, target.x),
+            y: lerp(self.y, target.y),
+            distance: lerp(self.distance, target.distance),
+            center: if center.abs_diff_eq(target.center, Self::EPSILON) {
+                target.center
+            } else {
+                center
+            },
+        }
+    }
+}
+
+/// Clamp a distance to a range, keeping it positive.
+pub fn clamp_distance(distance: f32, min: f32, max: f32) -> f32 {
+    let min = min.max(f32::EPSILON);
+    distance.clamp(min, max.max(min))
+}
+
+/// Clamp a pitch, measured from the up axis, to a range that stays off the poles.
+pub fn clamp_pitch(pitch: f32, range: &RangeInclusive<f32>) -> f32 {
+    let min = range.start().max(POLE_MARGIN);
+    let max = range.end().min(std::f32::consts::PI - POLE_MARGIN);
+    pitch.clamp(min, max.max(min))
+}
+
+/// Height framed at a distance by a perspective projection with a vertical field of view.
+pub fn frustum_height(distance: f32, fov: f32) -> f32 {
+    2.0 * distance * (fov * 0.5).tan()
+}
+
+/// Fraction of the way to move toward a target over a time step, for a damping time in
+/// seconds. Frame rate independent, 0 damping moves all the way.
+pub fn damping_factor(damping: f32, delta_seconds: f32) -> f32 {
+    if damping <= 0.0 {
+        1.0
+    } else {
+        1.0 - (-delta_seconds / damping).exp()
+    }
+}
+
 impl Default for OrbitCamera {
     fn default() -> Self {
         OrbitCamera {
@@ -37,10 +124,16 @@ impl Default for OrbitCamera {
             y: std::f32::consts::FRAC_PI_2,
             pitch_range: 0.01..=3.13,
             distance: 5.0,
+            min_distance: 0.1,
+            max_distance: 1000.0,
             center: Vec3::ZERO,
             rotate_sensitivity: 10.0,
             pan_sensitivity: 10.0,
             zoom_sensitivity: 0.8,
+            damping: 0.05,
+            pose: None,
+            orthographic: false,
+            perspective: PerspectiveProjection::default(),
             enabled: true,
         }
     }
@@ -54,19 +147,93 @@ impl OrbitCamera {
             ..Self::default()
         }
     }
+
+    /// Ease the center toward a target, and the distance when given, starting from the
+    /// current pose so a focus in progress is redirected without jumps.
+    pub fn focus(&mut self, target: Vec3, distance: Option<f32>) {
+        self.center = target;
+        if let Some(distance) = distance {
+            self.distance = clamp_distance(distance, self.min_distance, self.max_distance);
+        }
+    }
+
+    /// Pose the camera eases toward.
+    pub fn target_pose(&self) -> OrbitCameraPose {
+        OrbitCameraPose {
+            x: self.x,
+            y: clamp_pitch(self.y, &self.pitch_range),
+            distance: clamp_distance(self.distance, self.min_distance, self.max_distance),
+            center: self.center,
+        }
+    }
 }
 
 pub struct OrbitCameraPlugin;
 impl OrbitCameraPlugin {
     fn camera_update(
-        mut query: Query<(&OrbitCamera, &mut Transform), (Changed<OrbitCamera>, With<Camera>)>,
+        time: Res<Time>,
+        mut query: Query<(&mut OrbitCamera, &mut Transform), With<Camera>>,
     ) {
-        for (camera, mut transform) in query.iter_mut() {
+        for (mut camera, mut transform) in query.iter_mut() {
             if camera.enabled {
-                let rot = Quat::from_axis_angle(Vec3::Y, camera.x)
-                    * Quat::from_axis_angle(-Vec3::X, camera.y);
-                transform.translation = (rot * Vec3::Y) * camera.distance + camera.center;
-                transform.look_at(camera.center, Vec3::Y);
+                // Ease toward the target, nothing to do once there
+                let target = camera.target_pose();
+                let pose = match camera.pose {
+                    Some(pose) => pose.lerp(
+                        &target,
+                        damping_factor(camera.damping, time.delta_seconds()),
+                    ),
+                    None => target,
+                };
+                if camera.pose == Some(pose) && !camera.is_changed() {
+                    continue;
+                }
+                camera.pose = Some(pose);
+
+                let rot = Quat::from_axis_angle(Vec3::Y, pose.x)
+                    * Quat::from_axis_angle(-Vec3::X, pose.y);
+                transform.translation = (rot * Vec3::Y) * pose.distance + pose.center;
+                transform.look_at(pose.center, Vec3::Y);
+            }
+        }
+    }
+
+    fn camera_focus(mut events: EventReader<OrbitCameraFocus>, mut query: Query<&mut OrbitCamera>) {
+        for focus in events.iter() {
+            for mut camera in query.iter_mut().filter(|camera| camera.enabled) {
+                camera.focus(focus.target, focus.distance);
+            }
+        }
+    }
+
+    fn camera_projection(
+        mut query: Query<(&mut OrbitCamera, &mut Projection), Changed<OrbitCamera>>,
+    ) {
+        for (mut camera, mut projection) in query.iter_mut() {
+            let distance = camera.pose.map_or(camera.distance, |pose| pose.distance);
+            match (camera.orthographic, &mut *projection) {
+                // Follow zoom
+                (true, Projection::Orthographic(orthographic)) => {
+                    let height = frustum_height(distance, camera.perspective.fov);
+                    orthographic.scaling_mode = ScalingMode::FixedVertical(height);
+                }
+                // Switch to orthographic, keeping the framing
+                (true, Projection::Perspective(perspective)) => {
+                    let perspective = perspective.clone();
+                    let height = frustum_height(distance, perspective.fov);
+                    *projection = Projection::Orthographic(OrthographicProjection {
+                        near: perspective.near,
+                        far: perspective.far,
+                        scaling_mode: ScalingMode::FixedVertical(height),
+                        ..default()
+                    });
+                    camera.bypass_change_detection().perspective = perspective;
+                }
+                // Switch back to perspective
+                (false, Projection::Orthographic(_)) => {
+                    *projection = Projection::Perspective(camera.perspective.clone());
+                }
+                (false, Projection::Perspective(_)) => {}
             }
         }
     }
@@ -89,10 +256,7 @@ impl OrbitCameraPlugin {
                     let delta = Vec2::new(x, y);
                     camera.x -= delta.x * camera.rotate_sensitivity * time.delta_seconds();
                     camera.y -= delta.y * camera.rotate_sensitivity * time.delta_seconds();
-                    camera.y = camera
-                        .y
-                        .max(*camera.pitch_range.start())
-                        .min(*camera.pitch_range.end());
+                    camera.y = clamp_pitch(camera.y, &camera.pitch_range);
                 }
                 if mode.on(OrbitCameraMode::Pan) {
                     let x = motion.get(OrbitCameraMotion::Right).unwrap_or_default();
@@ -107,7 +271,11 @@ impl OrbitCameraPlugin {
                 }
                 // Zoom
                 let delta = motion.get(OrbitCameraMotion::Zoom).unwrap_or_default();
-                camera.distance *= camera.zoom_sensitivity.powf(delta);
+                camera.distance = clamp_distance(
+                    camera.distance * camera.zoom_sensitivity.powf(delta),
+                    camera.min_distance,
+                    camera.max_distance,
+                );
             }
             mode.clear();
             motion.clear();
@@ -121,9 +289,12 @@ impl Plugin for OrbitCameraPlugin {
             .add_system(setup)
             .add_system(action_map::<OrbitCameraMode, OrbitCamera>)
             .add_system(action_axis_map::<OrbitCameraMotion, OrbitCamera>)
+            .add_system(Self::camera_focus.before(Self::camera_update))
             .add_system(Self::camera_motion)
             .add_system(Self::camera_update)
-            .add_event::<CameraEvents>();
+            .add_system(Self::camera_projection.after(Self::camera_update))
+            .add_event::<CameraEvents>()
+            .add_event::<OrbitCameraFocus>();
     }
 }
 
@@ -288,3 +459,126 @@ fn setup(
             .insert(axis_map);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_clamps_to_range() {
+        assert_eq!(clamp_distance(5.0, 1.0, 10.0), 5.0);
+        assert_eq!(clamp_distance(0.5, 1.0, 10.0), 1.0);
+        assert_eq!(clamp_distance(20.0, 1.0, 10.0), 10.0);
+        // Stays positive, even with a zero or inverted range
+        assert!(clamp_distance(-1.0, 0.0, 10.0) > 0.0);
+        assert_eq!(clamp_distance(5.0, 3.0, 2.0), 3.0);
+    }
+
+    #[test]
+    fn pitch_clamps_to_limits() {
+        let range = 0.5..=2.5;
+        assert_eq!(clamp_pitch(1.0, &range), 1.0);
+        assert_eq!(clamp_pitch(0.1, &range), 0.5);
+        assert_eq!(clamp_pitch(3.0, &range), 2.5);
+        // Never reaches the poles, even when the range does
+        let range = 0.0..=std::f32::consts::PI;
+        assert_eq!(clamp_pitch(-1.0, &range), POLE_MARGIN);
+        assert_eq!(clamp_pitch(4.0, &range), std::f32::consts::PI - POLE_MARGIN);
+    }
+
+    #[test]
+    fn damping_is_frame_rate_independent() {
+        assert_eq!(damping_factor(0.0, 1.0 / 60.0), 1.0);
+
+        // Sixty small steps cover the same fraction as one second long step
+        let damping = 0.25;
+        let step = damping_factor(damping, 1.0 / 60.0);
+        let stepped = 1.0 - (1.0 - step).powi(60);
+        let whole = damping_factor(damping, 1.0);
+        assert!((stepped - whole).abs() < 1e-4);
+        assert!(whole > 0.0 && whole < 1.0);
+    }
+
+    #[test]
+    fn focus_eases_center_to_target() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_event::<OrbitCameraFocus>()
+            .add_system(OrbitCameraPlugin::camera_focus.before(OrbitCameraPlugin::camera_update))
+            .add_system(OrbitCameraPlugin::camera_update);
+        let camera = app
+            .world
+            .spawn((
+                OrbitCamera::default(),
+                Transform::default(),
+                Camera::default(),
+            ))
+            .id();
+
+        let mut now = bevy::utils::Instant::now();
+        let mut frame = |app: &mut App| {
+            now += std::time::Duration::from_secs_f32(1.0 / 60.0);
+            app.world.resource_mut::<Time>().update_with_instant(now);
+            app.update();
+        };
+
+        // Settle at the initial pose, then focus
+        frame(&mut app);
+        let target = Vec3::new(4.0, 1.0, -2.0);
+        app.world.send_event(OrbitCameraFocus {
+            target,
+            distance: Some(8.0),
+        });
+
+        // Eases, rather than jumps, toward the target
+        frame(&mut app);
+        let pose = app.world.get::<OrbitCamera>(camera).unwrap().pose.unwrap();
+        assert!(!pose.center.abs_diff_eq(target, 1e-3));
+
+        // Gets there within a second of 60 fps frames
+        for _ in 0..60 {
+            frame(&mut app);
+        }
+        let pose = app.world.get::<OrbitCamera>(camera).unwrap().pose.unwrap();
+        assert!(pose.center.abs_diff_eq(target, 1e-3));
+        assert!((pose.distance - 8.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn orthographic_toggles_projection() {
+        let mut app = App::new();
+        app.add_system(OrbitCameraPlugin::camera_projection);
+        let camera = app
+            .world
+            .spawn((
+                OrbitCamera::default(),
+                Projection::Perspective(PerspectiveProjection::default()),
+            ))
+            .id();
+        app.update();
+
+        app.world
+            .get_mut::<OrbitCamera>(camera)
+            .unwrap()
+            .orthographic = true;
+        app.update();
+        let Projection::Orthographic(orthographic) = app.world.get::<Projection>(camera).unwrap()
+        else {
+            panic!("expected an orthographic projection");
+        };
+        let ScalingMode::FixedVertical(height) = orthographic.scaling_mode else {
+            panic!("expected a fixed vertical scaling mode");
+        };
+        let fov = PerspectiveProjection::default().fov;
+        let expected = frustum_height(OrbitCamera::default().distance, fov);
+        assert!((height - expected).abs() < 1e-5);
+
+        app.world
+            .get_mut::<OrbitCamera>(camera)
+            .unwrap()
+            .orthographic = false;
+        app.update();
+        let projection = app.world.get::<Projection>(camera).unwrap();
+        assert!(matches!(projection, Projection::Perspective(_)));
+    }
+}
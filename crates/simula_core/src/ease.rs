@@ -1,10 +1,12 @@
-use bevy::prelude::Reflect;
+use bevy::prelude::{FromReflect, Reflect};
 use enum_iterator::Sequence;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[allow(missing_docs)]
-#[derive(Reflect, Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Sequence, Display)]
+#[derive(
+    Reflect, FromReflect, Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Sequence, Display,
+)]
 pub enum EaseFunction {
     Linear,
 
@@ -575,6 +577,64 @@ mod test {
         assert_eq!(1.0, Ease::back_in_out(1.0));
     }
 
+    // Bounce polynomials land on their end points up to rounding
+    fn assert_near(expected: f64, value: f64) {
+        assert!(
+            (expected - value).abs() < 1e-12,
+            "{} != {}",
+            expected,
+            value
+        );
+    }
+
+    #[test]
+    fn test_bounce_in() {
+        assert_near(0.0, Ease::bounce_in(0.0));
+        assert_near(0.28125, Ease::bounce_in(0.5));
+        assert_near(1.0, Ease::bounce_in(1.0));
+    }
+
+    #[test]
+    fn test_bounce_out() {
+        assert_near(0.0, Ease::bounce_out(0.0));
+        assert_near(0.71875, Ease::bounce_out(0.5));
+        assert_near(1.0, Ease::bounce_out(1.0));
+    }
+
+    #[test]
+    fn test_bounce_in_out() {
+        assert_near(0.0, Ease::bounce_in_out(0.0));
+        assert_near(0.5, Ease::bounce_in_out(0.5));
+        assert_near(1.0, Ease::bounce_in_out(1.0));
+    }
+
+    #[test]
+    fn test_overshoot_signs() {
+        // In variants dip below 0 before rising, out variants pass 1 before settling
+        assert!(Ease::elastic_in(0.5_f64) < 0.0);
+        assert!(Ease::elastic_out(0.5_f64) > 1.0);
+        assert!(Ease::back_in(0.5_f64) < 0.0);
+        assert!(Ease::back_out(0.5_f64) > 1.0);
+        assert!(Ease::back_in_out(0.1_f64) < 0.0);
+        assert!(Ease::back_in_out(0.9_f64) > 1.0);
+        // Bounces stay within range
+        for i in 0..=100 {
+            let p = i as f64 / 100.0;
+            for value in [
+                Ease::bounce_in(p),
+                Ease::bounce_out(p),
+                Ease::bounce_in_out(p),
+            ] {
+                assert!(
+                    (-1e-12..=1.0 + 1e-12).contains(&value),
+                    "{} at {}",
+                    value,
+                    p
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_sine_out() {
         assert_eq!(0.0, Ease::sine_out(0.0));
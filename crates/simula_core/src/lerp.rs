@@ -0,0 +1,134 @@
+use bevy::prelude::{Quat, Vec2, Vec3, Vec4};
+
+/// Values that can be linearly interpolated.
+pub trait Lerp {
+    fn lerp(&self, other: &Self, t: &f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, t: &f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, t: &f32) -> Self {
+        self + (other - self) * *t as f64
+    }
+}
+
+impl Lerp for Vec2 {
+    fn lerp(&self, other: &Self, t: &f32) -> Self {
+        *self + (*other - *self) * *t
+    }
+}
+
+impl Lerp for Vec3 {
+    fn lerp(&self, other: &Self, t: &f32) -> Self {
+        *self + (*other - *self) * *t
+    }
+}
+
+impl Lerp for Vec4 {
+    fn lerp(&self, other: &Self, t: &f32) -> Self {
+        *self + (*other - *self) * *t
+    }
+}
+
+/// Linearly interpolates from a to b.
+pub fn lerp<T: Lerp>(a: &T, b: &T, t: &f32) -> T {
+    a.lerp(b, t)
+}
+
+// Below this sine of the angle between rotations, slerp falls back to nlerp
+const SLERP_EPSILON: f32 = 1e-4;
+
+/// Spherically interpolates from rotation a to b, along the shortest arc.
+///
+/// When the rotations are nearly the same or nearly antipodal, the arc is too short to
+/// divide by its sine and this falls back to a normalized linear interpolation.
+pub fn slerp(a: &Quat, b: &Quat, t: &f32) -> Quat {
+    let a = a.normalize();
+    let mut b = b.normalize();
+
+    // Shortest arc, q and -q are the same rotation
+    let mut dot = a.dot(b);
+    if dot < 0.0 {
+        b = -b;
+        dot = -dot;
+    }
+
+    let theta = dot.min(1.0).acos();
+    let sin_theta = theta.sin();
+    if sin_theta < SLERP_EPSILON {
+        return nlerp(&a, &b, t);
+    }
+
+    let wa = ((1.0 - t) * theta).sin() / sin_theta;
+    let wb = (t * theta).sin() / sin_theta;
+    (a * wa + b * wb).normalize()
+}
+
+/// Linearly interpolates from rotation a to b, normalizing the result.
+pub fn nlerp(a: &Quat, b: &Quat, t: &f32) -> Quat {
+    let b = if a.dot(*b) < 0.0 { -*b } else { *b };
+    let q = lerp(&Vec4::from(*a), &Vec4::from(b), t);
+    if q.length_squared() <= f32::EPSILON {
+        *a
+    } else {
+        Quat::from_vec4(q.normalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn test_lerp() {
+        assert_eq!(lerp(&1.0, &3.0, &0.5), 2.0);
+        assert_eq!(
+            lerp(&Vec2::ZERO, &Vec2::new(2.0, -4.0), &0.25),
+            Vec2::new(0.5, -1.0)
+        );
+        assert_eq!(lerp(&Vec3::ONE, &Vec3::splat(3.0), &0.5), Vec3::splat(2.0));
+        assert_eq!(lerp(&Vec4::ZERO, &Vec4::ONE, &1.0), Vec4::ONE);
+    }
+
+    #[test]
+    fn test_slerp_half_angle() {
+        let a = Quat::IDENTITY;
+        let b = Quat::from_rotation_y(FRAC_PI_2);
+        let q = slerp(&a, &b, &0.5);
+        assert!(q.abs_diff_eq(Quat::from_rotation_y(FRAC_PI_4), 1e-5));
+
+        let a = Quat::from_rotation_x(0.2);
+        let b = Quat::from_rotation_x(1.4);
+        let q = slerp(&a, &b, &0.5);
+        assert!(q.abs_diff_eq(Quat::from_rotation_x(0.8), 1e-5));
+    }
+
+    #[test]
+    fn test_slerp_ends() {
+        let a = Quat::from_rotation_z(0.3);
+        let b = Quat::from_rotation_x(2.0);
+        assert!(slerp(&a, &b, &0.0).abs_diff_eq(a, 1e-5));
+        assert!(slerp(&a, &b, &1.0).abs_diff_eq(b, 1e-5));
+    }
+
+    #[test]
+    fn test_slerp_antipodal() {
+        // Same rotation with opposite signs, the result stays on that rotation
+        let a = Quat::from_rotation_y(PI / 3.0);
+        let q = slerp(&a, &-a, &0.5);
+        assert!(q.is_normalized());
+        assert!((q.dot(a).abs() - 1.0).abs() < 1e-5);
+
+        // Nearly antipodal
+        let b = -Quat::from_rotation_y(PI / 3.0 + 1e-6);
+        let q = slerp(&a, &b, &0.5);
+        assert!(q.is_normalized());
+        assert!((q.dot(a).abs() - 1.0).abs() < 1e-5);
+    }
+}
@@ -4,6 +4,7 @@ extern crate enum_display_derive;
 pub mod ease;
 pub mod epath;
 pub mod force_graph;
+pub mod lerp;
 pub mod map_range;
 pub mod prng;
 pub mod ray;
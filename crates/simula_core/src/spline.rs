@@ -243,3 +243,113 @@ impl Curve {
         tangent.length()
     }
 }
+
+/// Uniform Catmull-Rom spline passing through all its control points, sampled with t from 0
+/// to 1 across the whole path. End points are duplicated to shape the first and last segments.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CatmullRom {
+    pub points: Vec<Vec3>,
+}
+
+impl CatmullRom {
+    pub fn new(points: Vec<Vec3>) -> Self {
+        Self { points }
+    }
+
+    // Control points of the segment at t and the parameter within it
+    fn segment(&self, t: f32) -> ([Vec3; 4], f32) {
+        let last = self.points.len() - 1;
+        let t = t.clamp(0.0, 1.0) * last as f32;
+        let segment = (t.floor() as usize).min(last - 1);
+        let point = |i: isize| self.points[i.clamp(0, last as isize) as usize];
+        let i = segment as isize;
+        (
+            [point(i - 1), point(i), point(i + 1), point(i + 2)],
+            t - segment as f32,
+        )
+    }
+
+    /// Position at t, control point i is at t = i / (points - 1).
+    pub fn sample(&self, t: f32) -> Vec3 {
+        match self.points.len() {
+            0 => Vec3::ZERO,
+            1 => self.points[0],
+            _ => {
+                let ([p0, p1, p2, p3], t) = self.segment(t);
+                let t2 = t * t;
+                let t3 = t2 * t;
+                0.5 * (2.0 * p1
+                    + (p2 - p0) * t
+                    + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+                    + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+            }
+        }
+    }
+
+    /// Derivative of the position with respect to t.
+    pub fn sample_tangent(&self, t: f32) -> Vec3 {
+        match self.points.len() {
+            0 | 1 => Vec3::ZERO,
+            len => {
+                let ([p0, p1, p2, p3], t) = self.segment(t);
+                let t2 = t * t;
+                let tangent = 0.5
+                    * ((p2 - p0)
+                        + 2.0 * (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t
+                        + 3.0 * (3.0 * p1 - p0 - 3.0 * p2 + p3) * t2);
+                // Segments are 1 / (len - 1) long in t
+                tangent * (len - 1) as f32
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catmull_rom_passes_through_points() {
+        let points = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 2.0, 0.0),
+            Vec3::new(3.0, 2.0, -1.0),
+            Vec3::new(4.0, 0.0, 1.0),
+            Vec3::new(6.0, 1.0, 0.0),
+        ];
+        let spline = CatmullRom::new(points.clone());
+        let segments = (points.len() - 1) as f32;
+        for (i, point) in points.iter().enumerate() {
+            let sample = spline.sample(i as f32 / segments);
+            assert!(sample.abs_diff_eq(*point, 1e-5), "{} != {}", sample, point);
+        }
+        assert_eq!(spline.sample(-1.0), points[0]);
+        assert_eq!(spline.sample(2.0), points[4]);
+    }
+
+    #[test]
+    fn catmull_rom_tangent() {
+        // Evenly spaced points on a line, the middle segment moves at constant speed
+        let spline = CatmullRom::new(vec![Vec3::ZERO, Vec3::X, Vec3::X * 2.0, Vec3::X * 3.0]);
+        for i in 0..=10 {
+            let t = (1.0 + i as f32 / 10.0) / 3.0;
+            assert!(spline.sample(t).abs_diff_eq(Vec3::X * 3.0 * t, 1e-5));
+        }
+        assert!(spline.sample_tangent(0.5).abs_diff_eq(Vec3::X * 3.0, 1e-5));
+
+        // Inner control points, tangent is parallel to their neighbours
+        let spline = CatmullRom::new(vec![Vec3::ZERO, Vec3::Y, Vec3::new(2.0, 1.0, 0.0)]);
+        let tangent = spline.sample_tangent(0.5);
+        assert!(tangent
+            .normalize()
+            .abs_diff_eq(Vec3::new(2.0, 1.0, 0.0).normalize(), 1e-5));
+    }
+
+    #[test]
+    fn catmull_rom_degenerate() {
+        assert_eq!(CatmullRom::default().sample(0.5), Vec3::ZERO);
+        let spline = CatmullRom::new(vec![Vec3::ONE]);
+        assert_eq!(spline.sample(0.5), Vec3::ONE);
+        assert_eq!(spline.sample_tangent(0.5), Vec3::ZERO);
+    }
+}
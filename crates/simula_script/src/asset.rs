@@ -1,3 +1,4 @@
+use crate::register_std;
 use bevy::{
     asset::{AssetLoader, LoadContext, LoadedAsset},
     prelude::*,
@@ -16,8 +17,15 @@ pub struct ScriptContext {
 }
 
 impl ScriptContext {
+    /// Context with the standard library registered, see `register_std`
     pub fn new() -> Self {
         let mut engine = script::Engine::new();
+        register_std(&mut engine);
+        Self::with_engine(engine)
+    }
+
+    /// Context on a custom engine, to opt out of or extend the standard library
+    pub fn with_engine(mut engine: script::Engine) -> Self {
         engine.on_print(|x| info!("{x}"));
         let scope = script::Scope::new();
         Self { engine, scope }
@@ -35,6 +43,21 @@ impl ScriptContext {
     }
 }
 
+/// Script error message, prefixed with its position as `Error at line:column: ...` when known
+pub fn error_message(err: &script::EvalAltResult) -> String {
+    let position = err.position();
+    let message = err.to_string();
+    match (position.line(), position.position()) {
+        (Some(line), Some(column)) => {
+            // the message ends with the position already, in its own words
+            let suffix = format!(" ({})", position);
+            let message = message.strip_suffix(&suffix).unwrap_or(&message);
+            format!("Error at {}:{}: {}", line, column, message)
+        }
+        _ => format!("Error: {}", message),
+    }
+}
+
 // trait FromScript<InputType, OutputType> {
 //     fn from_script(script: &str) -> Result<OutputType, Box<script::EvalAltResult>>;
 // }
@@ -57,6 +80,10 @@ impl Script {
         Ok(())
     }
 
+    pub fn is_compiled(&self) -> bool {
+        self.ast.is_some()
+    }
+
     pub fn eval<T>(
         &self,
         context: &mut ScriptContext,
@@ -98,3 +125,28 @@ impl AssetLoader for ScriptLoader {
         EXTENSIONS
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_positions() {
+        let engine = ScriptContext::new().engine;
+        let parse = engine
+            .eval::<script::Dynamic>("let x = 1;\nlet y = ;")
+            .unwrap_err();
+        let message = error_message(&parse);
+        assert!(message.starts_with("Error at 2:"), "{}", message);
+        assert!(!message.ends_with(")"), "{}", message);
+
+        let runtime = engine.eval::<script::Dynamic>("1 + missing").unwrap_err();
+        let message = error_message(&runtime);
+        assert!(message.starts_with("Error at 1:5: "), "{}", message);
+        assert!(message.contains("missing"), "{}", message);
+
+        let custom: Box<script::EvalAltResult> = "out of range".into();
+        let message = error_message(&custom);
+        assert!(message.starts_with("Error: out of range"), "{}", message);
+    }
+}
@@ -1,9 +1,15 @@
 use asset::ScriptLoader;
-pub use asset::{Script, ScriptContext};
+pub use asset::{error_message, Script, ScriptContext};
 use bevy::prelude::*;
 pub use rhai as script;
+pub use run::{RunScript, ScriptError, SCRIPT_RESULT};
+pub use stdlib::register_std;
+pub use watch::{ScriptWatch, ScriptWatchPlugin, ScriptWatches};
 
 mod asset;
+mod run;
+mod stdlib;
+mod watch;
 
 pub struct ScriptPlugin;
 
@@ -12,7 +18,9 @@ impl Plugin for ScriptPlugin {
         app.add_asset::<Script>()
             .add_asset::<ScriptContext>()
             .init_asset_loader::<ScriptLoader>()
-            .add_system(script_changed);
+            .add_event::<ScriptError>()
+            .add_system(script_changed)
+            .add_system(run::run_scripts);
     }
 }
 
@@ -0,0 +1,154 @@
+use crate::{error_message, script, Script, ScriptContext};
+use bevy::{prelude::*, utils::HashSet};
+
+/// Name of the scope variable holding the result of the last run
+pub const SCRIPT_RESULT: &str = "result";
+
+/// Evaluate a script against a script context whenever the script is loaded or changed, and
+/// when this component is added to an entity with the script already loaded. The result is
+/// stored in the context scope as `result`, errors are sent as `ScriptError` events.
+#[derive(Debug, Clone, Component)]
+pub struct RunScript {
+    pub script: Handle<Script>,
+    pub context: Handle<ScriptContext>,
+}
+
+/// A script run by `RunScript` failed to compile or evaluate
+#[derive(Debug, Clone)]
+pub struct ScriptError {
+    pub handle: Handle<Script>,
+    pub message: String,
+}
+
+pub(crate) fn run_scripts(
+    mut script_events: EventReader<AssetEvent<Script>>,
+    mut script_errors: EventWriter<ScriptError>,
+    scripts: Res<Assets<Script>>,
+    mut script_ctxs: ResMut<Assets<ScriptContext>>,
+    runs: Query<Ref<RunScript>>,
+) {
+    let changed: HashSet<Handle<Script>> = script_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                Some(handle.clone_weak())
+            }
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+
+    for run in &runs {
+        if !run.is_added() && !changed.contains(&run.script) {
+            continue;
+        }
+        let (Some(script), Some(context)) =
+            (scripts.get(&run.script), script_ctxs.get_mut(&run.context))
+        else {
+            continue;
+        };
+        match eval(script, context) {
+            Ok(result) => context.scope.set_value(SCRIPT_RESULT, result),
+            Err(err) => script_errors.send(ScriptError {
+                handle: run.script.clone_weak(),
+                message: error_message(&err),
+            }),
+        }
+    }
+}
+
+// Compile locally, compiling into the asset would mark it modified and run it again
+fn eval(
+    script: &Script,
+    context: &mut ScriptContext,
+) -> Result<script::Dynamic, Box<script::EvalAltResult>> {
+    let ast = context.engine.compile(&script.script)?;
+    let stack = context.scope.len();
+    let result = context
+        .engine
+        .eval_ast_with_scope::<script::Dynamic>(&mut context.scope, &ast);
+    context.scope.rewind(stack);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScriptPlugin;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin::default())
+            .add_plugin(ScriptPlugin);
+        app
+    }
+
+    fn script(source: &str) -> Script {
+        let mut script = Script::default();
+        script.script = source.to_string().into();
+        script
+    }
+
+    fn result(app: &App, context: &Handle<ScriptContext>) -> Option<script::INT> {
+        let context = app.world.resource::<Assets<ScriptContext>>().get(context);
+        context?.scope.get_value::<script::INT>(SCRIPT_RESULT)
+    }
+
+    #[test]
+    fn run_script_on_change() {
+        let mut app = app();
+        let script_handle = app
+            .world
+            .resource_mut::<Assets<Script>>()
+            .add(script("40 + 2"));
+        let context = app
+            .world
+            .resource_mut::<Assets<ScriptContext>>()
+            .add(ScriptContext::new());
+        app.world.spawn(RunScript {
+            script: script_handle.clone(),
+            context: context.clone(),
+        });
+        app.update();
+        app.update();
+        assert_eq!(result(&app, &context), Some(42));
+
+        app.world
+            .resource_mut::<Assets<Script>>()
+            .get_mut(&script_handle)
+            .unwrap()
+            .script = "6 * 7 + 1".into();
+        app.update();
+        app.update();
+        assert_eq!(result(&app, &context), Some(43));
+    }
+
+    #[test]
+    fn run_script_errors() {
+        let mut app = app();
+        let script_handle = app
+            .world
+            .resource_mut::<Assets<Script>>()
+            .add(script("40 +"));
+        let context = app
+            .world
+            .resource_mut::<Assets<ScriptContext>>()
+            .add(ScriptContext::new());
+        app.world.spawn(RunScript {
+            script: script_handle.clone(),
+            context: context.clone(),
+        });
+        app.update();
+        app.update();
+        assert_eq!(result(&app, &context), None);
+
+        let events = app.world.resource::<Events<ScriptError>>();
+        let errors = events
+            .get_reader()
+            .iter(events)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert!(!errors.is_empty());
+        assert_eq!(errors[0].handle, script_handle);
+        assert!(!errors[0].message.is_empty());
+    }
+}
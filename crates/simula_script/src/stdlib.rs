@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use rhai::{
+    Array, Dynamic, Engine, EvalAltResult, ImmutableString, RegisterFn, RegisterResultFn, FLOAT,
+    INT,
+};
+use simula_core::lerp::lerp;
+
+/// Register the simula scripting standard library on a Rhai engine.
+///
+/// Vector math, on a `Vec3` type with `x`, `y` and `z` properties:
+/// - `vec3(x, y, z)`, `dot(a, b)`, `cross(a, b)`, `length(v)`, `normalize(v)`, `distance(a, b)`
+/// - `a + b`, `a - b`
+///
+/// Transforms, on a `Transform` type with `translation` and `rotation` properties:
+/// - `transform()`, the identity, and a `Quat` type for rotations
+///
+/// Common math, on floats:
+/// - `clamp(x, min, max)`, `lerp(a, b, t)`, `min(a, b)`, `max(a, b)`, `abs(x)`, `sqrt(x)`,
+///   `sin(x)`, `cos(x)`
+///
+/// Arithmetic as functions, on integers or floats, failing on overflow or division by zero:
+/// - `add(a, b)`, `sub(a, b)`, `mul(a, b)`, `div(a, b)`, `modulo(a, b)`
+///
+/// String helpers:
+/// - `upper(s)`, `lower(s)`, `trim(s)`, `starts_with(s, prefix)`, `ends_with(s, suffix)`,
+///   `split(s, separator)`, `str(x)`
+pub fn register_std(engine: &mut Engine) {
+    register_vec3(engine);
+    register_transform(engine);
+    register_math(engine);
+    register_arithmetic(engine);
+    register_string(engine);
+}
+
+fn register_vec3(engine: &mut Engine) {
+    engine.register_type_with_name::<Vec3>("Vec3");
+    engine.register_get_set(
+        "x",
+        |v: &mut Vec3| v.x as FLOAT,
+        |v: &mut Vec3, x: FLOAT| v.x = x as f32,
+    );
+    engine.register_get_set(
+        "y",
+        |v: &mut Vec3| v.y as FLOAT,
+        |v: &mut Vec3, y: FLOAT| v.y = y as f32,
+    );
+    engine.register_get_set(
+        "z",
+        |v: &mut Vec3| v.z as FLOAT,
+        |v: &mut Vec3, z: FLOAT| v.z = z as f32,
+    );
+    engine.register_fn("vec3", |x: FLOAT, y: FLOAT, z: FLOAT| {
+        Vec3::new(x as f32, y as f32, z as f32)
+    });
+    engine.register_fn("dot", |a: Vec3, b: Vec3| a.dot(b) as FLOAT);
+    engine.register_fn("cross", |a: Vec3, b: Vec3| a.cross(b));
+    engine.register_fn("length", |v: Vec3| v.length() as FLOAT);
+    engine.register_fn("normalize", |v: Vec3| v.normalize_or_zero());
+    engine.register_fn("distance", |a: Vec3, b: Vec3| a.distance(b) as FLOAT);
+    engine.register_fn("to_string", |v: &mut Vec3| -> ImmutableString {
+        format!("vec3({}, {}, {})", v.x, v.y, v.z).into()
+    });
+    engine.register_fn("+", |a: Vec3, b: Vec3| a + b);
+    engine.register_fn("-", |a: Vec3, b: Vec3| a - b);
+}
+
+fn register_transform(engine: &mut Engine) {
+    engine.register_type_with_name::<Quat>("Quat");
+    engine.register_type_with_name::<Transform>("Transform");
+    engine.register_get_set(
+        "translation",
+        |t: &mut Transform| t.translation,
+        |t: &mut Transform, translation: Vec3| t.translation = translation,
+    );
+    engine.register_get_set(
+        "rotation",
+        |t: &mut Transform| t.rotation,
+        |t: &mut Transform, rotation: Quat| t.rotation = rotation,
+    );
+    engine.register_fn("transform", || Transform::IDENTITY);
+}
+
+fn register_math(engine: &mut Engine) {
+    engine.register_fn("clamp", |x: FLOAT, min: FLOAT, max: FLOAT| {
+        x.max(min).min(max)
+    });
+    engine.register_fn("clamp", |x: INT, min: INT, max: INT| x.max(min).min(max));
+    engine.register_fn("lerp", |a: FLOAT, b: FLOAT, t: FLOAT| {
+        lerp(&a, &b, &(t as f32))
+    });
+    engine.register_fn("min", |a: FLOAT, b: FLOAT| a.min(b));
+    engine.register_fn("max", |a: FLOAT, b: FLOAT| a.max(b));
+    engine.register_fn("min", |a: INT, b: INT| a.min(b));
+    engine.register_fn("max", |a: INT, b: INT| a.max(b));
+    engine.register_fn("abs", |x: FLOAT| x.abs());
+    engine.register_fn("sqrt", |x: FLOAT| x.sqrt());
+    engine.register_fn("sin", |x: FLOAT| x.sin());
+    engine.register_fn("cos", |x: FLOAT| x.cos());
+}
+
+fn checked(name: &str, result: Option<INT>) -> Result<Dynamic, Box<EvalAltResult>> {
+    match result {
+        Some(result) => Ok(Dynamic::from(result)),
+        None => Err(format!("{}: arithmetic overflow or division by zero", name).into()),
+    }
+}
+
+fn register_arithmetic(engine: &mut Engine) {
+    engine.register_result_fn("add", |a: INT, b: INT| checked("add", a.checked_add(b)));
+    engine.register_result_fn("sub", |a: INT, b: INT| checked("sub", a.checked_sub(b)));
+    engine.register_result_fn("mul", |a: INT, b: INT| checked("mul", a.checked_mul(b)));
+    engine.register_result_fn("div", |a: INT, b: INT| checked("div", a.checked_div(b)));
+    engine.register_result_fn("modulo", |a: INT, b: INT| {
+        checked("modulo", a.checked_rem(b))
+    });
+    engine.register_fn("add", |a: FLOAT, b: FLOAT| a + b);
+    engine.register_fn("sub", |a: FLOAT, b: FLOAT| a - b);
+    engine.register_fn("mul", |a: FLOAT, b: FLOAT| a * b);
+    engine.register_result_fn("div", |a: FLOAT, b: FLOAT| match b {
+        b if b == 0.0 => Err("div: division by zero".into()),
+        b => Ok(Dynamic::from(a / b)),
+    });
+    engine.register_result_fn("modulo", |a: FLOAT, b: FLOAT| match b {
+        b if b == 0.0 => Err("modulo: division by zero".into()),
+        b => Ok(Dynamic::from(a % b)),
+    });
+}
+
+fn register_string(engine: &mut Engine) {
+    engine.register_fn("upper", |s: ImmutableString| -> ImmutableString {
+        s.to_uppercase().into()
+    });
+    engine.register_fn("lower", |s: ImmutableString| -> ImmutableString {
+        s.to_lowercase().into()
+    });
+    engine.register_fn("trim", |s: ImmutableString| -> ImmutableString {
+        s.trim().to_string().into()
+    });
+    engine.register_fn(
+        "starts_with",
+        |s: ImmutableString, prefix: ImmutableString| s.starts_with(prefix.as_str()),
+    );
+    engine.register_fn(
+        "ends_with",
+        |s: ImmutableString, suffix: ImmutableString| s.ends_with(suffix.as_str()),
+    );
+    engine.register_fn("split", |s: ImmutableString, separator: ImmutableString| {
+        s.split(separator.as_str())
+            .map(|part| Dynamic::from(ImmutableString::from(part.to_string())))
+            .collect::<Array>()
+    });
+    engine.register_fn("str", |x: FLOAT| -> ImmutableString {
+        x.to_string().into()
+    });
+    engine.register_fn("str", |x: INT| -> ImmutableString { x.to_string().into() });
+    engine.register_fn("str", |x: bool| -> ImmutableString { x.to_string().into() });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine() -> Engine {
+        let mut engine = Engine::new();
+        register_std(&mut engine);
+        engine
+    }
+
+    #[test]
+    fn vector_math() {
+        let engine = engine();
+        let dot = engine.eval::<FLOAT>("dot(vec3(1.0, 2.0, 3.0), vec3(4.0, 5.0, 6.0))");
+        assert_eq!(dot.unwrap(), 32.0);
+        let cross = engine.eval::<Vec3>("cross(vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0))");
+        assert_eq!(cross.unwrap(), Vec3::Z);
+        let length = engine.eval::<FLOAT>("let v = vec3(3.0, 0.0, 0.0); v.y = 4.0; length(v)");
+        assert_eq!(length.unwrap(), 5.0);
+    }
+
+    #[test]
+    fn vector_round_trip() {
+        let engine = engine();
+        let mut scope = rhai::Scope::new();
+        scope.push("v", Vec3::new(1.0, 2.0, 3.0));
+        let v = engine
+            .eval_with_scope::<Vec3>(&mut scope, "v + vec3(1.0, 1.0, 1.0) - vec3(0.0, 0.0, 4.0)");
+        assert_eq!(v.unwrap(), Vec3::new(2.0, 3.0, 0.0));
+    }
+
+    #[test]
+    fn transforms() {
+        let engine = engine();
+        let x = engine.eval::<FLOAT>("let t = transform(); t.translation.x + 1.0");
+        assert_eq!(x.unwrap(), 1.0);
+        let t = engine.eval::<Transform>(
+            "let t = transform(); t.translation = t.translation + vec3(1.0, 2.0, 3.0); t",
+        );
+        assert_eq!(t.unwrap().translation, Vec3::new(1.0, 2.0, 3.0));
+
+        let mut scope = rhai::Scope::new();
+        let rotation = Quat::from_rotation_y(1.0);
+        scope.push("t", Transform::from_rotation(rotation));
+        let t = engine.eval_with_scope::<Transform>(
+            &mut scope,
+            "let r = t.rotation; let u = transform(); u.rotation = r; u",
+        );
+        assert_eq!(t.unwrap().rotation, rotation);
+    }
+
+    #[test]
+    fn common_math() {
+        let engine = engine();
+        assert_eq!(engine.eval::<FLOAT>("clamp(7.5, 0.0, 5.0)").unwrap(), 5.0);
+        assert_eq!(engine.eval::<INT>("clamp(-3, 0, 5)").unwrap(), 0);
+        assert_eq!(engine.eval::<FLOAT>("lerp(2.0, 4.0, 0.5)").unwrap(), 3.0);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let engine = engine();
+        let int = |script: &str| engine.eval::<Dynamic>(script).unwrap().as_int().unwrap();
+        let float = |script: &str| engine.eval::<Dynamic>(script).unwrap().as_float().unwrap();
+        assert_eq!(int("add(3, 1)"), 4);
+        assert_eq!(int("sub(3, 1)"), 2);
+        assert_eq!(int("mul(3, 2)"), 6);
+        assert_eq!(int("div(7, 2)"), 3);
+        assert_eq!(int("modulo(7, 2)"), 1);
+        assert_eq!(float("add(3.0, 0.5)"), 3.5);
+        assert_eq!(float("sub(3.0, 0.5)"), 2.5);
+        assert_eq!(float("mul(3.0, 0.5)"), 1.5);
+        assert_eq!(float("div(3.0, 0.5)"), 6.0);
+        assert_eq!(float("modulo(3.5, 2.0)"), 1.5);
+        assert!(engine.eval::<Dynamic>("div(1, 0)").is_err());
+        assert!(engine.eval::<Dynamic>("modulo(1, 0)").is_err());
+        assert!(engine.eval::<Dynamic>("div(1.0, 0.0)").is_err());
+        assert!(engine
+            .eval::<Dynamic>("add(9223372036854775807, 1)")
+            .is_err());
+    }
+
+    #[test]
+    fn string_helpers() {
+        let engine = engine();
+        let upper = engine.eval::<ImmutableString>(r#"upper(trim("  agent "))"#);
+        assert_eq!(upper.unwrap().as_str(), "AGENT");
+        assert!(engine
+            .eval::<bool>(r#"starts_with("agent_1", "agent")"#)
+            .unwrap());
+        let parts = engine.eval::<Array>(r#"split("a,b,c", ",")"#).unwrap();
+        assert_eq!(parts.len(), 3);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{error_message, script, Script, ScriptContext};
+use bevy::prelude::*;
+use simula_inspector::{egui, Inspector, Inspectors};
+
+pub struct ScriptWatchPlugin;
+
+impl Plugin for ScriptWatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ScriptWatches::default())
+            .add_startup_system(setup)
+            .add_system(update);
+    }
+}
+
+/// A script expression evaluated every frame against a script context
+pub struct ScriptWatch {
+    pub script: Script,
+    pub context: Option<Handle<ScriptContext>>,
+    pub value: Result<String, String>,
+}
+
+impl ScriptWatch {
+    pub fn new(expression: impl Into<String>) -> Self {
+        let mut script = Script::default();
+        script.script = expression.into().into();
+        Self {
+            script,
+            context: None,
+            value: Ok("".into()),
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+pub struct ScriptWatches {
+    pub watches: Vec<ScriptWatch>,
+    pub show: bool,
+    editing: String,
+}
+
+impl ScriptWatches {
+    pub fn add(&mut self, watch: ScriptWatch) {
+        self.watches.push(watch);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.watches.len() {
+            self.watches.remove(index);
+        }
+    }
+}
+
+fn setup(mut inspectors: ResMut<Inspectors>) {
+    inspectors.add(Inspector { menu_ui, window_ui });
+}
+
+/// Re-evaluate all watches, errors are kept in the watch instead of logged
+fn update(mut watches: ResMut<ScriptWatches>, mut script_ctxs: ResMut<Assets<ScriptContext>>) {
+    if !watches.show {
+        return;
+    }
+    for watch in watches.watches.iter_mut() {
+        let Some(script_ctx) = watch
+            .context
+            .as_ref()
+            .and_then(|handle| script_ctxs.get_mut(handle))
+        else {
+            watch.value = Err("No script context".into());
+            continue;
+        };
+        if !watch.script.is_compiled() {
+            if let Err(err) = watch.script.compile(script_ctx) {
+                watch.value = Err(error_message(&err));
+                continue;
+            }
+        }
+        watch.value = watch
+            .script
+            .eval::<script::Dynamic>(script_ctx)
+            .map(|value| value.to_string())
+            .map_err(|err| error_message(&err));
+    }
+}
+
+fn menu_ui(ui: &mut egui::Ui, world: &mut World) {
+    let mut watches = world.resource_mut::<ScriptWatches>();
+    let show = watches.show;
+    if ui
+        .add(egui::SelectableLabel::new(show, "👁 Watches"))
+        .clicked()
+    {
+        watches.show = !show;
+    }
+}
+
+fn window_ui(context: &mut egui::Context, world: &mut World) {
+    if !world.resource::<ScriptWatches>().show {
+        return;
+    }
+
+    // Script contexts watches can be evaluated against
+    let handles: Vec<Handle<ScriptContext>> = {
+        let script_ctxs = world.resource::<Assets<ScriptContext>>();
+        script_ctxs.ids().map(Handle::weak).collect::<Vec<_>>()
+    };
+
+    let mut open = true;
+    let mut watches = world.resource_mut::<ScriptWatches>();
+    egui::Window::new("👁 Watches")
+        .open(&mut open)
+        .default_size(egui::vec2(300.0, 200.0))
+        .show(context, |ui| {
+            let mut remove = None;
+            egui::Grid::new("Script Watches")
+                .striped(true)
+                .show(ui, |ui| {
+                    for (idx, watch) in watches.watches.iter_mut().enumerate() {
+                        ui.label(watch.script.script.as_ref());
+                        egui::ComboBox::from_id_source(("Script Watch Context", idx))
+                            .selected_text(
+                                watch.context.as_ref().map_or("None".to_string(), |handle| {
+                                    format!("{:?}", handle.id())
+                                }),
+                            )
+                            .show_ui(ui, |ui| {
+                                for handle in &handles {
+                                    let selected = watch.context.as_ref() == Some(handle);
+                                    if ui
+                                        .selectable_label(selected, format!("{:?}", handle.id()))
+                                        .clicked()
+                                    {
+                                        watch.context = Some(handle.clone());
+                                    }
+                                }
+                            });
+                        match &watch.value {
+                            Ok(value) => {
+                                ui.label(value);
+                            }
+                            Err(err) => {
+                                ui.label(egui::RichText::new(err).color(egui::Color32::RED));
+                            }
+                        }
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(idx);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(idx) = remove {
+                watches.remove(idx);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                let mut editing = std::mem::take(&mut watches.editing);
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut editing)
+                        .hint_text("expression")
+                        .code_editor(),
+                );
+                let submit = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if (ui.button("✚").clicked() || submit) && !editing.trim().is_empty() {
+                    let mut watch = ScriptWatch::new(editing.trim());
+                    watch.context = handles.first().cloned();
+                    watches.add(watch);
+                    editing.clear();
+                }
+                watches.editing = editing;
+            });
+        });
+
+    if !open {
+        watches.show = false;
+    }
+}
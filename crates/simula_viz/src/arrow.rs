@@ -0,0 +1,155 @@
+use bevy::math::{Vec2, Vec3};
+use bevy::render::{
+    mesh::{Indices, Mesh},
+    render_resource::PrimitiveTopology,
+};
+
+/// A cylinder shaft capped by a cone head, from the origin to length along +y
+#[derive(Debug, Clone, Copy)]
+pub struct Arrow {
+    /// Total length, including the head.
+    pub length: f32,
+    /// Radius of the shaft on the xz plane.
+    pub shaft_radius: f32,
+    /// Length of the head, clamped to the total length.
+    pub head_length: f32,
+    /// Radius of the head base on the xz plane.
+    pub head_radius: f32,
+    /// Number of segments around the y axis.
+    pub segments: usize,
+}
+
+impl Default for Arrow {
+    fn default() -> Self {
+        Arrow {
+            length: 1.0,
+            shaft_radius: 0.02,
+            head_length: 0.2,
+            head_radius: 0.06,
+            segments: 16,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ArrowBuilder {
+    vs: Vec<[f32; 3]>,
+    vns: Vec<[f32; 3]>,
+    vts: Vec<[f32; 2]>,
+    tris: Vec<u32>,
+}
+
+impl ArrowBuilder {
+    fn push(&mut self, v: Vec3, vn: Vec3, vt: Vec2) -> u32 {
+        self.vs.push(v.into());
+        self.vns.push(vn.into());
+        self.vts.push(vt.into());
+        (self.vs.len() - 1) as u32
+    }
+
+    // Disk facing -y at height y
+    fn disk(&mut self, y: f32, radius: f32, directions: &[Vec3]) {
+        let center = self.push(Vec3::Y * y, Vec3::NEG_Y, Vec2::splat(0.5));
+        let first = self.vs.len() as u32;
+        for direction in directions {
+            let uv = Vec2::new(direction.x, direction.z) * 0.5 + 0.5;
+            self.push(*direction * radius + Vec3::Y * y, Vec3::NEG_Y, uv);
+        }
+        for j in 0..directions.len() as u32 - 1 {
+            self.tris.extend([center, first + j, first + j + 1]);
+        }
+    }
+
+    // Side between two rings, with the top ring shrunk to a point for a cone
+    fn side(&mut self, bottom: (f32, f32), top: (f32, f32), directions: &[Vec3]) {
+        let ((bottom_y, bottom_radius), (top_y, top_radius)) = (bottom, top);
+        let slope = Vec2::new(top_y - bottom_y, bottom_radius - top_radius).normalize_or_zero();
+        let segments = directions.len() - 1;
+        let first = self.vs.len() as u32;
+        for (j, direction) in directions.iter().enumerate() {
+            let s = j as f32 / segments as f32;
+            let normal = *direction * slope.x + Vec3::Y * slope.y;
+            self.push(
+                *direction * bottom_radius + Vec3::Y * bottom_y,
+                normal,
+                Vec2::new(s, 1.0),
+            );
+            self.push(
+                *direction * top_radius + Vec3::Y * top_y,
+                normal,
+                Vec2::new(s, 0.0),
+            );
+        }
+        for j in 0..segments as u32 {
+            let b0 = first + j * 2;
+            let t0 = b0 + 1;
+            let b1 = b0 + 2;
+            let t1 = b0 + 3;
+            self.tris.extend([b0, t0, b1]);
+            self.tris.extend([b1, t0, t1]);
+        }
+    }
+}
+
+impl From<Arrow> for Mesh {
+    fn from(arrow: Arrow) -> Self {
+        let Arrow {
+            length,
+            shaft_radius,
+            head_length,
+            head_radius,
+            segments,
+        } = arrow;
+        let head_length = head_length.clamp(0.0, length);
+        let shaft_length = length - head_length;
+
+        // Directions around the y axis, the last one duplicating the first for the UV seam
+        let to_theta = 2.0 * std::f32::consts::PI / segments as f32;
+        let directions: Vec<Vec3> = (0..=segments)
+            .map(|j| {
+                let (sin_theta, cos_theta) = (j as f32 * to_theta).sin_cos();
+                Vec3::new(cos_theta, 0.0, sin_theta)
+            })
+            .collect();
+
+        let mut builder = ArrowBuilder::default();
+        builder.disk(0.0, shaft_radius, &directions);
+        builder.side(
+            (0.0, shaft_radius),
+            (shaft_length, shaft_radius),
+            &directions,
+        );
+        builder.disk(shaft_length, head_radius, &directions);
+        builder.side((shaft_length, head_radius), (length, 0.0), &directions);
+
+        let ArrowBuilder { vs, vns, vts, tris } = builder;
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vs);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vns);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vts);
+        mesh.set_indices(Some(Indices::U32(tris)));
+        mesh.generate_tangents()
+            .expect("Failed to generate tangents");
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrow_height_is_length() {
+        for length in [0.5, 1.0, 3.0] {
+            let mesh = Mesh::from(Arrow {
+                length,
+                ..Default::default()
+            });
+            let aabb = mesh.compute_aabb().unwrap();
+            let height = aabb.max().y - aabb.min().y;
+            assert!((height - length).abs() < 1e-5);
+            assert!(aabb.min().y.abs() < 1e-5);
+        }
+    }
+}
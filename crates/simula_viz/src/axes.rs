@@ -2,13 +2,17 @@ use super::lines::{Lines, LinesMaterial};
 use bevy::{
     prelude::*,
     render::view::{ComputedVisibility, Visibility},
+    window::PrimaryWindow,
 };
+use simula_core::map_range::map_range;
 
 #[derive(Reflect, Component)]
 #[reflect(Component)]
 pub struct Axes {
     pub size: f32,
     pub inner_offset: f32,
+    /// Show "X", "Y" and "Z" labels at the tip of each axis.
+    pub axis_labels: bool,
 }
 
 impl Default for Axes {
@@ -16,6 +20,7 @@ impl Default for Axes {
         Axes {
             size: 1.,
             inner_offset: 0.,
+            axis_labels: false,
         }
     }
 }
@@ -56,12 +61,114 @@ fn update(mut query: Query<(&mut Lines, &Axes, &Visibility), With<Handle<LinesMa
     }
 }
 
+/// Screen space label at the tip of an axis, following the axes entity.
+#[derive(Component)]
+pub struct AxisLabel {
+    pub axes: Entity,
+    pub direction: Vec3,
+}
+
+// Label text, color and direction for each axis, as drawn by `update`
+const AXIS_LABELS: [(&str, Color, Vec3); 3] = [
+    ("X", Color::RED, Vec3::X),
+    ("Y", Color::GREEN, Vec3::Y),
+    ("Z", Color::BLUE, Vec3::NEG_Z),
+];
+
+fn spawn_labels(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    axes_query: Query<(Entity, &Axes), Changed<Axes>>,
+    labels: Query<(Entity, &AxisLabel)>,
+) {
+    for (entity, axes) in axes_query.iter() {
+        let mut spawned = false;
+        for (label_entity, label) in labels.iter().filter(|(_, label)| label.axes == entity) {
+            spawned = true;
+            if !axes.axis_labels {
+                commands.entity(label_entity).despawn_recursive();
+            }
+        }
+        if !axes.axis_labels || spawned {
+            continue;
+        }
+        for (text, color, direction) in AXIS_LABELS {
+            commands
+                .spawn(TextBundle {
+                    text: Text::from_section(
+                        text,
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                            font_size: 14.0,
+                            color,
+                        },
+                    ),
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        ..default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..default()
+                })
+                .insert(AxisLabel {
+                    axes: entity,
+                    direction,
+                })
+                .insert(Name::new(format!("Axis Label: {}", text)));
+        }
+    }
+}
+
+fn update_labels(
+    mut commands: Commands,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    axes_query: Query<(&Axes, &GlobalTransform, &ComputedVisibility)>,
+    mut labels: Query<(Entity, &AxisLabel, &mut Style, &mut Visibility)>,
+) {
+    let camera = cameras.iter().find(|(camera, _)| camera.is_active);
+    let window = windows.get_single().ok();
+    for (entity, label, mut style, mut visibility) in labels.iter_mut() {
+        // Axes are gone, so is the label
+        let Ok((axes, axes_transform, axes_visibility)) = axes_query.get(label.axes) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        let tip = axes_transform.transform_point(label.direction * (axes.inner_offset + axes.size));
+        let screen_pos = match (camera, window) {
+            (Some((camera, camera_transform)), Some(window)) if axes_visibility.is_visible() => {
+                camera
+                    .world_to_ndc(camera_transform, tip)
+                    .filter(|ndc| ndc.z > 0.0)
+                    .map(|ndc| {
+                        Vec2::new(
+                            map_range(ndc.x, (-1.0, 1.0), (0.0, window.width())),
+                            map_range(ndc.y, (-1.0, 1.0), (window.height(), 0.0)),
+                        )
+                    })
+            }
+            _ => None,
+        };
+
+        if let Some(screen_pos) = screen_pos {
+            style.position.left = Val::Px(screen_pos.x);
+            style.position.top = Val::Px(screen_pos.y);
+            *visibility = Visibility::Inherited;
+        } else {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
 pub struct AxesPlugin;
 
 impl Plugin for AxesPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Axes>()
             .add_system(update)
-            .add_system(add_lines);
+            .add_system(add_lines)
+            .add_system(spawn_labels)
+            .add_system(update_labels);
     }
 }
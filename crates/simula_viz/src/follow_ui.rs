@@ -1,4 +1,4 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::{prelude::*, render::primitives::Aabb, window::PrimaryWindow};
 use simula_core::{
     ease::EaseFunction,
     map_range::{map_range, map_range_eased},
@@ -15,11 +15,27 @@ impl Plugin for FollowUIPlugin {
 #[derive(Component)]
 pub struct FollowUICamera;
 
+/// Meshes with this component hide follow ui panels whose anchor is behind them.
+#[derive(Component)]
+pub struct FollowUIOccluder;
+
+/// Added to anchors in range of the camera and on screen, removed otherwise.
 #[derive(Component, Debug)]
 #[component(storage = "SparseSet")]
 pub struct FollowUIVisibility {
     pub screen_pos: Vec3,
     pub alpha: f32,
+    /// Anchor is behind a `FollowUIOccluder`, as seen from the camera. Alpha is zero then.
+    pub occluded: bool,
+    /// Anchor is in front of the camera and inside the window, within the screen margin.
+    pub on_screen: bool,
+}
+
+impl FollowUIVisibility {
+    /// Whether the panel should be drawn.
+    pub fn visible(&self) -> bool {
+        self.on_screen && !self.occluded
+    }
 }
 
 #[derive(Component, Debug)]
@@ -30,6 +46,11 @@ pub struct FollowUI {
     pub max_height: f32,
     pub max_view_angle: f32,
     pub size: Vec2,
+    /// Pixels beyond the window edges where the anchor still counts as on screen.
+    pub screen_margin: f32,
+    /// Distance in front of the anchor where occluders are ignored, so the anchor's own
+    /// geometry doesn't hide its panel.
+    pub occlusion_bias: f32,
 }
 
 impl Default for FollowUI {
@@ -41,10 +62,70 @@ impl Default for FollowUI {
             max_height: 2.0,
             max_view_angle: 30.0,
             size: Vec2::new(100.0, 100.0),
+            screen_margin: 0.0,
+            occlusion_bias: 0.1,
         }
     }
 }
 
+/// Where a point lands relative to the camera frustum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowUIScreen {
+    Behind,
+    OffScreen,
+    OnScreen,
+}
+
+/// Classify a point in normalized device coordinates, with a margin in ndc units beyond the
+/// screen edges.
+pub fn classify_ndc(ndc: Vec3, margin: Vec2) -> FollowUIScreen {
+    if !ndc.is_finite() || ndc.z <= 0.0 {
+        FollowUIScreen::Behind
+    } else if ndc.x.abs() > 1.0 + margin.x || ndc.y.abs() > 1.0 + margin.y {
+        FollowUIScreen::OffScreen
+    } else {
+        FollowUIScreen::OnScreen
+    }
+}
+
+/// Distance along a ray to where it enters a box, if it hits it in front of the origin.
+pub fn ray_aabb(origin: Vec3, direction: Vec3, min: Vec3, max: Vec3) -> Option<f32> {
+    let inv = direction.recip();
+    let t0 = (min - origin) * inv;
+    let t1 = (max - origin) * inv;
+    let t_enter = t0.min(t1).max_element();
+    let t_exit = t0.max(t1).min_element();
+    (t_exit >= t_enter.max(0.0)).then_some(t_enter.max(0.0))
+}
+
+// Whether an occluder, other than the anchor, is between the camera and the anchor
+fn is_occluded(
+    anchor: Entity,
+    camera_pos: Vec3,
+    anchor_pos: Vec3,
+    bias: f32,
+    occluders: &Query<(Entity, &Aabb, &GlobalTransform), With<FollowUIOccluder>>,
+) -> bool {
+    let distance = camera_pos.distance(anchor_pos) - bias;
+    if distance <= 0.0 {
+        return false;
+    }
+    occluders
+        .iter()
+        .filter(|(entity, _, _)| *entity != anchor)
+        .any(|(_, aabb, transform)| {
+            // Test in occluder space, the hit distance is scaled back to world
+            let world_to_local = transform.affine().inverse();
+            let origin = world_to_local.transform_point3(camera_pos);
+            let target = world_to_local.transform_point3(anchor_pos);
+            let direction = target - origin;
+            let min = Vec3::from(aabb.min());
+            let max = Vec3::from(aabb.max());
+            ray_aabb(origin, direction, min, max)
+                .map_or(false, |t| t * (distance + bias) < distance)
+        })
+}
+
 pub fn follow_ui(
     mut commands: Commands,
     windows: Query<&Window, With<PrimaryWindow>>,
@@ -55,6 +136,7 @@ pub fn follow_ui(
         &GlobalTransform,
     )>,
     camera_query: Query<(&Camera, &GlobalTransform), With<FollowUICamera>>,
+    occluders: Query<(Entity, &Aabb, &GlobalTransform), With<FollowUIOccluder>>,
 ) {
     if camera_query.iter().count() > 1 {
         warn!("Only one FollowUICamera is allowed");
@@ -78,28 +160,21 @@ pub fn follow_ui(
                 && camera_distance < follow_ui.max_distance
                 && view_angle < follow_ui.max_view_angle
             {
-                if let Some(mut screen_pos) =
-                    camera.world_to_ndc(camera_global_transform, ui_global_transform.translation())
-                {
-                    if screen_pos.z > 0.0 {
-                        if let Ok(window) = windows.get_single() {
-                            screen_pos.x =
-                                map_range(screen_pos.x, (-1.0, 1.0), (0.0, window.width()));
-                            screen_pos.y =
-                                map_range(screen_pos.y, (-1.0, 1.0), (window.height(), 0.0));
-                            screen_pos.x -= follow_ui.size.x / 2.0;
-                            screen_pos.y -= follow_ui.size.y / 2.0;
-                            Some(screen_pos)
-                        } else {
-                            // No primary window
-                            None
-                        }
-                    } else {
-                        // behind camera
-                        None
-                    }
+                if let Ok(window) = windows.get_single() {
+                    let ndc = camera
+                        .world_to_ndc(camera_global_transform, ui_global_transform.translation())
+                        .unwrap_or(Vec3::NAN);
+                    let window_size = Vec2::new(window.width(), window.height());
+                    let margin = 2.0 * follow_ui.screen_margin / window_size;
+                    let on_screen = classify_ndc(ndc, margin) == FollowUIScreen::OnScreen;
+                    let mut screen_pos = if ndc.is_finite() { ndc } else { Vec3::ZERO };
+                    screen_pos.x = map_range(screen_pos.x, (-1.0, 1.0), (0.0, window.width()));
+                    screen_pos.y = map_range(screen_pos.y, (-1.0, 1.0), (window.height(), 0.0));
+                    screen_pos.x -= follow_ui.size.x / 2.0;
+                    screen_pos.y -= follow_ui.size.y / 2.0;
+                    Some((screen_pos, on_screen))
                 } else {
-                    // off screen
+                    // No primary window
                     None
                 }
             } else {
@@ -107,7 +182,19 @@ pub fn follow_ui(
                 None
             };
 
-            if let Some(screen_pos) = screen_pos {
+            // Behind the camera or off screen there is no position to draw the panel at
+            let screen_pos = screen_pos.filter(|(_, on_screen)| *on_screen);
+
+            if let Some((screen_pos, on_screen)) = screen_pos {
+                let occluded = on_screen
+                    && is_occluded(
+                        entity,
+                        camera_global_transform.translation(),
+                        ui_global_transform.translation(),
+                        follow_ui.occlusion_bias,
+                        &occluders,
+                    );
+
                 let camera_pos_height_alpha = map_range_eased(
                     camera_height,
                     (0.0, follow_ui.max_height),
@@ -136,18 +223,28 @@ pub fn follow_ui(
                     EaseFunction::SineInOut,
                 )
                 .clamp(0.0, 1.0);
-                let alpha = camera_pos_height_alpha
-                    .min(camera_neg_height_alpha)
-                    .min(camera_distance_alpha)
-                    .min(view_angle_alpha);
+                // Occluded panels fade out completely
+                let alpha = if occluded {
+                    0.0
+                } else {
+                    camera_pos_height_alpha
+                        .min(camera_neg_height_alpha)
+                        .min(camera_distance_alpha)
+                        .min(view_angle_alpha)
+                };
 
                 if let Some(mut visibility) = visibility {
                     visibility.screen_pos = screen_pos;
                     visibility.alpha = alpha;
+                    visibility.occluded = occluded;
+                    visibility.on_screen = on_screen;
                 } else {
-                    commands
-                        .entity(entity)
-                        .insert(FollowUIVisibility { screen_pos, alpha });
+                    commands.entity(entity).insert(FollowUIVisibility {
+                        screen_pos,
+                        alpha,
+                        occluded,
+                        on_screen,
+                    });
                 }
             } else {
                 commands.entity(entity).remove::<FollowUIVisibility>();
@@ -155,3 +252,57 @@ pub fn follow_ui(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_ndc_in_front() {
+        let margin = Vec2::ZERO;
+        assert_eq!(
+            classify_ndc(Vec3::new(0.0, 0.0, 0.5), margin),
+            FollowUIScreen::OnScreen
+        );
+        assert_eq!(
+            classify_ndc(Vec3::new(-1.0, 1.0, 0.5), margin),
+            FollowUIScreen::OnScreen
+        );
+    }
+
+    #[test]
+    fn classify_ndc_behind() {
+        let margin = Vec2::splat(0.5);
+        assert_eq!(
+            classify_ndc(Vec3::new(0.0, 0.0, -0.5), margin),
+            FollowUIScreen::Behind
+        );
+        assert_eq!(
+            classify_ndc(Vec3::new(0.0, 0.0, 0.0), margin),
+            FollowUIScreen::Behind
+        );
+        assert_eq!(classify_ndc(Vec3::NAN, margin), FollowUIScreen::Behind);
+    }
+
+    #[test]
+    fn classify_ndc_outside_margin() {
+        let margin = Vec2::new(0.2, 0.1);
+        assert_eq!(
+            classify_ndc(Vec3::new(1.1, 0.0, 0.5), Vec2::ZERO),
+            FollowUIScreen::OffScreen
+        );
+        // Inside the margin still counts as on screen
+        assert_eq!(
+            classify_ndc(Vec3::new(1.15, -1.05, 0.5), margin),
+            FollowUIScreen::OnScreen
+        );
+        assert_eq!(
+            classify_ndc(Vec3::new(1.25, 0.0, 0.5), margin),
+            FollowUIScreen::OffScreen
+        );
+        assert_eq!(
+            classify_ndc(Vec3::new(0.0, -1.15, 0.5), margin),
+            FollowUIScreen::OffScreen
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use super::lines::{Lines, LinesMaterial};
 use bevy::{
     prelude::*,
-    render::view::{ComputedVisibility, Visibility},
+    reflect::TypeUuid,
+    render::{
+        render_resource::{AsBindGroup, ShaderRef},
+        view::{ComputedVisibility, Visibility},
+    },
 };
 
 #[derive(Component, Reflect)]
@@ -11,6 +15,12 @@ pub struct Grid {
     pub divisions: u32,
     pub start_color: Color,
     pub end_color: Color,
+    /// Render a ground plane grid fading with distance from the camera, instead of lines.
+    pub infinite: bool,
+    /// Line thickness in pixels of the infinite grid.
+    pub thickness: f32,
+    /// Distance from the camera where the infinite grid has faded out.
+    pub fade_distance: f32,
 }
 
 impl Default for Grid {
@@ -20,6 +30,9 @@ impl Default for Grid {
             divisions: 10,
             start_color: Color::rgb(0.025, 0.02, 0.03),
             end_color: Color::rgb(0.025, 0.02, 0.03),
+            infinite: false,
+            thickness: 1.0,
+            fade_distance: 100.0,
         }
     }
 }
@@ -42,6 +55,9 @@ fn add_lines(mut commands: Commands, query: Query<Entity, (With<Grid>, Without<L
 
 fn update(mut query: Query<(&mut Lines, &Grid, &Visibility), With<Handle<LinesMaterial>>>) {
     for (mut lines, grid, visibility) in query.iter_mut() {
+        if grid.infinite {
+            continue;
+        }
         match visibility {
             Visibility::Visible | Visibility::Inherited => {
                 let center = grid.divisions / 2;
@@ -75,12 +91,149 @@ fn update(mut query: Query<(&mut Lines, &Grid, &Visibility), With<Handle<LinesMa
     }
 }
 
+/// Material of the infinite grid plane, drawing lines on the world xz plane.
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "3f6f5e2a-5a0b-4c2e-9a53-7c1d8e0b4f21"]
+pub struct GridMaterial {
+    #[uniform(0)]
+    pub color: Color,
+    /// Distance between lines.
+    #[uniform(0)]
+    pub spacing: f32,
+    /// Line thickness in pixels.
+    #[uniform(0)]
+    pub thickness: f32,
+    /// Distance from the camera where lines have faded out.
+    #[uniform(0)]
+    pub fade_distance: f32,
+}
+
+impl Default for GridMaterial {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(0.025, 0.02, 0.03),
+            spacing: 1.0,
+            thickness: 1.0,
+            fade_distance: 100.0,
+        }
+    }
+}
+
+impl Material for GridMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/grid.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+}
+
+/// Plane rendering an infinite grid, child of the grid entity.
+#[derive(Component)]
+pub struct InfiniteGridPlane;
+
+fn spawn_infinite_grid(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<GridMaterial>>,
+    grids: Query<(Entity, &Grid, Option<&Children>), Changed<Grid>>,
+    planes: Query<Entity, With<InfiniteGridPlane>>,
+) {
+    for (entity, grid, children) in grids.iter() {
+        let mut spawned = false;
+        for plane in children.into_iter().flatten() {
+            if planes.contains(*plane) {
+                spawned = true;
+                if !grid.infinite {
+                    commands.entity(*plane).despawn_recursive();
+                }
+            }
+        }
+        if !grid.infinite || spawned {
+            continue;
+        }
+        let material = GridMaterial {
+            color: grid.start_color,
+            spacing: (grid.size as f32 / grid.divisions.max(1) as f32).max(f32::EPSILON),
+            thickness: grid.thickness,
+            fade_distance: grid.fade_distance,
+        };
+        let size = material.fade_distance * 2.0;
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn(MaterialMeshBundle {
+                    mesh: meshes.add(Mesh::from(shape::Plane { size })),
+                    material: materials.add(material),
+                    ..default()
+                })
+                .insert(InfiniteGridPlane)
+                .insert(Name::new("Grid: Infinite"));
+        });
+    }
+}
+
+// Keep the plane under the camera, lines stay put as they are drawn in world space
+fn follow_camera(
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    grids: Query<&GlobalTransform, With<Grid>>,
+    mut planes: Query<(&Parent, &mut Transform), With<InfiniteGridPlane>>,
+) {
+    let Some((_, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active) else {
+        return;
+    };
+    for (parent, mut transform) in planes.iter_mut() {
+        let Ok(grid_transform) = grids.get(parent.get()) else {
+            continue;
+        };
+        let camera_position = grid_transform
+            .affine()
+            .inverse()
+            .transform_point3(camera_transform.translation());
+        transform.translation = Vec3::new(camera_position.x, 0.0, camera_position.z);
+    }
+}
+
 pub struct GridPlugin;
 
 impl Plugin for GridPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Grid>()
+            .add_plugin(MaterialPlugin::<GridMaterial>::default())
             .add_system(update)
-            .add_system(add_lines);
+            .add_system(add_lines)
+            .add_system(spawn_infinite_grid)
+            .add_system(follow_camera);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infinite_grid_attaches_material() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<Mesh>()
+            .add_asset::<GridMaterial>()
+            .add_system(spawn_infinite_grid);
+        app.world.spawn(Grid {
+            infinite: true,
+            thickness: 2.0,
+            fade_distance: 50.0,
+            ..default()
+        });
+        app.update();
+
+        let mut planes = app
+            .world
+            .query_filtered::<&Handle<GridMaterial>, With<InfiniteGridPlane>>();
+        let handle = planes.single(&app.world).clone();
+        let materials = app.world.resource::<Assets<GridMaterial>>();
+        let material = materials.get(&handle).unwrap();
+        assert_eq!(material.thickness, 2.0);
+        assert_eq!(material.fade_distance, 50.0);
     }
 }
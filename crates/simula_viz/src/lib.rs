@@ -1,3 +1,4 @@
+pub mod arrow;
 pub mod axes;
 pub mod ease;
 pub mod follow_ui;
@@ -9,4 +10,5 @@ pub mod pointcloud;
 pub mod rod;
 pub mod signal;
 pub mod spline;
+pub mod torus;
 pub mod voxel;
@@ -11,12 +11,43 @@ use bevy::{
     },
 };
 
+/// How a line is drawn, dashes and dots are measured in world units
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect, Default)]
+pub enum LineStyle {
+    #[default]
+    Solid,
+    Dashed {
+        dash: f32,
+        gap: f32,
+    },
+    Dotted {
+        spacing: f32,
+    },
+}
+
+impl LineStyle {
+    // Dots are short dashes, a fraction of the spacing between them
+    const DOT_RATIO: f32 = 0.2;
+
+    /// Length of each drawn segment and of the gap after it, None for solid lines
+    fn pattern(&self) -> Option<(f32, f32)> {
+        match *self {
+            LineStyle::Solid => None,
+            LineStyle::Dashed { dash, gap } => Some((dash, gap)),
+            LineStyle::Dotted { spacing } => {
+                Some((spacing * Self::DOT_RATIO, spacing * (1.0 - Self::DOT_RATIO)))
+            }
+        }
+    }
+}
+
 #[derive(Clone, Reflect, Default)]
 pub struct Line {
     start: Vec3,
     end: Vec3,
     start_color: Color,
     end_color: Color,
+    style: LineStyle,
 }
 
 impl Line {
@@ -26,8 +57,34 @@ impl Line {
             end,
             start_color,
             end_color,
+            style: LineStyle::Solid,
         }
     }
+
+    pub fn with_style(mut self, style: LineStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Line segments as start and end fractions, split in dashes according to the line style
+    fn segments(&self) -> Vec<(f32, f32)> {
+        let length = self.start.distance(self.end);
+        let pattern = self
+            .style
+            .pattern()
+            .filter(|(dash, gap)| *dash > 0.0 && *gap > 0.0 && length > 0.0);
+        let Some((dash, gap)) = pattern else {
+            return vec![(0.0, 1.0)];
+        };
+
+        let mut segments = vec![];
+        let mut offset = 0.0;
+        while offset < length && segments.len() < MAX_LINES {
+            segments.push((offset / length, (offset + dash).min(length) / length));
+            offset += dash + gap;
+        }
+        segments
+    }
 }
 
 pub const MAX_LINES: usize = 128000;
@@ -50,7 +107,14 @@ impl Lines {
     }
 
     pub fn line_gradient(&mut self, start: Vec3, end: Vec3, start_color: Color, end_color: Color) {
-        let line = Line::new(start, end, start_color, end_color);
+        self.push(Line::new(start, end, start_color, end_color));
+    }
+
+    pub fn line_styled(&mut self, start: Vec3, end: Vec3, color: Color, style: LineStyle) {
+        self.push(Line::new(start, end, color, color).with_style(style));
+    }
+
+    pub fn push(&mut self, line: Line) {
         if self.lines.len() == MAX_LINES {
             self.lines.pop();
         }
@@ -206,20 +270,24 @@ fn generate_lines(
             continue;
         }
 
-        let mut points = vec![];
-        let mut colors = vec![];
-
         let num_lines = lines.lines.len();
 
-        points.resize(num_lines * 2, [0f32; 3]);
-        colors.resize(num_lines * 2, [0f32; 4]);
+        let mut points: Vec<[f32; 3]> = Vec::with_capacity(num_lines * 2);
+        let mut colors: Vec<[f32; 4]> = Vec::with_capacity(num_lines * 2);
 
-        for (idx, line) in lines.lines.iter().enumerate() {
-            let i = idx * 2;
-            points[i] = line.start.into();
-            points[i + 1] = line.end.into();
-            colors[i] = line.start_color.as_rgba_f32();
-            colors[i + 1] = line.end_color.as_rgba_f32();
+        // Dashed lines take more than one segment, stop at the mesh limit
+        'lines: for line in lines.lines.iter() {
+            let start_color = Vec4::from(line.start_color.as_rgba_f32());
+            let end_color = Vec4::from(line.end_color.as_rgba_f32());
+            for (t0, t1) in line.segments() {
+                if points.len() >= MAX_POINTS {
+                    break 'lines;
+                }
+                points.push(line.start.lerp(line.end, t0).into());
+                points.push(line.start.lerp(line.end, t1).into());
+                colors.push(start_color.lerp(end_color, t0).into());
+                colors.push(start_color.lerp(end_color, t1).into());
+            }
         }
 
         if let Some(mesh) = meshes.get_mut(&mesh_handle.clone()) {
@@ -254,3 +322,90 @@ impl Material for LinesMaterial {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(length: f32, style: LineStyle) -> Line {
+        Line::new(Vec3::ZERO, Vec3::X * length, Color::WHITE, Color::WHITE).with_style(style)
+    }
+
+    #[test]
+    fn dashed_segment_count() {
+        // Dashes start every 2 units over 10 units
+        let segments = line(
+            10.0,
+            LineStyle::Dashed {
+                dash: 1.0,
+                gap: 1.0,
+            },
+        )
+        .segments();
+        assert_eq!(segments.len(), 5);
+        assert_eq!(segments[0], (0.0, 0.1));
+        assert_eq!(segments[4], (0.8, 0.9));
+
+        // Last dash is cut at the end of the line
+        let segments = line(
+            10.0,
+            LineStyle::Dashed {
+                dash: 3.0,
+                gap: 1.0,
+            },
+        )
+        .segments();
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[2], (0.8, 1.0));
+
+        // A dot every unit
+        let segments = line(9.5, LineStyle::Dotted { spacing: 1.0 }).segments();
+        assert_eq!(segments.len(), 10);
+    }
+
+    #[test]
+    fn solid_line_is_one_segment() {
+        assert_eq!(line(10.0, LineStyle::Solid).segments(), vec![(0.0, 1.0)]);
+    }
+
+    #[test]
+    fn zero_gap_is_solid() {
+        let segments = line(
+            10.0,
+            LineStyle::Dashed {
+                dash: 1.0,
+                gap: 0.0,
+            },
+        )
+        .segments();
+        assert_eq!(segments, vec![(0.0, 1.0)]);
+    }
+
+    #[test]
+    fn dash_longer_than_line_is_cut() {
+        let segments = line(
+            2.0,
+            LineStyle::Dashed {
+                dash: 5.0,
+                gap: 1.0,
+            },
+        )
+        .segments();
+        assert_eq!(segments, vec![(0.0, 1.0)]);
+    }
+
+    #[test]
+    fn zero_length_line_has_no_nan() {
+        for style in [
+            LineStyle::Solid,
+            LineStyle::Dashed {
+                dash: 1.0,
+                gap: 1.0,
+            },
+            LineStyle::Dotted { spacing: 1.0 },
+        ] {
+            let segments = line(0.0, style).segments();
+            assert_eq!(segments, vec![(0.0, 1.0)]);
+        }
+    }
+}
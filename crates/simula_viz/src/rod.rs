@@ -1,8 +1,13 @@
-use bevy::math::{Vec2, Vec3};
 use bevy::render::{
     mesh::{Indices, Mesh},
     render_resource::PrimitiveTopology,
 };
+use bevy::{
+    log::warn,
+    math::{Vec2, Vec3, Vec4},
+    prelude::Color,
+    utils::HashSet,
+};
 use simula_core::{
     ease::{Ease, EaseFunction},
     map_range::lerp,
@@ -13,10 +18,19 @@ fn interpolate(ease_func: EaseFunction, a: f32, b: f32, t: f32) -> f32 {
     lerp(t, (a, b))
 }
 
+/// A radius control point of a rod profile.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RodRadius {
+    /// Position along the cylinder, from south (0) to north (1).
+    pub t: f32,
+    /// Radius on the xz plane.
+    pub radius: f32,
+}
+
 /// A cylinder with variable hemispheres at the top and bottom
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct Rod {
-    /// Interpolation function to go from north to south
+    /// Interpolation function to go from north to south, or between profile points
     pub ease_func: EaseFunction,
     /// North radius on the xz plane.
     pub north_radius: f32,
@@ -30,8 +44,19 @@ pub struct Rod {
     pub latitudes: usize,
     /// Number of longitudes, or meridians, distributed by azimuth.
     pub longitudes: usize,
+    /// Number of longitudes on the hemispheres, when they need a different resolution than the
+    /// cylinder. Hemispheres are stitched to the equators without gaps.
+    pub cap_longitudes: Option<usize>,
     /// Manner in which UV coordinates are distributed vertically.
     pub uv_profile: RodUvProfile,
+    /// Manner in which UV coordinates meet at the seam, where the last meridian wraps to the first.
+    pub uv_seam: RodUvSeam,
+    /// Radius control points sorted by t, overriding north and south radii when not empty.
+    pub profile: Vec<RodRadius>,
+    /// Vertex color at the north summit.
+    pub north_color: Color,
+    /// Vertex color at the south summit, eased to the north color along the y axis.
+    pub south_color: Color,
 }
 
 impl Default for Rod {
@@ -44,11 +69,67 @@ impl Default for Rod {
             depth: 1.0,
             latitudes: 16,
             longitudes: 32,
+            cap_longitudes: None,
             uv_profile: RodUvProfile::Uniform,
+            uv_seam: RodUvSeam::Wrap,
+            profile: vec![],
+            north_color: Color::WHITE,
+            south_color: Color::WHITE,
         }
     }
 }
 
+impl Rod {
+    /// Check that profile control points are sorted by t.
+    pub fn validate_profile(&self) -> Result<(), String> {
+        match self.profile.windows(2).find(|pair| pair[0].t > pair[1].t) {
+            Some(pair) => Err(format!(
+                "Rod profile points are not sorted: t {} is before t {}",
+                pair[0].t, pair[1].t
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Radius at t, from south (0) to north (1). Without a profile, eases from the south to the
+    /// north radius, otherwise eases between the profile points around t, holding the end
+    /// radii beyond the first and last points.
+    pub fn radius_at(&self, t: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.profile.first(), self.profile.last()) else {
+            return interpolate(self.ease_func, self.south_radius, self.north_radius, t);
+        };
+        if t <= first.t {
+            return first.radius;
+        }
+        if t >= last.t {
+            return last.radius;
+        }
+        let next = self.profile.partition_point(|point| point.t <= t);
+        let (a, b) = (self.profile[next - 1], self.profile[next]);
+        let span = b.t - a.t;
+        if span <= f32::EPSILON {
+            return b.radius;
+        }
+        interpolate(self.ease_func, a.radius, b.radius, (t - a.t) / span)
+    }
+
+    /// Number of longitudes on the hemispheres, defaulting to the cylinder longitudes.
+    pub fn cap_longitudes(&self) -> usize {
+        self.cap_longitudes.unwrap_or(self.longitudes)
+    }
+
+    /// Longitudes of every vertex row between the polar caps, from north to south. Each row
+    /// has one more vertex to duplicate the seam.
+    pub fn row_longitudes(&self) -> Vec<usize> {
+        let half_latsn1 = self.latitudes / 2 - 1;
+        let cap_longitudes = self.cap_longitudes();
+        let mut rows = vec![cap_longitudes; half_latsn1];
+        rows.extend(std::iter::repeat(self.longitudes).take(self.rings + 2));
+        rows.extend(std::iter::repeat(cap_longitudes).take(half_latsn1));
+        rows
+    }
+}
+
 impl From<Rod> for Mesh {
     fn from(value: Rod) -> Self {
         let rod_mesh = RodMesh::from(value);
@@ -58,6 +139,10 @@ impl From<Rod> for Mesh {
 
 #[derive(Debug, Clone, Copy)]
 /// Manner in which UV coordinates are distributed vertically.
+///
+/// `Uniform` is the least seam-prone: texel density stays closest between the hemispheres and
+/// the cylinder, so the equators don't show as a stretch line. None of the profiles affect the
+/// meridian seam, which is controlled by [`RodUvSeam`].
 pub enum RodUvProfile {
     /// UV space is distributed by how much of the capsule consists of the hemispheres.
     Aspect,
@@ -74,6 +159,38 @@ impl Default for RodUvProfile {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Manner in which UV coordinates meet at the seam, where the last meridian wraps to the first.
+/// Seam vertices are duplicated so they can carry different UVs, positions and normals are shared.
+pub enum RodUvSeam {
+    /// Texture wraps once around the rod, from 1 at the first meridian to 0 at the last.
+    Wrap,
+    /// Texture wraps once around the rod, shifted horizontally by the given amount, moving
+    /// which texture column lands on the seam. Relies on repeat sampling.
+    Offset(f32),
+    /// Texture is mirrored over each half of the rod, so both sides of the seam sample the same
+    /// texels. Hides the seam of horizontally symmetric textures.
+    Mirror,
+}
+
+impl Default for RodUvSeam {
+    fn default() -> Self {
+        RodUvSeam::Wrap
+    }
+}
+
+impl RodUvSeam {
+    /// Horizontal texture coordinate for a wrapped coordinate s, from 1 at the first meridian to
+    /// 0 at the last.
+    pub fn apply(self, s: f32) -> f32 {
+        match self {
+            RodUvSeam::Wrap => s,
+            RodUvSeam::Offset(offset) => s + offset,
+            RodUvSeam::Mirror => 1.0 - (2.0 * s - 1.0).abs(),
+        }
+    }
+}
+
 pub struct RodMesh {
     pub rod: Rod,
     mesh: Mesh,
@@ -86,18 +203,26 @@ pub struct RodMesh {
 
 impl From<Rod> for RodMesh {
     #[allow(clippy::needless_range_loop)]
-    fn from(rod: Rod) -> Self {
+    fn from(mut rod: Rod) -> Self {
         // code adapted from https://behreajj.medium.com/making-a-capsule-mesh-via-script-in-five-3d-environments-c2214abf02db
 
+        if let Err(err) = rod.validate_profile() {
+            warn!("{}, sorting them", err);
+            rod.profile.sort_by(|a, b| a.t.total_cmp(&b.t));
+        }
+
+        // hemispheres follow the profile ends
+        let north_radius = rod.radius_at(1.0);
+        let south_radius = rod.radius_at(0.0);
+        let cap_lons = rod.cap_longitudes();
         let Rod {
-            ease_func,
-            north_radius,
-            south_radius,
             rings,
             depth,
             latitudes,
             longitudes,
             uv_profile,
+            uv_seam,
+            ..
         } = rod;
 
         let radius = 1.0;
@@ -107,13 +232,14 @@ impl From<Rod> for RodMesh {
         let half_latsn2 = half_lats - 2;
         let ringsp1 = rings + 1;
         let lonsp1 = longitudes + 1;
+        let cap_lonsp1 = cap_lons + 1;
         let half_depth = depth * 0.5;
         let north_summit = half_depth + north_radius;
         let south_summit = half_depth + south_radius;
 
         // Vertex index offsets.
-        let vert_offset_north_hemi = longitudes;
-        let vert_offset_north_equator = vert_offset_north_hemi + lonsp1 * half_latsn1;
+        let vert_offset_north_hemi = cap_lons;
+        let vert_offset_north_equator = vert_offset_north_hemi + cap_lonsp1 * half_latsn1;
         let vert_offset_cylinder = vert_offset_north_equator + lonsp1;
         let vert_offset_south_equator = if calc_middle {
             vert_offset_cylinder + lonsp1 * rings
@@ -121,11 +247,11 @@ impl From<Rod> for RodMesh {
             vert_offset_cylinder
         };
         let vert_offset_south_hemi = vert_offset_south_equator + lonsp1;
-        let vert_offset_south_polar = vert_offset_south_hemi + lonsp1 * half_latsn2;
-        let vert_offset_south_cap = vert_offset_south_polar + lonsp1;
+        let vert_offset_south_polar = vert_offset_south_hemi + cap_lonsp1 * half_latsn2;
+        let vert_offset_south_cap = vert_offset_south_polar + cap_lonsp1;
 
         // Initialize arrays.
-        let vert_len = vert_offset_south_cap + longitudes;
+        let vert_len = vert_offset_south_cap + cap_lons;
 
         let mut vs: Vec<Vec3> = vec![Vec3::ZERO; vert_len];
         let mut vts: Vec<Vec2> = vec![Vec2::ZERO; vert_len];
@@ -134,6 +260,8 @@ impl From<Rod> for RodMesh {
         let to_theta = 2.0 * std::f32::consts::PI / longitudes as f32;
         let to_phi = std::f32::consts::PI / latitudes as f32;
         let to_tex_horizontal = 1.0 / longitudes as f32;
+        let to_cap_theta = 2.0 * std::f32::consts::PI / cap_lons as f32;
+        let to_cap_tex_horizontal = 1.0 / cap_lons as f32;
         let to_tex_vertical = 1.0 / half_lats as f32;
 
         let vt_aspect_ratio = match uv_profile {
@@ -147,17 +275,24 @@ impl From<Rod> for RodMesh {
         let mut theta_cartesian: Vec<Vec2> = vec![Vec2::ZERO; longitudes];
         let mut rho_theta_cartesian: Vec<Vec2> = vec![Vec2::ZERO; longitudes];
         let mut s_texture_cache: Vec<f32> = vec![0.0; lonsp1];
+        let mut cap_theta_cartesian: Vec<Vec2> = vec![Vec2::ZERO; cap_lons];
+        let mut cap_s_texture_cache: Vec<f32> = vec![0.0; cap_lonsp1];
 
         for j in 0..longitudes {
-            let jf = j as f32;
-            let s_texture_polar = 1.0 - ((jf + 0.5) * to_tex_horizontal);
-            let theta = jf * to_theta;
-
+            let theta = j as f32 * to_theta;
             let cos_theta = theta.cos();
             let sin_theta = theta.sin();
 
             theta_cartesian[j] = Vec2::new(cos_theta, sin_theta);
             rho_theta_cartesian[j] = Vec2::new(radius * cos_theta, radius * sin_theta);
+        }
+
+        for j in 0..cap_lons {
+            let jf = j as f32;
+            let s_texture_polar = uv_seam.apply(1.0 - ((jf + 0.5) * to_cap_tex_horizontal));
+            let theta = jf * to_cap_theta;
+            cap_theta_cartesian[j] = Vec2::new(theta.cos(), theta.sin());
+            cap_s_texture_cache[j] = uv_seam.apply(1.0 - jf * to_cap_tex_horizontal);
 
             // North.
             vs[j] = Vec3::new(0.0, north_summit, 0.0);
@@ -170,10 +305,12 @@ impl From<Rod> for RodMesh {
             vts[idx] = Vec2::new(s_texture_polar, 0.0);
             vns[idx] = Vec3::new(0.0, -1.0, 0.0);
         }
+        cap_s_texture_cache[cap_lons] =
+            uv_seam.apply(1.0 - cap_lons as f32 * to_cap_tex_horizontal);
 
         // Equatorial vertices.
         for j in 0..lonsp1 {
-            let s_texture = 1.0 - j as f32 * to_tex_horizontal;
+            let s_texture = uv_seam.apply(1.0 - j as f32 * to_tex_horizontal);
             s_texture_cache[j] = s_texture;
 
             // Wrap to first element upon reaching last.
@@ -220,28 +357,18 @@ impl From<Rod> for RodMesh {
             let t_tex_north = cmpl_tex_fac + vt_aspect_north * t_tex_fac;
             let t_tex_south = cmpl_tex_fac * vt_aspect_south;
 
-            let i_lonsp1 = i * lonsp1;
+            let i_lonsp1 = i * cap_lonsp1;
             let vert_curr_lat_north = vert_offset_north_hemi + i_lonsp1;
             let vert_curr_lat_south = vert_offset_south_hemi + i_lonsp1;
 
-            for j in 0..lonsp1 {
-                let j_mod = j % longitudes;
+            for j in 0..cap_lonsp1 {
+                let j_mod = j % cap_lons;
 
-                let s_texture = s_texture_cache[j];
-                let tc = theta_cartesian[j_mod];
+                let s_texture = cap_s_texture_cache[j];
+                let tc = cap_theta_cartesian[j_mod];
 
-                let north_radius_weight = interpolate(
-                    ease_func,
-                    south_radius,
-                    north_radius,
-                    (z_offset_north + half_depth) / depth,
-                );
-                let south_radius_weight = interpolate(
-                    ease_func,
-                    south_radius,
-                    north_radius,
-                    (z_offset_south + half_depth) / depth,
-                );
+                let north_radius_weight = rod.radius_at((z_offset_north + half_depth) / depth);
+                let south_radius_weight = rod.radius_at((z_offset_south + half_depth) / depth);
 
                 // North hemisphere.
                 let idxn = vert_curr_lat_north + j;
@@ -278,12 +405,7 @@ impl From<Rod> for RodMesh {
                 let t_texture = cmpl_fac * vt_aspect_north + fac * vt_aspect_south;
                 let z = half_depth - depth * fac;
 
-                let radius_weight = interpolate(
-                    ease_func,
-                    south_radius,
-                    north_radius,
-                    (z + half_depth) / depth,
-                );
+                let radius_weight = rod.radius_at((z + half_depth) / depth);
 
                 for j in 0..lonsp1 {
                     let j_mod = j % longitudes;
@@ -304,23 +426,26 @@ impl From<Rod> for RodMesh {
 
         // Stride is 3 for polar triangles;
         // stride is 6 for two triangles forming a quad.
-        let lons3 = longitudes * 3;
+        // The hemisphere row next to the equator is stitched with one triangle per longitude
+        // of both rows, which is a quad per longitude when they match.
         let lons6 = longitudes * 6;
-        let hemi_lons = half_latsn1 * lons6;
+        let cap_lons3 = cap_lons * 3;
+        let cap_lons6 = cap_lons * 6;
+        let hemi_lons = (half_latsn1 - 1) * cap_lons6 + cap_lons3 + longitudes * 3;
 
-        let tri_offset_north_hemi = lons3;
+        let tri_offset_north_hemi = cap_lons3;
         let tri_offset_cylinder = tri_offset_north_hemi + hemi_lons;
         let tri_offset_south_hemi = tri_offset_cylinder + ringsp1 * lons6;
         let tri_offset_south_cap = tri_offset_south_hemi + hemi_lons;
 
-        let fs_len = tri_offset_south_cap + lons3;
+        let fs_len = tri_offset_south_cap + cap_lons3;
         let mut tris: Vec<u32> = vec![0; fs_len];
 
         // Polar caps.
         let mut i = 0;
         let mut k = 0;
         let mut m = tri_offset_south_cap;
-        while i < longitudes {
+        while i < cap_lons {
             // North.
             tris[k] = i as u32;
             tris[k + 1] = (vert_offset_north_hemi + i) as u32;
@@ -338,54 +463,29 @@ impl From<Rod> for RodMesh {
 
         // Hemispheres.
 
+        // Rows from the pole to the equator, and from the equator to the pole.
+        let north_row = |i: usize| {
+            if i < half_latsn1 {
+                (vert_offset_north_hemi + i * cap_lonsp1, cap_lons)
+            } else {
+                (vert_offset_north_equator, longitudes)
+            }
+        };
+        let south_row = |i: usize| {
+            if i == 0 {
+                (vert_offset_south_equator, longitudes)
+            } else {
+                (vert_offset_south_hemi + (i - 1) * cap_lonsp1, cap_lons)
+            }
+        };
+
         let mut i = 0;
         let mut k = tri_offset_north_hemi;
         let mut m = tri_offset_south_hemi;
 
         while i < half_latsn1 {
-            let i_lonsp1 = i * lonsp1;
-
-            let vert_curr_lat_north = vert_offset_north_hemi + i_lonsp1;
-            let vert_next_lat_north = vert_curr_lat_north + lonsp1;
-
-            let vert_curr_lat_south = vert_offset_south_equator + i_lonsp1;
-            let vert_next_lat_south = vert_curr_lat_south + lonsp1;
-
-            let mut j = 0;
-            while j < longitudes {
-                // North.
-                let north00 = vert_curr_lat_north + j;
-                let north01 = vert_next_lat_north + j;
-                let north11 = vert_next_lat_north + j + 1;
-                let north10 = vert_curr_lat_north + j + 1;
-
-                tris[k] = north00 as u32;
-                tris[k + 1] = north11 as u32;
-                tris[k + 2] = north10 as u32;
-
-                tris[k + 3] = north00 as u32;
-                tris[k + 4] = north01 as u32;
-                tris[k + 5] = north11 as u32;
-
-                // South.
-                let south00 = vert_curr_lat_south + j;
-                let south01 = vert_next_lat_south + j;
-                let south11 = vert_next_lat_south + j + 1;
-                let south10 = vert_curr_lat_south + j + 1;
-
-                tris[m] = south00 as u32;
-                tris[m + 1] = south11 as u32;
-                tris[m + 2] = south10 as u32;
-
-                tris[m + 3] = south00 as u32;
-                tris[m + 4] = south01 as u32;
-                tris[m + 5] = south11 as u32;
-
-                j += 1;
-                k += 6;
-                m += 6;
-            }
-
+            k = strip(&mut tris, k, north_row(i), north_row(i + 1));
+            m = strip(&mut tris, m, south_row(i), south_row(i + 1));
             i += 1;
         }
 
@@ -419,6 +519,22 @@ impl From<Rod> for RodMesh {
             i += 1;
         }
 
+        // Vertex colors, eased from south to north summits.
+        let north_color = Vec4::from(rod.north_color.as_linear_rgba_f32());
+        let south_color = Vec4::from(rod.south_color.as_linear_rgba_f32());
+        let height = north_summit + south_summit;
+        let vxs: Vec<[f32; 4]> = vs
+            .iter()
+            .map(|v| {
+                let t = if height > f32::EPSILON {
+                    ((v.y + south_summit) / height).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                south_color.lerp(north_color, t.calc(rod.ease_func)).into()
+            })
+            .collect();
+
         let vs: Vec<[f32; 3]> = vs.into_iter().map(Into::into).collect();
         let vns: Vec<[f32; 3]> = vns.into_iter().map(Into::into).collect();
         let vts: Vec<[f32; 2]> = vts.into_iter().map(Into::into).collect();
@@ -430,14 +546,12 @@ impl From<Rod> for RodMesh {
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vs.clone());
         mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vns.clone());
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vts.clone());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vxs.clone());
         mesh.set_indices(Some(Indices::U32(tris.clone())));
         mesh.generate_tangents()
             .expect("Failed to generate tangents");
 
-        let mut vxs = vec![];
-        vxs.resize(vs.len(), [0f32; 4]);
-
-        RodMesh {
+        let rod_mesh = RodMesh {
             rod,
             mesh,
             vs,
@@ -445,6 +559,339 @@ impl From<Rod> for RodMesh {
             vts,
             vxs,
             tris,
+        };
+        debug_assert_eq!(rod_mesh.validate_seam(), Ok(()));
+        debug_assert_eq!(rod_mesh.validate_geometry(), Ok(()));
+        rod_mesh
+    }
+}
+
+// Triangulate the strip between two rows of vertices, each with a duplicated seam vertex,
+// returning the index after the last triangle written
+fn strip(tris: &mut [u32], mut k: usize, curr: (usize, usize), next: (usize, usize)) -> usize {
+    let (curr, curr_lons) = curr;
+    let (next, next_lons) = next;
+
+    // Same longitudes, a quad per longitude
+    if curr_lons == next_lons {
+        for j in 0..curr_lons {
+            let v00 = curr + j;
+            let v01 = next + j;
+            let v11 = next + j + 1;
+            let v10 = curr + j + 1;
+
+            tris[k] = v00 as u32;
+            tris[k + 1] = v11 as u32;
+            tris[k + 2] = v10 as u32;
+
+            tris[k + 3] = v00 as u32;
+            tris[k + 4] = v01 as u32;
+            tris[k + 5] = v11 as u32;
+
+            k += 6;
+        }
+        return k;
+    }
+
+    // Different longitudes, advance on the row whose next vertex comes first by azimuth
+    let (mut i, mut j) = (0, 0);
+    while i < curr_lons || j < next_lons {
+        let advance_next =
+            j < next_lons && (i == curr_lons || (j + 1) * curr_lons <= (i + 1) * next_lons);
+        if advance_next {
+            tris[k] = (curr + i) as u32;
+            tris[k + 1] = (next + j) as u32;
+            tris[k + 2] = (next + j + 1) as u32;
+            j += 1;
+        } else {
+            tris[k] = (curr + i) as u32;
+            tris[k + 1] = (next + j) as u32;
+            tris[k + 2] = (curr + i + 1) as u32;
+            i += 1;
         }
+        k += 3;
+    }
+    k
+}
+
+impl RodMesh {
+    /// Unique edges of the triangles, as pairs of vertex indices in order of first use.
+    pub fn edges(&self) -> Vec<[u32; 2]> {
+        let mut seen = HashSet::new();
+        self.tris
+            .chunks_exact(3)
+            .flat_map(|tri| [[tri[0], tri[1]], [tri[1], tri[2]], [tri[2], tri[0]]])
+            .filter(|&[a, b]| seen.insert((a.min(b), a.max(b))))
+            .collect()
+    }
+
+    /// Line list mesh of the triangle edges, sharing the rod vertices, to debug its geometry.
+    pub fn wireframe(&self) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.vs.clone());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, self.vxs.clone());
+        mesh.set_indices(Some(Indices::U32(
+            self.edges().into_iter().flatten().collect(),
+        )));
+        mesh
+    }
+
+    /// Check that the duplicated vertices on the seam share positions and normals, so the seam
+    /// is continuous and only UVs differ. Every row between the polar caps starts and ends on
+    /// the seam.
+    pub fn validate_seam(&self) -> Result<(), String> {
+        let cap_longitudes = self.rod.cap_longitudes();
+        let mut row = cap_longitudes;
+        for longitudes in self.rod.row_longitudes() {
+            let (first, last) = (row, row + longitudes);
+            row = last + 1;
+            let same = |a: [f32; 3], b: [f32; 3]| Vec3::from(a).abs_diff_eq(Vec3::from(b), 1e-5);
+            if !same(self.vs[first], self.vs[last]) {
+                return Err(format!(
+                    "Rod seam vertices {} and {} differ in position",
+                    first, last
+                ));
+            }
+            if !same(self.vns[first], self.vns[last]) {
+                return Err(format!(
+                    "Rod seam vertices {} and {} differ in normal",
+                    first, last
+                ));
+            }
+        }
+        if row + cap_longitudes != self.vs.len() {
+            return Err(format!(
+                "Rod has {} vertices, expected {} from its rows",
+                self.vs.len(),
+                row + cap_longitudes
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that all vertex positions are finite and triangles index existing vertices.
+    pub fn validate_geometry(&self) -> Result<(), String> {
+        if let Some(index) = self.vs.iter().position(|v| !Vec3::from(*v).is_finite()) {
+            return Err(format!("Rod vertex {} is not finite", index));
+        }
+        if let Some(index) = self.tris.iter().find(|&&i| i as usize >= self.vs.len()) {
+            return Err(format!("Rod triangle index {} is out of range", index));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{
+        prelude::default,
+        render::mesh::{MeshVertexAttributeId, VertexAttributeValues},
+    };
+
+    fn float3(mesh: &Mesh, attribute: impl Into<MeshVertexAttributeId>) -> Vec<Vec3> {
+        match mesh.attribute(attribute) {
+            Some(VertexAttributeValues::Float32x3(values)) => {
+                values.iter().map(|v| Vec3::from(*v)).collect()
+            }
+            _ => panic!("expected a float3 attribute"),
+        }
+    }
+
+    // Vertex and index counts from the rod parameters: polar caps, hemisphere rows and
+    // cylinder rows with a duplicated seam vertex, and the triangles between them
+    fn expected_counts(rod: &Rod) -> (usize, usize) {
+        let cap_lons = rod.cap_longitudes();
+        let half_latsn1 = rod.latitudes / 2 - 1;
+        let vertices = 2 * cap_lons
+            + 2 * half_latsn1 * (cap_lons + 1)
+            + (rod.rings + 2) * (rod.longitudes + 1);
+        let hemisphere = (half_latsn1 - 1) * cap_lons * 6 + (cap_lons + rod.longitudes) * 3;
+        let indices = 2 * cap_lons * 3 + 2 * hemisphere + (rod.rings + 1) * rod.longitudes * 6;
+        (vertices, indices)
+    }
+
+    fn check_mesh(rod: Rod) {
+        let (vertices, indices) = expected_counts(&rod);
+        let mesh = Mesh::from(rod);
+        let positions = float3(&mesh, Mesh::ATTRIBUTE_POSITION);
+        let normals = float3(&mesh, Mesh::ATTRIBUTE_NORMAL);
+        assert_eq!(positions.len(), vertices);
+        assert_eq!(normals.len(), vertices);
+        assert_eq!(mesh.indices().unwrap().len(), indices);
+        assert!(positions.iter().all(|v| v.is_finite()));
+        assert!(normals.iter().all(|n| n.is_finite()));
+    }
+
+    #[test]
+    fn tapered_rod_mesh() {
+        check_mesh(Rod {
+            north_radius: 0.2,
+            south_radius: 1.0,
+            ..default()
+        });
+        check_mesh(Rod {
+            profile: vec![
+                RodRadius {
+                    t: 0.0,
+                    radius: 1.0,
+                },
+                RodRadius {
+                    t: 0.5,
+                    radius: 0.3,
+                },
+                RodRadius {
+                    t: 1.0,
+                    radius: 0.6,
+                },
+            ],
+            cap_longitudes: Some(12),
+            ..default()
+        });
+    }
+
+    #[test]
+    fn degenerate_rod_mesh() {
+        // Coincident control points
+        check_mesh(Rod {
+            profile: vec![
+                RodRadius {
+                    t: 0.0,
+                    radius: 0.5,
+                },
+                RodRadius {
+                    t: 0.5,
+                    radius: 0.2,
+                },
+                RodRadius {
+                    t: 0.5,
+                    radius: 0.8,
+                },
+                RodRadius {
+                    t: 1.0,
+                    radius: 0.5,
+                },
+            ],
+            ..default()
+        });
+        // Pinched to a point at the south end, without a cylinder
+        check_mesh(Rod {
+            profile: vec![
+                RodRadius {
+                    t: 0.0,
+                    radius: 0.0,
+                },
+                RodRadius {
+                    t: 1.0,
+                    radius: 0.5,
+                },
+            ],
+            rings: 0,
+            ..default()
+        });
+        // Fewest latitudes, a single point profile
+        check_mesh(Rod {
+            latitudes: 4,
+            longitudes: 3,
+            cap_longitudes: Some(5),
+            profile: vec![RodRadius {
+                t: 0.5,
+                radius: 0.4,
+            }],
+            ..default()
+        });
+    }
+
+    #[test]
+    fn wireframe_edges_are_unique() {
+        let rod_mesh = RodMesh::from(Rod {
+            latitudes: 4,
+            longitudes: 4,
+            rings: 0,
+            ..default()
+        });
+        // 4 rows of 4 seam-duplicated segments, 3 strips of 5 meridians and 4 diagonals,
+        // and 2 edges from each of the 8 pole vertices
+        let edges = rod_mesh.edges();
+        assert_eq!(edges.len(), 16 + 15 + 12 + 16);
+
+        let mut seen = HashSet::new();
+        assert!(edges
+            .iter()
+            .all(|&[a, b]| a != b && seen.insert((a.min(b), a.max(b)))));
+
+        let wireframe = rod_mesh.wireframe();
+        assert_eq!(wireframe.indices().unwrap().len(), edges.len() * 2);
+    }
+
+    #[test]
+    fn cap_vertices_carry_summit_colors() {
+        let rod = Rod {
+            north_color: Color::RED,
+            south_color: Color::BLUE,
+            ..default()
+        };
+        let cap_lons = rod.cap_longitudes();
+        let mesh = Mesh::from(rod);
+        let colors: Vec<Vec4> = match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+            Some(VertexAttributeValues::Float32x4(values)) => {
+                values.iter().map(|v| Vec4::from(*v)).collect()
+            }
+            _ => panic!("expected a float4 color attribute"),
+        };
+        let north = Vec4::from(Color::RED.as_linear_rgba_f32());
+        let south = Vec4::from(Color::BLUE.as_linear_rgba_f32());
+        let (top, rest) = colors.split_at(cap_lons);
+        let bottom = &rest[rest.len() - cap_lons..];
+        assert!(top.iter().all(|color| color.abs_diff_eq(north, 1e-5)));
+        assert!(bottom.iter().all(|color| color.abs_diff_eq(south, 1e-5)));
+    }
+
+    // U at the first and last vertex of every row between the polar caps, which sit on the seam
+    fn seam_us(rod: Rod) -> Vec<(f32, f32)> {
+        let rows = rod.row_longitudes();
+        let mut row = rod.cap_longitudes();
+        let mesh = Mesh::from(rod);
+        let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(values)) => values.clone(),
+            _ => panic!("expected a float2 uv attribute"),
+        };
+        rows.into_iter()
+            .map(|longitudes| {
+                let seam = (uvs[row][0], uvs[row + longitudes][0]);
+                row += longitudes + 1;
+                seam
+            })
+            .collect()
+    }
+
+    fn assert_seam(uv_seam: RodUvSeam, first: f32, last: f32) {
+        let seams = seam_us(Rod {
+            uv_seam,
+            cap_longitudes: Some(12),
+            ..default()
+        });
+        assert!(!seams.is_empty());
+        for (u_first, u_last) in seams {
+            assert!(
+                (u_first - first).abs() < 1e-5,
+                "{:?} first u {}",
+                uv_seam,
+                u_first
+            );
+            assert!(
+                (u_last - last).abs() < 1e-5,
+                "{:?} last u {}",
+                uv_seam,
+                u_last
+            );
+        }
+    }
+
+    #[test]
+    fn seam_vertices_have_mode_uvs() {
+        assert_seam(RodUvSeam::Wrap, 1.0, 0.0);
+        assert_seam(RodUvSeam::Offset(0.25), 1.25, 0.25);
+        assert_seam(RodUvSeam::Mirror, 0.0, 0.0);
     }
 }
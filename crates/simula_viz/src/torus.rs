@@ -0,0 +1,119 @@
+use bevy::math::Vec3;
+use bevy::render::{
+    mesh::{Indices, Mesh},
+    render_resource::PrimitiveTopology,
+};
+
+/// A ring shaped tube lying on the xz plane, around the y axis
+#[derive(Debug, Clone, Copy)]
+pub struct Torus {
+    /// Distance from the center to the middle of the tube.
+    pub major_radius: f32,
+    /// Radius of the tube.
+    pub minor_radius: f32,
+    /// Number of segments around the y axis.
+    pub major_segments: usize,
+    /// Number of segments around the tube.
+    pub minor_segments: usize,
+}
+
+impl Default for Torus {
+    fn default() -> Self {
+        Torus {
+            major_radius: 1.0,
+            minor_radius: 0.25,
+            major_segments: 32,
+            minor_segments: 16,
+        }
+    }
+}
+
+impl From<Torus> for Mesh {
+    fn from(torus: Torus) -> Self {
+        let Torus {
+            major_radius,
+            minor_radius,
+            major_segments,
+            minor_segments,
+        } = torus;
+
+        // Rows around the y axis, each with a vertex around the tube. First and last rows and
+        // columns are duplicated so UVs wrap.
+        let minor_segmentsp1 = minor_segments + 1;
+        let vert_len = (major_segments + 1) * minor_segmentsp1;
+
+        let mut vs: Vec<[f32; 3]> = Vec::with_capacity(vert_len);
+        let mut vns: Vec<[f32; 3]> = Vec::with_capacity(vert_len);
+        let mut vts: Vec<[f32; 2]> = Vec::with_capacity(vert_len);
+
+        let to_theta = 2.0 * std::f32::consts::PI / major_segments as f32;
+        let to_phi = 2.0 * std::f32::consts::PI / minor_segments as f32;
+
+        for i in 0..=major_segments {
+            let theta = i as f32 * to_theta;
+            let (sin_theta, cos_theta) = theta.sin_cos();
+            let center = Vec3::new(cos_theta, 0.0, sin_theta) * major_radius;
+
+            for j in 0..=minor_segments {
+                let phi = j as f32 * to_phi;
+                let (sin_phi, cos_phi) = phi.sin_cos();
+                let normal = Vec3::new(cos_phi * cos_theta, sin_phi, cos_phi * sin_theta);
+
+                vs.push((center + normal * minor_radius).into());
+                vns.push(normal.into());
+                vts.push([
+                    i as f32 / major_segments as f32,
+                    j as f32 / minor_segments as f32,
+                ]);
+            }
+        }
+
+        let mut tris: Vec<u32> = Vec::with_capacity(major_segments * minor_segments * 6);
+        for i in 0..major_segments {
+            for j in 0..minor_segments {
+                let v00 = (i * minor_segmentsp1 + j) as u32;
+                let v01 = v00 + 1;
+                let v10 = v00 + minor_segmentsp1 as u32;
+                let v11 = v10 + 1;
+
+                tris.extend([v00, v01, v10]);
+                tris.extend([v01, v11, v10]);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vs);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vns);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vts);
+        mesh.set_indices(Some(Indices::U32(tris)));
+        mesh.generate_tangents()
+            .expect("Failed to generate tangents");
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::mesh::VertexAttributeValues;
+
+    #[test]
+    fn torus_vertices_and_normals() {
+        let torus = Torus {
+            major_segments: 12,
+            minor_segments: 7,
+            ..Default::default()
+        };
+        let mesh = Mesh::from(torus);
+        let Some(VertexAttributeValues::Float32x3(vns)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("expected float3 normals");
+        };
+        assert_eq!(mesh.count_vertices(), 13 * 8);
+        assert_eq!(vns.len(), 13 * 8);
+        assert!(vns
+            .iter()
+            .all(|vn| (Vec3::from(*vn).length() - 1.0).abs() < 1e-5));
+        assert_eq!(mesh.indices().unwrap().len(), 12 * 7 * 6);
+    }
+}
@@ -50,6 +50,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 divisions: 10,
                 start_color: grid_color,
                 end_color: grid_color,
+                ..default()
             },
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
             ..Default::default()
@@ -152,6 +152,7 @@ fn setup(
                 divisions: 10,
                 start_color: Color::BLUE,
                 end_color: Color::RED,
+                ..default()
             },
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
             ..default()
@@ -164,6 +165,7 @@ fn setup(
             axes: Axes {
                 size: 1.,
                 inner_offset: 5.,
+                axis_labels: true,
             },
             transform: Transform::from_xyz(0.0, 0.01, 0.0),
             ..default()
@@ -176,6 +178,7 @@ fn setup(
             axes: Axes {
                 size: 3.,
                 inner_offset: 0.,
+                ..default()
             },
             transform: Transform::from_xyz(7.0, 0.0, 0.0),
             ..default()
@@ -193,6 +196,7 @@ fn setup(
             axes: Axes {
                 size: 3.,
                 inner_offset: 0.,
+                ..default()
             },
             transform: Transform::from_xyz(0.0, 7.0, 0.0),
             ..default()
@@ -210,6 +214,7 @@ fn setup(
             axes: Axes {
                 size: 3.,
                 inner_offset: 0.,
+                ..default()
             },
             transform: Transform::from_xyz(0.0, 0.0, -7.0),
             ..default()
@@ -286,6 +291,7 @@ fn setup(
                         axes: Axes {
                             size: 1.,
                             inner_offset: 1.,
+                            ..default()
                         },
                         transform: Transform::from_xyz(0.0, 1.0, 0.0),
                         ..default()
@@ -804,6 +810,9 @@ fn follow_ui(
     follow_uis: Query<(Entity, &FollowUI, &FollowUIVisibility), With<SandboxPanel>>,
 ) {
     for (entity, follow_ui, visibility) in follow_uis.iter() {
+        if !visibility.visible() {
+            continue;
+        }
         let ui_pos = visibility.screen_pos;
 
         let my_frame = egui::containers::Frame {
@@ -35,6 +35,31 @@ pub enum DerivedBehavior {
     Delay(Delay),
     Guard(Guard),
     Timeout(Timeout),
+    Spawn(Spawn),
+    Despawn(Despawn),
+    StampNow(StampNow),
+    CooldownReady(CooldownReady),
+    Retry(Retry),
+    RetryUntilSuccess(RetryUntilSuccess),
+    Chance(Chance),
+    DistanceCheck(DistanceCheck),
+    Compute(Compute),
+    Overlap(Overlap),
+    Yield(Yield),
+    AddComponent(AddComponent),
+    RemoveComponent(RemoveComponent),
+    Wander(Wander),
+    Tween(Tween),
+    InputPressed(InputPressed),
+    FindNearest(FindNearest),
+    ForEach(ForEach),
+    Cooldown(Cooldown),
+    RandomSelector(RandomSelector),
+    BlackboardGate(BlackboardGate),
+    Parallel(Parallel),
+    Switch(Switch),
+    WaitForSignal(WaitForSignal),
+    ScriptCondition(ScriptCondition),
     // Substrees are typed, can load same or different types of subtrees
     Subtree(Subtree<DerivedBehavior>),
     SubImpl(Subtree<ImplementedBehavior>),
@@ -57,25 +82,6 @@ impl BehaviorNodeInspectable<DerivedBehavior> for DerivedBehaviorAttributes {
 }
 
 impl BehaviorInspectable for DerivedBehavior {
-    fn color(&self) -> Color {
-        match self {
-            DerivedBehavior::Debug(_) => Color::hex("#235").unwrap(),
-            DerivedBehavior::Selector(_) => Color::hex("#522").unwrap(),
-            DerivedBehavior::Sequencer(_) => Color::hex("#252").unwrap(),
-            DerivedBehavior::All(_) => Color::hex("#252").unwrap(),
-            DerivedBehavior::Any(_) => Color::hex("#522").unwrap(),
-            DerivedBehavior::Repeater(_) => Color::hex("#440").unwrap(),
-            DerivedBehavior::Inverter(_) => Color::hex("#440").unwrap(),
-            DerivedBehavior::Succeeder(_) => Color::hex("#440").unwrap(),
-            DerivedBehavior::Wait(_) => Color::hex("#235").unwrap(),
-            DerivedBehavior::Delay(_) => Color::hex("#440").unwrap(),
-            DerivedBehavior::Guard(_) => Color::hex("#440").unwrap(),
-            DerivedBehavior::Timeout(_) => Color::hex("#440").unwrap(),
-            DerivedBehavior::Subtree(_) => Color::hex("#530").unwrap(),
-            DerivedBehavior::SubImpl(_) => Color::hex("#530").unwrap(),
-        }
-    }
-
     #[rustfmt::skip]
     fn categories(&self) -> Vec<&'static str> {
         match self {
@@ -91,6 +97,31 @@ impl BehaviorInspectable for DerivedBehavior {
             DerivedBehavior::Delay(_) => vec![<Delay as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Guard(_) => vec![<Guard as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Timeout(_) => vec![<Timeout as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Spawn(_) => vec![<Spawn as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Despawn(_) => vec![<Despawn as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::StampNow(_) => vec![<StampNow as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::CooldownReady(_) => vec![<CooldownReady as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Retry(_) => vec![<Retry as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::RetryUntilSuccess(_) => vec![<RetryUntilSuccess as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Chance(_) => vec![<Chance as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::DistanceCheck(_) => vec![<DistanceCheck as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Compute(_) => vec![<Compute as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Overlap(_) => vec![<Overlap as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Yield(_) => vec![<Yield as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::AddComponent(_) => vec![<AddComponent as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::RemoveComponent(_) => vec![<RemoveComponent as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Wander(_) => vec![<Wander as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Tween(_) => vec![<Tween as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::InputPressed(_) => vec![<InputPressed as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::FindNearest(_) => vec![<FindNearest as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::ForEach(_) => vec![<ForEach as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Cooldown(_) => vec![<Cooldown as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::RandomSelector(_) => vec![<RandomSelector as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::BlackboardGate(_) => vec![<BlackboardGate as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Parallel(_) => vec![<Parallel as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::Switch(_) => vec![<Switch as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::WaitForSignal(_) => vec![<WaitForSignal as BehaviorSpec>::TYPE.as_ref()],
+            DerivedBehavior::ScriptCondition(_) => vec![<ScriptCondition as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::Subtree(_) => vec![<Subtree<DerivedBehavior> as BehaviorSpec>::TYPE.as_ref()],
             DerivedBehavior::SubImpl(_) => vec![<Subtree<ImplementedBehavior> as BehaviorSpec>::TYPE.as_ref()],
         }
@@ -38,6 +38,10 @@ pub enum ImplementedBehavior {
     Delay(Delay),
     Guard(Guard),
     Timeout(Timeout),
+    Parallel(Parallel),
+    Switch(Switch),
+    RetryUntilSuccess(RetryUntilSuccess),
+    Cooldown(Cooldown),
     Subtree(Subtree<ImplementedBehavior>), // Substrees are typed, this loads same tree type
     AnotherTree(Subtree<DerivedBehavior>),
 }
@@ -59,25 +63,6 @@ impl BehaviorNodeInspectable<ImplementedBehavior> for ImplementedBehaviorAttribu
 }
 
 impl BehaviorInspectable for ImplementedBehavior {
-    fn color(&self) -> Color {
-        match self {
-            ImplementedBehavior::Debug(_) => Color::hex("#235").unwrap(),
-            ImplementedBehavior::Selector(_) => Color::hex("#522").unwrap(),
-            ImplementedBehavior::Sequencer(_) => Color::hex("#252").unwrap(),
-            ImplementedBehavior::All(_) => Color::hex("#252").unwrap(),
-            ImplementedBehavior::Any(_) => Color::hex("#522").unwrap(),
-            ImplementedBehavior::Repeater(_) => Color::hex("#440").unwrap(),
-            ImplementedBehavior::Inverter(_) => Color::hex("#440").unwrap(),
-            ImplementedBehavior::Succeeder(_) => Color::hex("#440").unwrap(),
-            ImplementedBehavior::Wait(_) => Color::hex("#235").unwrap(),
-            ImplementedBehavior::Delay(_) => Color::hex("#440").unwrap(),
-            ImplementedBehavior::Guard(_) => Color::hex("#440").unwrap(),
-            ImplementedBehavior::Timeout(_) => Color::hex("#440").unwrap(),
-            ImplementedBehavior::Subtree(_) => Color::hex("#440").unwrap(),
-            ImplementedBehavior::AnotherTree(_) => Color::hex("#440").unwrap(),
-        }
-    }
-
     #[rustfmt::skip]
     fn categories(&self) -> Vec<&'static str> {
         match self {
@@ -93,6 +78,10 @@ impl BehaviorInspectable for ImplementedBehavior {
             ImplementedBehavior::Delay(_) => vec![<Delay as BehaviorSpec>::TYPE.as_ref()],
             ImplementedBehavior::Guard(_) => vec![<Guard as BehaviorSpec>::TYPE.as_ref()],
             ImplementedBehavior::Timeout(_) => vec![<Timeout as BehaviorSpec>::TYPE.as_ref()],
+            ImplementedBehavior::Parallel(_) => vec![<Parallel as BehaviorSpec>::TYPE.as_ref()],
+            ImplementedBehavior::Switch(_) => vec![<Switch as BehaviorSpec>::TYPE.as_ref()],
+            ImplementedBehavior::RetryUntilSuccess(_) => vec![<RetryUntilSuccess as BehaviorSpec>::TYPE.as_ref()],
+            ImplementedBehavior::Cooldown(_) => vec![<Cooldown as BehaviorSpec>::TYPE.as_ref()],
             ImplementedBehavior::Subtree(_) => vec![<Subtree<ImplementedBehavior> as BehaviorSpec>::TYPE.as_ref()],
             ImplementedBehavior::AnotherTree(_) => vec![<Subtree<DerivedBehavior> as BehaviorSpec>::TYPE.as_ref()],
         }
@@ -116,6 +105,12 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(data) => BehaviorSpec::insert_with(commands, data),
             ImplementedBehavior::Guard(data) => BehaviorSpec::insert_with(commands, data),
             ImplementedBehavior::Timeout(data) => BehaviorSpec::insert_with(commands, data),
+            ImplementedBehavior::Parallel(data) => BehaviorSpec::insert_with(commands, data),
+            ImplementedBehavior::Switch(data) => BehaviorSpec::insert_with(commands, data),
+            ImplementedBehavior::RetryUntilSuccess(data) => {
+                BehaviorSpec::insert_with(commands, data)
+            }
+            ImplementedBehavior::Cooldown(data) => BehaviorSpec::insert_with(commands, data),
             ImplementedBehavior::Subtree(data) => BehaviorSpec::insert_with(commands, data),
             ImplementedBehavior::AnotherTree(data) => BehaviorSpec::insert_with(commands, data),
         }
@@ -135,6 +130,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(_) => <Delay as BehaviorSpec>::NAME,
             ImplementedBehavior::Guard(_) => <Guard as BehaviorSpec>::NAME,
             ImplementedBehavior::Timeout(_) => <Timeout as BehaviorSpec>::NAME,
+            ImplementedBehavior::Parallel(_) => <Parallel as BehaviorSpec>::NAME,
+            ImplementedBehavior::Switch(_) => <Switch as BehaviorSpec>::NAME,
+            ImplementedBehavior::RetryUntilSuccess(_) => <RetryUntilSuccess as BehaviorSpec>::NAME,
+            ImplementedBehavior::Cooldown(_) => <Cooldown as BehaviorSpec>::NAME,
             ImplementedBehavior::Subtree(_) => "Subtree",
             ImplementedBehavior::AnotherTree(_) => "AnotherTree",
         }
@@ -154,6 +153,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(_) => <Delay as BehaviorSpec>::ICON,
             ImplementedBehavior::Guard(_) => <Guard as BehaviorSpec>::ICON,
             ImplementedBehavior::Timeout(_) => <Timeout as BehaviorSpec>::ICON,
+            ImplementedBehavior::Parallel(_) => <Parallel as BehaviorSpec>::ICON,
+            ImplementedBehavior::Switch(_) => <Switch as BehaviorSpec>::ICON,
+            ImplementedBehavior::RetryUntilSuccess(_) => <RetryUntilSuccess as BehaviorSpec>::ICON,
+            ImplementedBehavior::Cooldown(_) => <Cooldown as BehaviorSpec>::ICON,
             ImplementedBehavior::Subtree(_) => <Subtree<ImplementedBehavior> as BehaviorSpec>::ICON,
             ImplementedBehavior::AnotherTree(_) => <Subtree<DerivedBehavior> as BehaviorSpec>::ICON,
         }
@@ -173,6 +176,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(_) => <Delay as BehaviorSpec>::DESC,
             ImplementedBehavior::Guard(_) => <Guard as BehaviorSpec>::DESC,
             ImplementedBehavior::Timeout(_) => <Timeout as BehaviorSpec>::DESC,
+            ImplementedBehavior::Parallel(_) => <Parallel as BehaviorSpec>::DESC,
+            ImplementedBehavior::Switch(_) => <Switch as BehaviorSpec>::DESC,
+            ImplementedBehavior::RetryUntilSuccess(_) => <RetryUntilSuccess as BehaviorSpec>::DESC,
+            ImplementedBehavior::Cooldown(_) => <Cooldown as BehaviorSpec>::DESC,
             ImplementedBehavior::Subtree(_) => <Subtree<ImplementedBehavior> as BehaviorSpec>::DESC,
             ImplementedBehavior::AnotherTree(_) => <Subtree<DerivedBehavior> as BehaviorSpec>::DESC,
         }
@@ -192,6 +199,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(data) => data,
             ImplementedBehavior::Guard(data) => data,
             ImplementedBehavior::Timeout(data) => data,
+            ImplementedBehavior::Parallel(data) => data,
+            ImplementedBehavior::Switch(data) => data,
+            ImplementedBehavior::RetryUntilSuccess(data) => data,
+            ImplementedBehavior::Cooldown(data) => data,
             ImplementedBehavior::Subtree(data) => data,
             ImplementedBehavior::AnotherTree(data) => data,
         }
@@ -211,6 +222,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(data) => data,
             ImplementedBehavior::Guard(data) => data,
             ImplementedBehavior::Timeout(data) => data,
+            ImplementedBehavior::Parallel(data) => data,
+            ImplementedBehavior::Switch(data) => data,
+            ImplementedBehavior::RetryUntilSuccess(data) => data,
+            ImplementedBehavior::Cooldown(data) => data,
             ImplementedBehavior::Subtree(data) => data,
             ImplementedBehavior::AnotherTree(data) => data,
         }
@@ -235,6 +250,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(data) => data.ui(None, state, ui, type_registry),
             ImplementedBehavior::Guard(data) => data.ui(None, state, ui, type_registry),
             ImplementedBehavior::Timeout(data) => data.ui(None, state, ui, type_registry),
+            ImplementedBehavior::Parallel(data) => data.ui(None, state, ui, type_registry),
+            ImplementedBehavior::Switch(data) => data.ui(None, state, ui, type_registry),
+            ImplementedBehavior::RetryUntilSuccess(data) => data.ui(None, state, ui, type_registry),
+            ImplementedBehavior::Cooldown(data) => data.ui(None, state, ui, type_registry),
             ImplementedBehavior::Subtree(data) => data.ui(None, state, ui, type_registry),
             ImplementedBehavior::AnotherTree(data) => data.ui(None, state, ui, type_registry),
         }
@@ -263,6 +282,12 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(data) => data.ui_readonly(None, state, ui, type_registry),
             ImplementedBehavior::Guard(data) => data.ui_readonly(None, state, ui, type_registry),
             ImplementedBehavior::Timeout(data) => data.ui_readonly(None, state, ui, type_registry),
+            ImplementedBehavior::Parallel(data) => data.ui_readonly(None, state, ui, type_registry),
+            ImplementedBehavior::Switch(data) => data.ui_readonly(None, state, ui, type_registry),
+            ImplementedBehavior::RetryUntilSuccess(data) => {
+                data.ui_readonly(None, state, ui, type_registry)
+            }
+            ImplementedBehavior::Cooldown(data) => data.ui_readonly(None, state, ui, type_registry),
             ImplementedBehavior::Subtree(data) => data.ui_readonly(None, state, ui, type_registry),
             ImplementedBehavior::AnotherTree(data) => {
                 data.ui_readonly(None, state, ui, type_registry)
@@ -285,6 +310,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(data) => *data = world.get::<Delay>(entity).ok_or(BehaviorMissing)?.clone(),
             ImplementedBehavior::Guard(data) => *data = world.get::<Guard>(entity).ok_or(BehaviorMissing)?.clone(),
             ImplementedBehavior::Timeout(data) => *data = world.get::<Timeout>(entity).ok_or(BehaviorMissing)?.clone(),
+            ImplementedBehavior::Parallel(data) => *data = world.get::<Parallel>(entity).ok_or(BehaviorMissing)?.clone(),
+            ImplementedBehavior::Switch(data) => *data = world.get::<Switch>(entity).ok_or(BehaviorMissing)?.clone(),
+            ImplementedBehavior::RetryUntilSuccess(data) => *data = world.get::<RetryUntilSuccess>(entity).ok_or(BehaviorMissing)?.clone(),
+            ImplementedBehavior::Cooldown(data) => *data = world.get::<Cooldown>(entity).ok_or(BehaviorMissing)?.clone(),
             ImplementedBehavior::Subtree(data) => *data = world.get::<Subtree<ImplementedBehavior>>(entity).ok_or(BehaviorMissing)?.clone(),
             ImplementedBehavior::AnotherTree(data) => *data = world.get::<Subtree<DerivedBehavior>>(entity).ok_or(BehaviorMissing)?.clone(),
         };
@@ -305,6 +334,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(_) => <Delay as BehaviorSpec>::TYPE,
             ImplementedBehavior::Guard(_) => <Guard as BehaviorSpec>::TYPE,
             ImplementedBehavior::Timeout(_) => <Timeout as BehaviorSpec>::TYPE,
+            ImplementedBehavior::Parallel(_) => <Parallel as BehaviorSpec>::TYPE,
+            ImplementedBehavior::Switch(_) => <Switch as BehaviorSpec>::TYPE,
+            ImplementedBehavior::RetryUntilSuccess(_) => <RetryUntilSuccess as BehaviorSpec>::TYPE,
+            ImplementedBehavior::Cooldown(_) => <Cooldown as BehaviorSpec>::TYPE,
             ImplementedBehavior::Subtree(_) => <Subtree<ImplementedBehavior> as BehaviorSpec>::TYPE,
             ImplementedBehavior::AnotherTree(_) => <Subtree<DerivedBehavior> as BehaviorSpec>::TYPE,
         }
@@ -324,6 +357,10 @@ impl BehaviorFactory for ImplementedBehavior {
             ImplementedBehavior::Delay(Default::default()),
             ImplementedBehavior::Guard(Default::default()),
             ImplementedBehavior::Timeout(Default::default()),
+            ImplementedBehavior::Parallel(Default::default()),
+            ImplementedBehavior::Switch(Default::default()),
+            ImplementedBehavior::RetryUntilSuccess(Default::default()),
+            ImplementedBehavior::Cooldown(Default::default()),
             ImplementedBehavior::Subtree(Default::default()),
             ImplementedBehavior::AnotherTree(Default::default()),
         ]
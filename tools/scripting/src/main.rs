@@ -7,6 +7,7 @@ use simula_action::ActionPlugin;
 use simula_behavior::prelude::*;
 use simula_camera::orbitcam::*;
 use simula_inspector::{InspectorPlugin, WorldInspectorPlugin};
+use simula_script::ScriptWatchPlugin;
 use simula_viz::{
     axes::{Axes, AxesBundle, AxesPlugin},
     grid::{Grid, GridBundle, GridPlugin},
@@ -53,6 +54,7 @@ fn main() {
         .add_startup_system(scene_setup)
         // Behavior setup
         .add_plugin(BehaviorPlugin)
+        .add_plugin(ScriptWatchPlugin)
         // ImplementedBehavior setup
         .add_plugin(ImplementedBehaviorPlugin)
         .add_plugin(BehaviorServerPlugin::<ImplementedBehavior>::default())
@@ -105,7 +107,7 @@ fn behavior_setup<T: BehaviorFactory>(
             commands.spawn((
                 Name::new(format!("BHT: {}", behavior)),
                 behavior_handle.clone(),
-                BehaviorTree::<T>::default(),
+                BehaviorTree::<T>::with_hot_reload(),
                 BehaviorTreeReset::<T>::default(),
             ));
         }
@@ -149,6 +151,7 @@ fn scene_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 divisions: 10,
                 start_color: grid_color,
                 end_color: grid_color,
+                ..default()
             },
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
             ..Default::default()
@@ -86,6 +86,7 @@ fn setup(
                 divisions: 10,
                 start_color: grid_color,
                 end_color: grid_color,
+                ..default()
             },
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
             ..Default::default()
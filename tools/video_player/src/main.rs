@@ -76,6 +76,7 @@ fn setup(
                 divisions: 10,
                 start_color: Color::BLUE,
                 end_color: Color::RED,
+                ..default()
             },
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
             ..default()